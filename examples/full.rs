@@ -1,41 +1,50 @@
 use may_clack::{
-	cancel, confirm, error::ClackError, info, input, intro, multi_input, multi_select, outro,
-	select,
+	confirm, error::ClackError, info, input, multi_input, multi_select, select, session,
+	session::OnCancel,
 };
-use owo_colors::OwoColorize;
 
 fn main() -> Result<(), ClackError> {
-	println!();
-	intro!(" full ".reversed());
+	let mut session = session("full");
+	session
+		.cancel_message("demo cancelled")
+		.on_cancel(OnCancel::Exit(1))
+		.start();
 
 	info!("visit the documentation at https://docs.rs/may-clack");
 
-	let do_input = input("input")
-		.placeholder("placeholder")
-		.cancel(do_cancel)
-		.required()?;
-	let do_multi_input = multi_input("multi input")
-		.max(4)
-		.cancel(do_cancel)
-		.interact()?;
-	let do_confirm = confirm("confirm")
-		.prompts("true", "false")
-		.cancel(do_cancel)
-		.interact()?;
-	let do_multi_select = multi_select("multi select")
-		.option("opt1", "option 1")
-		.option("opt2", "option 2")
-		.option_hint("opt3", "option 3", "hint")
-		.cancel(do_cancel)
-		.interact()?;
-	let do_select = select("select")
-		.option("val1", "value 1")
-		.option("val2", "value 2")
-		.option_hint("val 3", "value 3", "hint")
-		.cancel(do_cancel)
-		.interact()?;
+	let do_input = session.prompt("input", |cx| {
+		input("input")
+			.placeholder("placeholder")
+			.context(cx)
+			.required()
+	})?;
+	let do_multi_input = session.prompt("multi_input", |cx| {
+		multi_input("multi input").max(4).context(cx).interact()
+	})?;
+	let do_confirm = session.prompt("confirm", |cx| {
+		confirm("confirm")
+			.prompts("true", "false")
+			.context(cx)
+			.interact()
+	})?;
+	let do_multi_select = session.prompt("multi_select", |cx| {
+		multi_select("multi select")
+			.option("opt1", "option 1")
+			.option("opt2", "option 2")
+			.option_hint("opt3", "option 3", "hint")
+			.context(cx)
+			.interact()
+	})?;
+	let do_select = session.prompt("select", |cx| {
+		select("select")
+			.option("val1", "value 1")
+			.option("val2", "value 2")
+			.option_hint("val 3", "value 3", "hint")
+			.context(cx)
+			.interact()
+	})?;
 
-	outro!();
+	drop(session);
 
 	println!("input {:?}", do_input);
 	println!("confirm {:?}", do_confirm);
@@ -45,8 +54,3 @@ fn main() -> Result<(), ClackError> {
 
 	Ok(())
 }
-
-fn do_cancel() {
-	cancel!("demo cancelled");
-	panic!("demo cancelled");
-}
@@ -1,9 +1,8 @@
-use may_clack::{cancel, confirm, error::ClackError, intro, multi_input, outro};
+use may_clack::{cancel, confirm, error::ClackError, intro::Intro, multi_input, outro};
 use owo_colors::OwoColorize;
 
 fn main() -> Result<(), ClackError> {
-	println!();
-	intro!(" generic messages ".reversed());
+	Intro::new("generic messages").print();
 
 	let number = confirm(20).cancel(do_cancel).interact()?;
 	let styled = multi_input("style".on_cyan())
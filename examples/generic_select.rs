@@ -1,5 +1,4 @@
-use may_clack::{cancel, error::ClackError, intro, multi_select, outro, select};
-use owo_colors::OwoColorize;
+use may_clack::{cancel, error::ClackError, intro::Intro, multi_select, outro, select};
 use std::fmt::Display;
 
 #[derive(Debug, Clone)]
@@ -20,13 +19,12 @@ impl Display for SelectEnum {
 }
 
 fn main() -> Result<(), ClackError> {
-	println!();
-	intro!(" generic select ".reversed());
+	Intro::new("generic select").print();
 
 	let select_enum = select("select enum")
-		.option(SelectEnum::One, SelectEnum::One)
-		.option(SelectEnum::Two, SelectEnum::Two)
-		.option(SelectEnum::Three, SelectEnum::Three)
+		.option_auto(SelectEnum::One)
+		.option_auto(SelectEnum::Two)
+		.option_auto(SelectEnum::Three)
 		.cancel(do_cancel)
 		.interact()?;
 
@@ -1,13 +1,11 @@
-use may_clack::{cancel, error::ClackError, intro, multi_select, outro, select};
-use owo_colors::OwoColorize;
+use may_clack::{cancel, error::ClackError, intro::Intro, multi_select, outro, select};
 
 /// Activating "less" mode activates a pager.
 ///
 /// With the value given to the `.less(val: i32)` function,
 /// you can decide the amount of options per page.
 fn main() -> Result<(), ClackError> {
-	println!();
-	intro!(" less ".reversed());
+	Intro::new("less").print();
 
 	let select_less = select("less")
 		.option("val 1", "value 1")
@@ -1,9 +1,7 @@
-use may_clack::{cancel, error::ClackError, input, intro, outro};
-use owo_colors::OwoColorize;
+use may_clack::{cancel, error::ClackError, input, intro::Intro, outro};
 
 fn main() -> Result<(), ClackError> {
-	println!();
-	intro!(" maybe_initial ".reversed());
+	Intro::new("maybe_initial").print();
 
 	let opt = Some("test");
 
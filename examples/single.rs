@@ -1,9 +1,7 @@
-use may_clack::{cancel, error::ClackError, intro, multi_select, outro, select};
-use owo_colors::OwoColorize;
+use may_clack::{cancel, error::ClackError, intro::Intro, multi_select, outro, select};
 
 fn main() -> Result<(), ClackError> {
-	println!();
-	intro!(" single ".reversed());
+	Intro::new("single").print();
 	let do_single_select = select("single")
 		.option("one", "one")
 		.cancel(do_cancel)
@@ -1,5 +1,4 @@
-use may_clack::{error::ClackError, input, intro, outro};
-use owo_colors::OwoColorize;
+use may_clack::{error::ClackError, input, intro::Intro, outro};
 use std::fmt::Display;
 
 struct Name;
@@ -11,8 +10,7 @@ impl Display for Name {
 }
 
 fn main() -> Result<(), ClackError> {
-	println!();
-	intro!(" to_string ".reversed());
+	Intro::new("to_string").print();
 
 	let int = input("int").initial_value(23).parse::<i32>()?;
 	let unit = input("struct").placeholder(Name).required()?;
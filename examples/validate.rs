@@ -1,10 +1,8 @@
-use may_clack::{cancel, error::ClackError, input, intro, multi_input, outro};
-use owo_colors::OwoColorize;
+use may_clack::{cancel, error::ClackError, input, intro::Intro, multi_input, outro};
 use std::{borrow::Cow, net::Ipv4Addr};
 
 fn main() -> Result<(), ClackError> {
-	println!();
-	intro!(" validate ".reversed());
+	Intro::new("validate").print();
 
 	let do_validate_input = input("validate single (only use ascii)")
 		.validate(|x| {
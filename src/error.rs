@@ -19,4 +19,24 @@ pub enum ClackError {
 	/// No options specified
 	#[error("no options specified")]
 	NoOptions,
+	/// Loading the options failed
+	#[error("failed to load options")]
+	Load(#[source] Box<dyn std::error::Error + Send + Sync>),
+	/// Two options have the same value
+	#[error("duplicate options at index {0} and {1}")]
+	DuplicateOptions(usize, usize),
+	/// The value was rejected as often as allowed with `max_attempts`
+	#[error("too many attempts: {last_error}")]
+	TooManyAttempts {
+		/// Error the last submission was rejected with
+		last_error: String,
+	},
+	/// An initial value can't be parsed
+	#[error("initial value {index} can't be parsed: {error}")]
+	InvalidInitialValue {
+		/// Index of the value
+		index: usize,
+		/// Error the value couldn't be parsed with
+		error: String,
+	},
 }
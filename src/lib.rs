@@ -16,10 +16,13 @@
 //!
 //! When the user cancels a question, you can use the [`cancel!`] utility to provide a cancellation message.
 //!
+//! To end the session after a cancelled or failed prompt, use [`outro_cancel!`] or [`outro_error!`].
+//!
 //! When cancelled the will return a [`error::ClackError::Cancelled`],
 //! or you can check if it was cancelled using the [`traits::IsCancel`] trait extension.
 //!
-//! All input types that can return a `Cancelled` Err will also have the option to add a `.cancel` closure
+//! All input types that can return a `Cancelled` Err will also have the option to add a `.cancel` closure,
+//! or a `.cancel_with` closure that gets what was entered before the prompt was cancelled.
 //!
 //! ```no_run
 //! use may_clack::{cancel, error::ClackError, input};
@@ -56,9 +59,50 @@
 //! outro!("outro");
 //! ```
 //!
+//! With the `log` feature, the [`log`](https://docs.rs/log) records can be written the same way with the `logger::Logger`.
+//! With the `tracing` feature, the `layer::Layer` does the same for [`tracing`](https://docs.rs/tracing) events and spans.
+//! Records logged while a prompt is active are written once it is finished.
+//!
+//! ## Session
+//!
+//! A [`session::Session`] opens and closes the prompt session for you, collects the answers
+//! and closes the session with a cancel message when a prompt is cancelled.
+//!
+//! ```no_run
+//! use may_clack::{confirm, input, session};
+//!
+//! # fn main() -> Result<(), may_clack::error::ClackError> {
+//! let mut session = session("create-app");
+//! let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+//! let git = session.prompt("git", |cx| confirm("initialize git?").context(cx).interact())?;
+//! session.outro("done");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! When the questions are only known at runtime, e.g. from plugins or a config file,
+//! the [`runner`] asks a list of prompts with different answer types in order.
+//!
+//! ```no_run
+//! use may_clack::{confirm, input, runner};
+//!
+//! # fn main() -> Result<(), may_clack::error::ClackError> {
+//! let prompts = vec![
+//!     runner::step("name", || input("name").required()),
+//!     runner::step("git", || confirm("initialize git?").interact()),
+//! ];
+//! let answers = runner::run(prompts)?;
+//! let git = answers.get::<bool>("git");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! For scripts that wrap the program, [`json_mode()`] moves the prompts to stderr
+//! and writes a JSON summary of the answers to stdout at the outro.
+//!
 //! ## General
 //!
-//! There are 6 components: [`input`](#input), [`confirm`](#confirm),
+//! There are 7 components: [`input`](#input), [`password`](#password), [`confirm`](#confirm),
 //! [`select`](#select), [`multi_select`](#multi_select), [`multi_input`](#multi_input)
 //!
 //! Each of the input types returns a struct, that allows you to setup the prompt.  
@@ -75,6 +119,42 @@
 //! # }
 //! ```
 //!
+//! A prompt can be asked again by calling `.interact()` on it again,
+//! and the closures passed to a prompt have to be `Send + Sync`,
+//! so a prompt can be set up on one thread and asked on another.
+//!
+//! ```no_run
+//! use may_clack::{error::ClackError, input, validate};
+//!
+//! # fn main() -> Result<(), ClackError> {
+//! let mut question = input("name");
+//! question.validator(validate::not_empty());
+//!
+//! let names = std::thread::spawn(move || {
+//!     // the same prompt is asked twice
+//!     let first = question.required()?;
+//!     let second = question.required()?;
+//!     Ok::<_, ClackError>((first, second))
+//! });
+//! let (first, second) = names.join().unwrap()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Markup
+//!
+//! Prompt messages and option hints support a tiny inline markup:
+//! `**bold**`, `_dim_` and `` `code` ``. Escape a marker with a backslash to print it as is.
+//!
+//! ```no_run
+//! use may_clack::confirm;
+//!
+//! # fn main() -> Result<(), may_clack::error::ClackError> {
+//! let answer = confirm("push to **main** with `--force`?").interact()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # Components
 //!
 //! ## Input
@@ -93,6 +173,21 @@
 //! # }
 //! ```
 //!
+//! ## Password
+//!
+//! The [`password::Password`] component accepts a single line of text, masked while it is typed.
+//!
+//! ```no_run
+//! use may_clack::password;
+//!
+//! # fn main() -> Result<(), may_clack::error::ClackError> {
+//! let answer = password("password")
+//!     .confirm("repeat password")
+//!     .interact()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## Confirm
 //!
 //! The [`confirm::Confirm`] component accepts a yes or no answer.
@@ -163,6 +258,33 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! ## Spinner
+//!
+//! The [`spinner::Spinner`] shows that something is happening, until it is stopped with a message.
+//!
+//! ```no_run
+//! use may_clack::spinner;
+//!
+//! let mut spinner = spinner();
+//! spinner.start("installing dependencies");
+//! // do stuff
+//! spinner.stop("installed dependencies");
+//! ```
+//!
+//! A [`spinner::TaskGroup`] runs several tasks with the state of each drawn in front of it,
+//! and finishes with a summary like `4 ok, 1 failed, 12.3s`.
+//!
+//! ```no_run
+//! use may_clack::task_group;
+//! use std::fs;
+//!
+//! let summary = task_group()
+//!     .task("creating cache", || fs::create_dir_all(".cache"))
+//!     .task("creating logs", || fs::create_dir_all(".logs"))
+//!     .parallel(true)
+//!     .run();
+//! ```
 
 #![warn(missing_docs)]
 
@@ -177,4 +299,8 @@ pub use prompt::confirm::confirm;
 pub use prompt::input::input;
 pub use prompt::multi_input::multi_input;
 pub use prompt::multi_select::multi_select;
+pub use prompt::password::password;
 pub use prompt::select::select;
+pub use prompt::session::session;
+pub use prompt::spinner::spinner;
+pub use prompt::spinner::task_group;
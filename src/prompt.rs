@@ -1,7 +1,172 @@
+pub mod cache;
 pub mod confirm;
+pub mod debug;
+pub mod draw;
 pub mod input;
+pub mod intro;
+#[cfg(feature = "tracing")]
+pub mod layer;
+#[cfg(feature = "log")]
+pub mod logger;
 pub mod multi_input;
 pub mod multi_select;
+pub mod outro;
+pub mod password;
+pub mod runner;
 pub mod select;
+pub mod session;
+pub mod spinner;
+pub mod token;
+pub mod transcript;
+pub mod validate;
 
+mod editor;
+mod frame;
+mod list;
+mod markup;
 mod misc;
+mod render;
+mod summary;
+mod text;
+
+pub use misc::{__message, outro_cancel, outro_error};
+
+/// Write the prompts to stderr and a JSON summary of the answers to stdout, for scripts that wrap the program.
+///
+/// The prompts are used like before, but are cleared once they are submitted.
+/// Every [`outro!`](crate::outro!), [`outro_cancel!`](crate::outro_cancel!) and [`outro_error!`](crate::outro_error!)
+/// writes a summary of the prompts since the last one as a single line to stdout, like:
+///
+/// ```text
+/// {"status":"done","prompts":[{"message":"name","answer":"may","cancelled":false}]}
+/// ```
+///
+/// The status is `done`, `cancelled` or `error`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{input, intro, json_mode, outro};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// json_mode(true);
+///
+/// intro!("create-app");
+/// let name = input("name").required()?;
+/// outro!();
+/// # Ok(())
+/// # }
+/// ```
+pub fn json_mode(enabled: bool) {
+	summary::set_enabled(enabled);
+}
+
+/// Write the prompts and all decorative output, like the intro, outro and messages, to stderr,
+/// so stdout only gets the results the program prints itself and stays machine-parseable.
+///
+/// Unlike [`json_mode()`], the prompts are drawn like before and nothing is written to stdout.
+/// The output stays on stderr while either mode is enabled, so disabling one doesn't undo the other.
+/// The line editor of the text prompts reads from and writes to the terminal directly.
+///
+/// A [`Session`](session::Session) can enable it for its prompts with [`Session::stderr()`](session::Session::stderr()).
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{input, intro, outro, stderr_mode};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// stderr_mode(true);
+///
+/// intro!("create-app");
+/// let name = input("name").required()?;
+/// outro!();
+///
+/// println!("{}", name);
+/// # Ok(())
+/// # }
+/// ```
+pub fn stderr_mode(enabled: bool) {
+	frame::set_stderr(enabled);
+}
+
+/// Leave out the decorative bars around the prompts and messages, for wizards that run inside logs or over slow links.
+///
+/// The prompts are drawn like before while they are active,
+/// but once they are finished only a `message: answer` line is left of them.
+/// The intro, outro and messages are written without the bars around them.
+///
+/// A [`Session`](session::Session) can enable it for its prompts with [`Session::quiet()`](session::Session::quiet()).
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{confirm, input, intro, outro, quiet_mode};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// quiet_mode(true);
+///
+/// intro!("create-app");
+/// // name: my-app
+/// let name = input("name").required()?;
+/// // initialize git?: yes
+/// let git = confirm("initialize git?").interact()?;
+/// outro!("done");
+/// # Ok(())
+/// # }
+/// ```
+pub fn quiet_mode(enabled: bool) {
+	frame::set_quiet(enabled);
+}
+
+/// Mirror the prompts and messages for right-to-left CLIs.
+///
+/// The bars and symbols are drawn on the right edge of the terminal, with the text right-aligned next to them.
+/// The line that is being edited in [`input`](input::Input) and [`multi_input`](multi_input::MultiInput) prompts
+/// is still drawn from the left by the line editor.
+///
+/// Right-to-left labels and messages are isolated from the symbols around them either way,
+/// so they don't get reordered by terminals that support bidirectional text.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{rtl, select};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// rtl(true);
+///
+/// let answer = select("בחר שפה")
+///     .option("he", "עברית")
+///     .option("ar", "العربية")
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn rtl(enabled: bool) {
+	frame::set_rtl(enabled);
+}
+
+/// Draw the ascii versions of the [chars](crate::style::chars), even if the terminal seems to support unicode.
+///
+/// Fixes garbled glyphs where the unicode detection is wrong, e.g. over ssh or in tmux.
+/// Setting the `MAY_CLACK_ASCII` environment variable to `1` does the same without changing the code.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{force_ascii, select};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// force_ascii(true);
+///
+/// let answer = select("select")
+///     .option("val1", "value 1")
+///     .option("val2", "value 2")
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn force_ascii(enabled: bool) {
+	crate::style::set_ascii(enabled);
+}
@@ -0,0 +1,361 @@
+//! Answer cache
+
+use super::transcript::json;
+use std::{
+	any::{Any, TypeId},
+	fs, io,
+	path::{Path, PathBuf},
+};
+
+/// An answer saved in a [`Cache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cached {
+	/// Answer of an [`input`](crate::input::Input), [`password`](crate::password::Password)
+	/// or a [`select`](crate::select::Select) with string values.
+	Text(String),
+	/// Answer of a [`confirm`](crate::confirm::Confirm).
+	Bool(bool),
+	/// Answer of a [`multi_input`](crate::multi_input::MultiInput)
+	/// or a [`multi_select`](crate::multi_select::MultiSelect) with string values.
+	List(Vec<String>),
+}
+
+impl Cached {
+	/// The text, if the answer is text.
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Cached::Text(text) => Some(text),
+			_ => None,
+		}
+	}
+
+	/// The bool, if the answer is a bool.
+	pub fn as_bool(&self) -> Option<bool> {
+		match *self {
+			Cached::Bool(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// The list, if the answer is a list.
+	pub fn as_list(&self) -> Option<&[String]> {
+		match self {
+			Cached::List(list) => Some(list),
+			_ => None,
+		}
+	}
+
+	/// Convert an answer, if it is of a type that can be cached.
+	///
+	/// Returns `Some(None)` for answers that are cached as no answer, like an empty optional input.
+	pub(super) fn from_answer(answer: &dyn Any) -> Option<Option<Cached>> {
+		if let Some(text) = answer.downcast_ref::<String>() {
+			Some(Some(Cached::Text(text.clone())))
+		} else if let Some(&text) = answer.downcast_ref::<&str>() {
+			Some(Some(Cached::Text(text.into())))
+		} else if let Some(text) = answer.downcast_ref::<Option<String>>() {
+			Some(text.clone().map(Cached::Text))
+		} else if let Some(&value) = answer.downcast_ref::<bool>() {
+			Some(Some(Cached::Bool(value)))
+		} else if let Some(list) = answer.downcast_ref::<Vec<String>>() {
+			Some(Some(Cached::List(list.clone())))
+		} else if let Some(list) = answer.downcast_ref::<Vec<&str>>() {
+			let list = list.iter().map(|&text| text.into()).collect();
+			Some(Some(Cached::List(list)))
+		} else {
+			None
+		}
+	}
+
+	/// Convert to an answer of the type `T`, if it is a type that can be cached.
+	///
+	/// Text is converted to bools like `true`, `yes` or `1`, and to lists by splitting it at commas,
+	/// so values from environment variables can be used as any of them.
+	pub(super) fn to_answer<T: Any>(&self) -> Option<T> {
+		let answer: Box<dyn Any> = match self {
+			_ if TypeId::of::<T>() == TypeId::of::<String>() => Box::new(self.as_str()?.to_owned()),
+			_ if TypeId::of::<T>() == TypeId::of::<Option<String>>() => {
+				Box::new(Some(self.as_str()?.to_owned()))
+			}
+			Cached::Bool(value) => Box::new(*value),
+			Cached::List(list) => Box::new(list.clone()),
+			Cached::Text(text) if TypeId::of::<T>() == TypeId::of::<bool>() => {
+				let value = match &*text.to_lowercase() {
+					"true" | "yes" | "y" | "1" => true,
+					"false" | "no" | "n" | "0" => false,
+					_ => return None,
+				};
+				Box::new(value)
+			}
+			Cached::Text(text) => {
+				let list = text.split(',').map(|item| item.trim().to_owned());
+				Box::new(list.filter(|item| !item.is_empty()).collect::<Vec<_>>())
+			}
+		};
+
+		answer.downcast().ok().map(|answer| *answer)
+	}
+
+	/// Whether the answer is the `value`, for prompts that pre-fill it by looking it up in their options.
+	pub(super) fn is<T: Any>(&self, value: &T) -> bool {
+		let value = value as &dyn Any;
+		match self {
+			Cached::Text(text) => text_of(value).is_some_and(|value| value == text),
+			Cached::Bool(answer) => value.downcast_ref::<bool>() == Some(answer),
+			Cached::List(_) => false,
+		}
+	}
+
+	/// Whether the answer is a list containing the `value`.
+	pub(super) fn contains<T: Any>(&self, value: &T) -> bool {
+		let Some(value) = text_of(value as &dyn Any) else {
+			return false;
+		};
+
+		self.as_list()
+			.is_some_and(|list| list.iter().any(|item| item == value))
+	}
+
+	/// The answer as it is drawn.
+	pub(super) fn to_text(&self) -> String {
+		match self {
+			Cached::Text(text) => text.clone(),
+			Cached::Bool(value) => value.to_string(),
+			Cached::List(list) => list.join(", "),
+		}
+	}
+
+	fn to_value(&self) -> String {
+		match self {
+			Cached::Text(text) => json(text),
+			Cached::Bool(value) => value.to_string(),
+			Cached::List(list) => {
+				let list = list.iter().map(|text| json(text)).collect::<Vec<_>>();
+				format!("[{}]", list.join(", "))
+			}
+		}
+	}
+
+	fn parse_value(value: &str) -> Option<Cached> {
+		match value {
+			"true" => Some(Cached::Bool(true)),
+			"false" => Some(Cached::Bool(false)),
+			_ if value.starts_with('"') => match parse_string(value)? {
+				(text, "") => Some(Cached::Text(text)),
+				_ => None,
+			},
+			_ => {
+				let mut rest = value.strip_prefix('[')?.trim_start();
+				let mut list = vec![];
+				while !rest.starts_with(']') {
+					let (text, after) = parse_string(rest)?;
+					list.push(text);
+
+					rest = after.trim_start();
+					rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+				}
+
+				(rest == "]").then_some(Cached::List(list))
+			}
+		}
+	}
+}
+
+/// The text of a `String` or `&str` value.
+fn text_of(value: &dyn Any) -> Option<&str> {
+	value
+		.downcast_ref::<String>()
+		.map(String::as_str)
+		.or_else(|| value.downcast_ref::<&str>().copied())
+}
+
+/// A cached answer a prompt with options is pre-filled with, and how it is looked up in the options.
+pub(super) struct Prefill<T> {
+	cached: Cached,
+	matches: fn(&Cached, &T) -> bool,
+}
+
+impl<T> Prefill<T> {
+	/// Pre-fill the option that is the cached answer.
+	pub(super) fn answer(cached: &Cached) -> Prefill<T>
+	where
+		T: Any,
+	{
+		let cached = cached.clone();
+		Prefill {
+			cached,
+			matches: Cached::is,
+		}
+	}
+
+	/// Pre-fill every option in the cached list.
+	pub(super) fn list(cached: &Cached) -> Prefill<T>
+	where
+		T: Any,
+	{
+		let cached = cached.clone();
+		Prefill {
+			cached,
+			matches: Cached::contains,
+		}
+	}
+
+	/// Whether the option with the `value` is pre-filled.
+	pub(super) fn matches(&self, value: &T) -> bool {
+		(self.matches)(&self.cached, value)
+	}
+}
+
+/// Parse a `key = value` line, with a quoted or bare key.
+fn parse_line(line: &str) -> Option<(String, Cached)> {
+	let (key, rest) = if line.starts_with('"') {
+		parse_string(line)?
+	} else {
+		let end = line
+			.find(|c: char| !is_bare_key(c))
+			.filter(|&end| end > 0)?;
+		(line[..end].to_owned(), &line[end..])
+	};
+
+	let value = rest.trim_start().strip_prefix('=')?.trim();
+	Some((key, Cached::parse_value(value)?))
+}
+
+/// Whether the char can be used in a key without quotes.
+fn is_bare_key(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Parse a quoted string at the start of `value`, and return it with the rest of the value.
+fn parse_string(value: &str) -> Option<(String, &str)> {
+	let mut chars = value.strip_prefix('"')?.char_indices();
+	let mut text = String::new();
+	while let Some((i, c)) = chars.next() {
+		match c {
+			'"' => return Some((text, &value[i + 2..])),
+			'\\' => match chars.next()?.1 {
+				'n' => text.push('\n'),
+				'r' => text.push('\r'),
+				't' => text.push('\t'),
+				'u' => {
+					let hex = (0..4).map(|_| chars.next().map(|(_, c)| c));
+					let hex = hex.collect::<Option<String>>()?;
+					text.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+				}
+				c => text.push(c),
+			},
+			c => text.push(c),
+		}
+	}
+
+	None
+}
+
+/// `Cache` struct.
+///
+/// Saves the last answers of named prompts in a file, so they can be pre-filled the next time.
+///
+/// The file has a `key = value` line for every answer, and lines starting with `#` are comments.
+/// Text is quoted like a JSON string, bools are `true` or `false`,
+/// and lists are quoted strings between `[` and `]`, separated by commas.
+/// It is usually used through [`Session::cache()`](crate::session::Session::cache()).
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{cache::Cache, input};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut cache = Cache::open(".answers");
+/// let last = cache.get("name").and_then(|name| name.as_str()).unwrap_or_default();
+///
+/// let name = input("name").initial_value(last).required()?;
+/// cache.set("name", &name);
+/// cache.save()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cache {
+	path: PathBuf,
+	answers: Vec<(String, Cached)>,
+}
+
+impl Cache {
+	/// Open the cache saved at the `path`.
+	///
+	/// If the file doesn't exist or can't be read, the cache starts out empty.
+	/// Lines that can't be read are left out.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::cache::{Cache, Cached};
+	///
+	/// let path = std::env::temp_dir().join("may-clack-cache-doctest");
+	/// std::fs::write(&path, "name = \"may \\\"clack\\\"\"\ngit = true\ntags = [\"cli\", \"tui\"]\n")?;
+	///
+	/// let cache = Cache::open(&path);
+	/// assert_eq!(cache.get("name"), Some(&Cached::Text("may \"clack\"".into())));
+	/// assert_eq!(cache.get("git").and_then(Cached::as_bool), Some(true));
+	/// assert_eq!(cache.get("tags").and_then(Cached::as_list), Some(&["cli".to_owned(), "tui".to_owned()][..]));
+	/// # std::fs::remove_file(&path)?;
+	/// # Ok::<_, std::io::Error>(())
+	/// ```
+	pub fn open<P: AsRef<Path>>(path: P) -> Cache {
+		let path = path.as_ref().to_owned();
+		let file = fs::read_to_string(&path).unwrap_or_default();
+
+		let mut answers = vec![];
+		for line in file.lines().map(str::trim) {
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			if let Some(answer) = parse_line(line) {
+				answers.push(answer);
+			}
+		}
+
+		Cache { path, answers }
+	}
+
+	/// Get the answer saved under the `key`.
+	pub fn get(&self, key: &str) -> Option<&Cached> {
+		self.answers
+			.iter()
+			.find(|(k, _)| k == key)
+			.map(|(_, answer)| answer)
+	}
+
+	/// Save the `answer` under the `key`, if it is of a type that can be cached.
+	///
+	/// Strings, bools and vectors of strings can be cached,
+	/// an empty optional answer removes the saved answer.
+	///
+	/// The file isn't written until [`Cache::save()`] is called.
+	pub fn set<T: Any>(&mut self, key: &str, answer: &T) {
+		if let Some(answer) = Cached::from_answer(answer) {
+			self.answers.retain(|(k, _)| k != key);
+			if let Some(answer) = answer {
+				self.answers.push((key.into(), answer));
+			}
+		}
+	}
+
+	/// Write the answers to the file.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the file can't be written.
+	pub fn save(&self) -> io::Result<()> {
+		let mut file = String::from("# answers saved by may-clack\n");
+		for (key, answer) in &self.answers {
+			let is_bare = !key.is_empty() && key.chars().all(is_bare_key);
+			let key = if is_bare { key.clone() } else { json(key) };
+			file.push_str(&format!("{} = {}\n", key, answer.to_value()));
+		}
+
+		fs::write(&self.path, file)
+	}
+}
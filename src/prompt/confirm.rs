@@ -1,18 +1,24 @@
 //! Confirm
 
-use crate::{
-	error::ClackError,
-	style::{ansi, chars},
+use super::{
+	cache::Cached,
+	frame,
+	input::CancelReturnFn,
+	markup,
+	render::{self, RenderFn, Renderer},
+	session::Context,
+	transcript,
 };
+use crate::{error::ClackError, style::chars};
 use crossterm::{
-	cursor,
-	event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
-	execute, terminal,
+	event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+	terminal,
 };
 use owo_colors::OwoColorize;
 use std::{
 	fmt::Display,
-	io::{stdout, Write},
+	sync::Arc,
+	time::{Duration, Instant},
 };
 
 /// `Confirm` struct.
@@ -33,9 +39,19 @@ use std::{
 /// ```
 pub struct Confirm<M: Display> {
 	message: M,
+	extra: Option<String>,
+	help: Option<String>,
 	initial_value: bool,
 	prompts: (String, String),
-	cancel: Option<Box<dyn Fn()>>,
+	keys: Option<(char, char)>,
+	default_after: Option<Duration>,
+	compact: bool,
+	cancel: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+	cancel_return: Option<Arc<CancelReturnFn<bool>>>,
+	format: Option<Arc<dyn Fn(bool) -> String + Send + Sync>>,
+	on_render: Option<Arc<RenderFn>>,
+	context: Context,
+	on_submit: Option<Arc<dyn Fn(bool) + Send + Sync>>,
 }
 
 impl<M: Display> Confirm<M> {
@@ -55,9 +71,19 @@ impl<M: Display> Confirm<M> {
 	pub fn new(message: M) -> Confirm<M> {
 		Confirm {
 			message,
+			extra: None,
+			help: None,
 			initial_value: false,
 			prompts: ("yes".into(), "no".into()),
+			keys: None,
+			default_after: None,
+			compact: false,
 			cancel: None,
+			cancel_return: None,
+			format: None,
+			on_render: None,
+			context: Context::default(),
+			on_submit: None,
 		}
 	}
 
@@ -96,11 +122,256 @@ impl<M: Display> Confirm<M> {
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn prompts<S: ToString>(&mut self, yes: S, no: S) -> &mut Self {
+	pub fn prompts<Y: ToString, N: ToString>(&mut self, yes: Y, no: N) -> &mut Self {
 		self.prompts = (yes.to_string(), no.to_string());
 		self
 	}
 
+	/// Specify the prompt to display for [`true`], keeping the one for [`false`].
+	///
+	/// Default: `"yes"`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("overwrite the file?").yes_label("overwrite").interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn yes_label<S: ToString>(&mut self, yes: S) -> &mut Self {
+		self.prompts.0 = yes.to_string();
+		self
+	}
+
+	/// Specify the prompt to display for [`false`], keeping the one for [`true`].
+	///
+	/// Default: `"no"`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("overwrite the file?").no_label("keep").interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn no_label<S: ToString>(&mut self, no: S) -> &mut Self {
+		self.prompts.1 = no.to_string();
+		self
+	}
+
+	/// Specify the keys that answer with [`true`] and [`false`] directly.
+	///
+	/// Default: the first letters of the prompts, like `y` and `n`.
+	/// If both prompts start with the same letter, there are no such keys unless they are set here.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("weiter?")
+	///     .prompts("ja", "nein")
+	///     .keys('j', 'n')
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn keys(&mut self, yes: char, no: char) -> &mut Self {
+		self.keys = Some((yes, no));
+		self
+	}
+
+	/// Submit the initial value after the `duration`, if no key was pressed until then.
+	///
+	/// The time that is left is counted down next to the help line.
+	/// Pressing any key stops the countdown.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	/// use std::time::Duration;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("reboot now?")
+	///     .initial_value(true)
+	///     .default_after(Duration::from_secs(10))
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn default_after(&mut self, duration: Duration) -> &mut Self {
+		self.default_after = Some(duration);
+		self
+	}
+
+	/// Draw the prompts and the answer on the same line as the message.
+	///
+	/// Default: `false`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("continue?").compact(true).interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn compact(&mut self, compact: bool) -> &mut Self {
+		self.compact = compact;
+		self
+	}
+
+	/// Add secondary lines below the message, drawn dimmed while the prompt is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("delete the branch?")
+	///     .message_extra("this can not be undone")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn message_extra<S: ToString>(&mut self, extra: S) -> &mut Self {
+		self.extra = Some(extra.to_string());
+		self
+	}
+
+	/// Show a dimmed help line at the end of the prompt while it is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("continue?")
+	///     .help("y or n to answer directly")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn help<S: ToString>(&mut self, help: S) -> &mut Self {
+		self.help = Some(help.to_string());
+		self
+	}
+
+	/// Change what is printed after submitting, instead of the prompt of the answer.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("delete the files?")
+	///     .format_submitted(|yes| if yes { "deleting".into() } else { "keeping".into() })
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn format_submitted<F>(&mut self, format: F) -> &mut Self
+	where
+		F: Fn(bool) -> String + Send + Sync + 'static,
+	{
+		self.format = Some(Arc::new(format));
+		self
+	}
+
+	/// Call `on_render` with the lines of every frame that is drawn, e.g. to record the prompt in tests.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	/// use std::sync::{Arc, Mutex};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let frames = Arc::new(Mutex::new(vec![]));
+	/// let recorded = Arc::clone(&frames);
+	/// let answer = confirm("continue?")
+	///     .on_render(move |lines| recorded.lock().unwrap().push(lines.to_vec()))
+	///     .interact()?;
+	/// println!("{} frames", frames.lock().unwrap().len());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_render<F>(&mut self, on_render: F) -> &mut Self
+	where
+		F: Fn(&[String]) + Send + Sync + 'static,
+	{
+		self.on_render = Some(Arc::new(on_render));
+		self
+	}
+
+	/// Draw the prompt as part of a [`Session`](crate::session::Session),
+	/// with the [`Context`] the session passes to [`Session::prompt()`](crate::session::Session::prompt()).
+	///
+	/// The prompt is pre-filled with its [cached](crate::session::Session::cache()) answer,
+	/// which takes precedence over the [initial value](Confirm::initial_value()).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.steps(1);
+	///
+	/// let answer = session.prompt("continue", |cx| confirm("continue?").context(cx).interact())?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn context(&mut self, cx: &Context) -> &mut Self {
+		self.context = cx.clone();
+		self
+	}
+
+	/// Call `on_submit` with the answer when the prompt is submitted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("continue?")
+	///     .on_submit(|yes| println!("continue {}", yes))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_submit<F>(&mut self, on_submit: F) -> &mut Self
+	where
+		F: Fn(bool) + Send + Sync + 'static,
+	{
+		self.on_submit = Some(Arc::new(on_submit));
+		self
+	}
+
 	/// Specify function to call on cancel.
 	///
 	/// # Examples
@@ -120,13 +391,59 @@ impl<M: Display> Confirm<M> {
 	/// }
 	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
 	where
-		F: Fn() + 'static,
+		F: Fn() + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(move |_| cancel());
+		self.cancel = Some(cancel);
+		self
+	}
+
+	/// Like [`Confirm::cancel()`], but the function gets the answer that was selected when it was cancelled.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = confirm("delete the backups?")
+	///     .cancel_with(|selected| eprintln!("cancelled with {} selected", selected))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cancel_with<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn(bool) + Send + Sync + 'static,
 	{
-		let cancel = Box::new(cancel);
+		let cancel = Arc::new(cancel);
 		self.cancel = Some(cancel);
 		self
 	}
 
+	/// Return the answer of the `cancel_return` function instead of an `Err` when the prompt is cancelled.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// // ctrl+c answers no
+	/// let telemetry = confirm("send telemetry?")
+	///     .on_cancel_return(|| false)
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_cancel_return<F>(&mut self, cancel_return: F) -> &mut Self
+	where
+		F: Fn() -> bool + Send + Sync + 'static,
+	{
+		self.cancel_return = Some(Arc::new(cancel_return));
+		self
+	}
+
 	/// Wait for the user to submit an answer.
 	///
 	/// # Examples
@@ -144,58 +461,148 @@ impl<M: Display> Confirm<M> {
 	/// # }
 	/// ```
 	pub fn interact(&self) -> Result<bool, ClackError> {
-		self.w_init();
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		r.hide_cursor();
+
+		let keys = self.answer_keys();
+		let keys = keys.map(|(yes, no)| {
+			let prompts = format!("{}/{}", self.prompts.0, self.prompts.1);
+			(format!("{}/{}", yes, no), prompts)
+		});
+
+		let mut footer = vec![(*chars::ARROWS_LEFT_RIGHT, "switch")];
+		if let Some((keys, prompts)) = &keys {
+			footer.push((keys, prompts));
+		}
+		footer.extend([("enter", "submit"), ("?", "hide keys")]);
+		r.keys(&footer);
+
+		let mut val = self.initial();
+		let mut deadline = self.default_after.map(|after| Instant::now() + after);
+		self.draw(&mut r, val, deadline);
 
-		let mut stdout = stdout();
-		let _ = execute!(stdout, crossterm::cursor::Hide);
 		terminal::enable_raw_mode()?;
 
-		let mut val = self.initial_value;
 		loop {
-			if let Event::Key(key) = event::read()? {
+			frame::flush()?;
+
+			let left = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+			if left.is_some_and(|left| left.is_zero()) {
+				terminal::disable_raw_mode()?;
+				self.w_out(&mut r, self.initial());
+				return Ok(self.initial());
+			}
+
+			let Some(event) = r.read_event(left.map(|left| left.min(render::TICK)))? else {
+				self.draw(&mut r, val, deadline);
+				continue;
+			};
+
+			if let Event::Resize(width, height) = event {
+				r.resize(width, height);
+				self.draw(&mut r, val, deadline);
+			}
+
+			if let Event::Key(key) = event {
 				if key.kind == KeyEventKind::Press {
+					deadline = None;
+
 					match (key.code, key.modifiers) {
 						(KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right, _) => {
 							val = !val;
-							self.draw(val);
+							self.draw(&mut r, val, deadline);
 						}
-						(KeyCode::Char('y' | 'Y'), _) => {
-							let _ = execute!(stdout, crossterm::cursor::Show);
-							terminal::disable_raw_mode()?;
-							self.w_out(true);
-							return Ok(true);
+						(KeyCode::Char('?'), _) if r.has_footer() => {
+							r.toggle_footer();
+							self.draw(&mut r, val, deadline);
 						}
-						(KeyCode::Char('n' | 'N'), _) => {
-							let _ = execute!(stdout, crossterm::cursor::Show);
+						(KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT)
+							if self.key_value(c).is_some() =>
+						{
+							let value = self.key_value(c) == Some(true);
 							terminal::disable_raw_mode()?;
-							self.w_out(false);
-							return Ok(false);
+							self.w_out(&mut r, value);
+							return Ok(value);
 						}
 						(KeyCode::Enter, _) => {
-							let _ = execute!(stdout, crossterm::cursor::Show);
 							terminal::disable_raw_mode()?;
-							self.w_out(val);
+							self.w_out(&mut r, val);
 							return Ok(val);
 						}
 						(KeyCode::Char('c' | 'd'), KeyModifiers::CONTROL) => {
-							let _ = execute!(stdout, crossterm::cursor::Show);
 							terminal::disable_raw_mode()?;
-							self.w_cancel(val);
+							self.w_cancel(&mut r, val);
 							if let Some(cancel) = self.cancel.as_deref() {
-								cancel();
+								cancel(val);
+							}
+
+							if let Some(cancel_return) = self.cancel_return.as_deref() {
+								return Ok(cancel_return());
 							}
 
 							return Err(ClackError::Cancelled);
 						}
-						_ => {}
+						_ => self.draw(&mut r, val, deadline),
 					}
 				}
 			}
 		}
 	}
+
+	/// Draw the prompt as if the `value` was submitted, without waiting for the user.
+	///
+	/// Useful to show the answers of an earlier run as static lines before the first live prompt.
+	/// The [`on_submit`](Confirm::on_submit()) hook isn't called,
+	/// and the answer isn't added to the [transcript](crate::transcript) or the [`json_mode()`](crate::json_mode) summary.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::confirm;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// confirm("install dependencies?").render_submitted(true);
+	/// let answer = confirm("initialize a git repository?").interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn render_submitted(&self, value: bool) {
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_submitted(&mut r, value, true);
+	}
 }
 
 impl<M: Display> Confirm<M> {
+	/// Keys that answer with [`true`] and [`false`], lowercase.
+	fn answer_keys(&self) -> Option<(char, char)> {
+		let first = |prompt: &str| prompt.chars().next();
+		let (yes, no) = match self.keys {
+			Some(keys) => keys,
+			None => (first(&self.prompts.0)?, first(&self.prompts.1)?),
+		};
+
+		let (yes, no) = (lowercase(yes), lowercase(no));
+		(yes != no).then_some((yes, no))
+	}
+
+	/// Answer of the key `c`, if it is one of the answer keys.
+	fn key_value(&self, c: char) -> Option<bool> {
+		let (yes, no) = self.answer_keys()?;
+		let c = lowercase(c);
+
+		if c == yes {
+			Some(true)
+		} else if c == no {
+			Some(false)
+		} else {
+			None
+		}
+	}
+
 	/// Format a radio point.
 	fn radio_pnt(&self, is_active: bool, prompt: &str) -> String {
 		if is_active {
@@ -215,60 +622,153 @@ impl<M: Display> Confirm<M> {
 		format!("{} / {}", yes, no)
 	}
 
+	/// The initial value, or the cached answer if there is one.
+	fn initial(&self) -> bool {
+		let cached = self.context.cached().and_then(Cached::as_bool);
+		cached.unwrap_or(self.initial_value)
+	}
+
+	/// Help line, with the time that is left until the `deadline`.
+	fn fmt_help(&self, deadline: Option<Instant>) -> Option<String> {
+		let Some(deadline) = deadline else {
+			return self.help.clone();
+		};
+
+		let left = deadline.saturating_duration_since(Instant::now());
+		let secs = left.as_millis().div_ceil(1000);
+		let answer = if self.initial() {
+			&self.prompts.0
+		} else {
+			&self.prompts.1
+		};
+
+		let countdown = format!("{} in {}s", answer, secs);
+		match &self.help {
+			Some(help) => Some(format!("{}  {}", help, countdown)),
+			None => Some(countdown),
+		}
+	}
+
 	/// Draw the prompt.
-	fn draw(&self, value: bool) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToColumn(0));
+	fn draw(&self, r: &mut Renderer, value: bool, deadline: Option<Instant>) {
+		if self.compact {
+			let mut line = self.radio(value);
+			if let Some(help) = self.fmt_help(deadline) {
+				line = format!("{}  {}", line, markup::render(&help, true));
+			}
 
-		let r = self.radio(value);
-		print!("{}  {}", (*chars::BAR).cyan(), r);
-		let _ = stdout.flush();
+			let step = (*chars::STEP_ACTIVE).cyan();
+			let bar = (*chars::BAR).cyan();
+			let (lines, row) = self.compact_lines(r, step, bar, &line, self.extra.as_deref());
+			r.draw(lines, row);
+			return;
+		}
+
+		let mut lines = vec![chars::BAR.to_string()];
+		lines.extend(r.message(
+			(*chars::STEP_ACTIVE).cyan(),
+			(*chars::BAR).cyan(),
+			&self.message,
+			self.extra.as_deref(),
+		));
+
+		let row = lines.len();
+		lines.push(format!("{}  {}", (*chars::BAR).cyan(), self.radio(value)));
+		lines.push(render::end(self.fmt_help(deadline).as_deref()));
+
+		r.draw(lines, row);
 	}
-}
 
-impl<M: Display> Confirm<M> {
-	/// Write initial prompt.
-	fn w_init(&self) {
-		println!("{}", *chars::BAR);
-		println!("{}  {}", (*chars::STEP_ACTIVE).cyan(), self.message);
-		println!("{}", (*chars::BAR).cyan());
-		print!("{}", (*chars::BAR_END).cyan());
+	/// Lines of the compact layout, with `after` on the last line of the message,
+	/// and the row of that line.
+	fn compact_lines<S: Display, B: Display>(
+		&self,
+		r: &Renderer,
+		step: S,
+		bar: B,
+		after: &str,
+		extra: Option<&str>,
+	) -> (Vec<String>, usize) {
+		let mut lines = vec![chars::BAR.to_string()];
+		lines.extend(r.message(step, bar, &self.message, extra));
 
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(1));
+		let row = lines.len() - extra.map_or(0, |extra| extra.lines().count()) - 1;
+		lines[row] = format!("{}  {}", lines[row], after);
 
-		self.draw(self.initial_value);
+		(lines, row)
 	}
+}
 
+impl<M: Display> Confirm<M> {
 	/// Write outro prompt.
-	fn w_out(&self, value: bool) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(1));
+	fn w_out(&self, r: &mut Renderer, value: bool) {
+		self.w_submitted(r, value, false);
 
-		let answer = if value {
-			&self.prompts.0
+		if let Some(on_submit) = self.on_submit.as_deref() {
+			on_submit(value);
+		}
+	}
+
+	fn w_submitted(&self, r: &mut Renderer, value: bool, preview: bool) {
+		let answer = match self.format.as_deref() {
+			Some(format) => format(value),
+			None if value => self.prompts.0.clone(),
+			None => self.prompts.1.clone(),
+		};
+		if preview {
+			transcript::preview(&self.message, &answer);
 		} else {
-			&self.prompts.1
+			transcript::prompt(&self.message, Some(&answer));
+		}
+
+		let step = (*chars::STEP_SUBMIT).green();
+		let lines = if self.compact {
+			let answer = answer.dimmed().to_string();
+			self.compact_lines(r, step, *chars::BAR, &answer, None).0
+		} else {
+			let mut lines = vec![chars::BAR.to_string()];
+			lines.extend(r.message(step, *chars::BAR, &self.message, None));
+			lines.push(format!("{}  {}", *chars::BAR, answer.dimmed()));
+			lines
 		};
 
-		println!("{}  {}", (*chars::STEP_SUBMIT).green(), self.message);
-		print!("{}", ansi::CLEAR_LINE);
-		println!("{}  {}", *chars::BAR, answer.dimmed());
+		r.finish(lines);
 	}
 
-	fn w_cancel(&self, value: bool) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(1));
-
+	fn w_cancel(&self, r: &mut Renderer, value: bool) {
 		let answer = if value {
 			&self.prompts.0
 		} else {
 			&self.prompts.1
 		};
+		transcript::prompt(&self.message, None);
+
+		let step = (*chars::STEP_CANCEL).red();
+		if self.compact {
+			let answer = answer.strikethrough().dimmed().to_string();
+			let (lines, _) = self.compact_lines(r, step, *chars::BAR, &answer, None);
+			r.finish(lines);
+			return;
+		}
+
+		let mut lines = vec![chars::BAR.to_string()];
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!(
+			"{}  {}",
+			*chars::BAR,
+			answer.strikethrough().dimmed()
+		));
+
+		r.finish(lines);
+	}
+}
 
-		println!("{}  {}", (*chars::STEP_CANCEL).red(), self.message);
-		print!("{}", ansi::CLEAR_LINE);
-		println!("{}  {}", *chars::BAR, answer.strikethrough().dimmed());
+/// The char in lowercase, if it has a single char lowercase form.
+fn lowercase(c: char) -> char {
+	let mut lower = c.to_lowercase();
+	match (lower.next(), lower.next()) {
+		(Some(lower), None) => lower,
+		_ => c,
 	}
 }
 
@@ -0,0 +1,120 @@
+//! Render debug log
+//!
+//! Logs every cursor movement and line write of the prompts to a side channel,
+//! to find out where a block is drawn off by a line.
+//!
+//! The log is started with [`start()`] or [`file()`],
+//! or by setting the `MAY_CLACK_RENDER_LOG` environment variable to the path of a file.
+//!
+//! Lines are logged with their escape codes escaped, like:
+//!
+//! ```text
+//! draw 4 lines, cursor at row 0
+//! move to row 1
+//! write row 1: "\u{1b}[36m◆\u{1b}[39m  name"
+//! clear row 4
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use may_clack::{debug, input};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! debug::file("render.log")?;
+//! let name = input("name").required()?;
+//! debug::stop();
+//! # Ok(())
+//! # }
+//! ```
+
+use once_cell::sync::Lazy;
+use std::{
+	env,
+	fs::{File, OpenOptions},
+	io::{self, BufWriter, Write},
+	path::Path,
+	sync::{Mutex, PoisonError},
+};
+
+type Writer = Box<dyn Write + Send>;
+
+/// Environment variable with the path of a file to log to.
+const ENV: &str = "MAY_CLACK_RENDER_LOG";
+
+/// Writer of the log, started from the environment variable the first time it is used.
+static LOG: Lazy<Mutex<Option<Writer>>> = Lazy::new(|| {
+	let file = env::var_os(ENV).and_then(|path| {
+		let file = OpenOptions::new().create(true).append(true).open(path);
+		file.ok()
+	});
+
+	Mutex::new(file.map(|file| Box::new(file) as Writer))
+});
+
+/// Log the entry, if the log is started.
+///
+/// The entry is only formatted when it is logged.
+pub(super) fn log<F: FnOnce() -> String>(entry: F) {
+	let mut log = LOG.lock().unwrap_or_else(PoisonError::into_inner);
+	if let Some(writer) = log.as_mut() {
+		let _ = writeln!(writer, "{}", entry());
+		let _ = writer.flush();
+	}
+}
+
+/// Start logging to the `writer`, replacing the previous log.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::debug;
+///
+/// debug::start(std::io::stderr());
+/// ```
+pub fn start<W: Write + Send + 'static>(writer: W) {
+	let mut log = LOG.lock().unwrap_or_else(PoisonError::into_inner);
+	if let Some(mut previous) = log.replace(Box::new(writer)) {
+		let _ = previous.flush();
+	}
+}
+
+/// Start logging to a file, replacing the previous log.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::debug;
+///
+/// # fn main() -> std::io::Result<()> {
+/// debug::file("render.log")?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn file<P: AsRef<Path>>(path: P) -> io::Result<()> {
+	let file = File::create(path)?;
+	start(BufWriter::new(file));
+	Ok(())
+}
+
+/// Stop logging.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::debug;
+///
+/// debug::start(std::io::stderr());
+/// // do stuff
+/// debug::stop();
+/// ```
+pub fn stop() {
+	let mut log = LOG.lock().unwrap_or_else(PoisonError::into_inner);
+	if let Some(mut writer) = log.take() {
+		let _ = writer.flush();
+	}
+}
@@ -0,0 +1,168 @@
+//! Drawing primitives
+//!
+//! The building blocks the prompts are drawn with,
+//! so custom components can write output that matches the prompts around them.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use may_clack::draw::{self, Step};
+//!
+//! draw::bar_line();
+//! draw::step("checking for updates", Step::Active);
+//! // do stuff
+//! draw::clear_block(2);
+//! draw::submitted("checking for updates", "up to date");
+//! ```
+
+use super::{
+	debug,
+	frame::{self, Out},
+	render::{self, Renderer},
+	transcript,
+};
+use crate::style::chars;
+use crossterm::{cursor, queue};
+use owo_colors::OwoColorize;
+use std::fmt::{self, Display};
+
+/// State of a step, drawn as the glyph in front of its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+	/// Active prompt, drawn in cyan
+	Active,
+	/// Submitted prompt, drawn in green
+	Submit,
+	/// Cancelled prompt, drawn in red
+	Cancel,
+	/// Failed prompt, drawn in yellow
+	Error,
+}
+
+impl Display for Step {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Step::Active => write!(f, "{}", (*chars::STEP_ACTIVE).cyan()),
+			Step::Submit => write!(f, "{}", (*chars::STEP_SUBMIT).green()),
+			Step::Cancel => write!(f, "{}", (*chars::STEP_CANCEL).red()),
+			Step::Error => write!(f, "{}", (*chars::STEP_ERROR).yellow()),
+		}
+	}
+}
+
+/// Write a line with only a bar, like the one in front of every prompt.
+///
+/// Nothing is written in [`quiet_mode()`](super::quiet_mode).
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::draw;
+///
+/// draw::bar_line();
+/// ```
+pub fn bar_line() {
+	frame::bar();
+}
+
+/// Write the `message` with the glyph of the `step` in front of it, like the first line of a prompt.
+///
+/// The lines after the first one get a bar in front of them,
+/// and the step counter is drawn in front of the message if the prompts are [numbered](super::steps).
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::draw::{self, Step};
+///
+/// draw::step("downloading", Step::Active);
+/// ```
+pub fn step<M: Display>(message: M, step: Step) {
+	let lines = match step {
+		Step::Active => render::message(step, (*chars::BAR).cyan(), &message, None),
+		_ => render::message(step, *chars::BAR, &message, None),
+	};
+
+	for line in lines {
+		frame::line(line);
+	}
+}
+
+/// Draw a prompt as if it was submitted with the `answer`,
+/// the same way as the prompts are drawn once they are submitted.
+///
+/// The answer is added to the [transcript](super::transcript), and the prompts move on to the next step.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::draw;
+///
+/// draw::submitted("region", "Europe");
+/// ```
+pub fn submitted<M: Display, A: Display>(message: M, answer: A) {
+	let answer = answer.to_string();
+	transcript::prompt(&message, Some(&answer));
+
+	let mut lines = vec![chars::BAR.to_string()];
+	lines.extend(render::message(Step::Submit, *chars::BAR, &message, None));
+	lines.extend(
+		answer
+			.split('\n')
+			.map(|line| format!("{}  {}", *chars::BAR, line.dimmed())),
+	);
+
+	Renderer::new().finish(lines);
+}
+
+/// Draw a prompt as if it was cancelled,
+/// the same way as the prompts are drawn once they are cancelled.
+///
+/// The cancelled prompt is added to the [transcript](super::transcript), and the prompts move on to the next step.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::draw;
+///
+/// draw::cancelled("region");
+/// ```
+pub fn cancelled<M: Display>(message: M) {
+	transcript::prompt(&message, None);
+
+	let mut lines = vec![chars::BAR.to_string()];
+	lines.extend(render::message(Step::Cancel, *chars::BAR, &message, None));
+	lines.push(format!(
+		"{}  {}",
+		*chars::BAR,
+		"cancelled".strikethrough().dimmed()
+	));
+
+	Renderer::new().finish(lines);
+}
+
+/// Clear the `lines` above the cursor, and move the cursor to the start of the first one,
+/// to draw something else in their place.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::draw::{self, Step};
+///
+/// draw::bar_line();
+/// draw::step("downloading", Step::Active);
+/// // do stuff
+/// draw::clear_block(2);
+/// draw::submitted("downloading", "done");
+/// ```
+pub fn clear_block(lines: usize) {
+	debug::log(|| format!("clear {} lines above the cursor", lines));
+
+	for _ in 0..lines {
+		let _ = queue!(Out, cursor::MoveUp(1));
+		frame::clear_line();
+	}
+
+	let _ = queue!(Out, cursor::MoveToColumn(0));
+	let _ = frame::flush();
+}
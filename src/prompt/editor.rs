@@ -0,0 +1,417 @@
+//! Line editor shared by the text prompts
+
+use super::{frame, input::CompleteFn, render, token::CancelToken};
+use crate::style::chars;
+use owo_colors::OwoColorize;
+use rustyline::{
+	completion::Completer,
+	highlight::Highlighter,
+	hint::{Hint, Hinter},
+	history::DefaultHistory,
+	Behavior, Cmd, ConditionalEventHandler, Config, Context, Editor, Event, EventContext,
+	EventHandler, Helper, KeyCode, KeyEvent, Modifiers, RepeatCount, Validator,
+};
+use std::{
+	borrow::Cow,
+	cell::Cell,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex, PoisonError,
+	},
+	thread::{self, JoinHandle},
+};
+
+/// Line editor for the input row.
+///
+/// If the prompts are written to stderr, it reads from and writes to the terminal directly,
+/// so that stdout stays free for the output of the program.
+pub(super) fn editor<H: Helper>() -> rustyline::Result<Editor<H, DefaultHistory>> {
+	let behavior = if frame::is_stderr() {
+		Behavior::PreferTerm
+	} else {
+		Behavior::Stdio
+	};
+
+	let config = Config::builder().behavior(behavior).build();
+	Editor::with_config(config)
+}
+
+/// Maximum length of an [`Input`](super::input::Input).
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Limit {
+	/// No more chars can be typed.
+	Hard(usize),
+	/// More chars can be typed, but the counter turns yellow.
+	Soft(usize),
+}
+
+impl Limit {
+	fn max(self) -> usize {
+		match self {
+			Limit::Hard(max) | Limit::Soft(max) => max,
+		}
+	}
+}
+
+/// Ignores typed chars once the line is at the maximum length.
+struct HardLimit(usize);
+
+impl ConditionalEventHandler for HardLimit {
+	fn handle(&self, evt: &Event, _: RepeatCount, _: bool, ctx: &EventContext) -> Option<Cmd> {
+		let is_char = matches!(
+			evt.get(0),
+			Some(KeyEvent(
+				KeyCode::Char(_),
+				Modifiers::NONE | Modifiers::SHIFT
+			))
+		);
+
+		(is_char && ctx.line().chars().count() >= self.0).then_some(Cmd::Noop)
+	}
+}
+
+/// Puts the placeholder in the empty line, so it can be accepted or edited.
+struct AcceptPlaceholder(String);
+
+impl ConditionalEventHandler for AcceptPlaceholder {
+	fn handle(&self, _: &Event, _: RepeatCount, _: bool, ctx: &EventContext) -> Option<Cmd> {
+		ctx.line()
+			.is_empty()
+			.then(|| Cmd::Insert(1, self.0.clone()))
+	}
+}
+
+/// Accept the `placeholder` with tab, or with the right arrow key at the end of the empty line.
+pub(super) fn bind_placeholder<H: Helper>(
+	editor: &mut Editor<H, DefaultHistory>,
+	placeholder: Option<&str>,
+) {
+	let Some(placeholder) = placeholder.filter(|placeholder| !placeholder.is_empty()) else {
+		return;
+	};
+
+	for key in [KeyCode::Tab, KeyCode::Right] {
+		let handler = AcceptPlaceholder(placeholder.to_owned());
+		let handler = EventHandler::Conditional(Box::new(handler));
+		editor.bind_sequence(KeyEvent(key, Modifiers::NONE), handler);
+	}
+}
+
+/// Keeps the line when ctrl+c is pressed, which is not returned by the editor when it is cancelled.
+struct KeepLine(Arc<Mutex<String>>);
+
+impl ConditionalEventHandler for KeepLine {
+	fn handle(&self, _: &Event, _: RepeatCount, _: bool, ctx: &EventContext) -> Option<Cmd> {
+		let mut line = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+		ctx.line().clone_into(&mut line);
+		None
+	}
+}
+
+/// Cancels the editor with the next key press once the `token` is cancelled,
+/// and passes the key on to the `next` handler otherwise.
+struct Abort {
+	token: CancelToken,
+	line: Arc<Mutex<String>>,
+	next: Option<EventHandler>,
+}
+
+impl ConditionalEventHandler for Abort {
+	fn handle(
+		&self,
+		evt: &Event,
+		n: RepeatCount,
+		positive: bool,
+		ctx: &EventContext,
+	) -> Option<Cmd> {
+		if self.token.is_cancelled() {
+			let mut line = self.line.lock().unwrap_or_else(PoisonError::into_inner);
+			ctx.line().clone_into(&mut line);
+			return Some(Cmd::Interrupt);
+		}
+
+		match &self.next {
+			Some(EventHandler::Simple(cmd)) => Some(cmd.clone()),
+			Some(EventHandler::Conditional(next)) => next.handle(evt, n, positive, ctx),
+			None => None,
+		}
+	}
+}
+
+/// Keep the line that was typed when the editor is cancelled with ctrl+c, in the returned string.
+///
+/// The editor is also cancelled with the next key press once the `token` is cancelled,
+/// so it is bound after the other handlers for any key.
+/// [`readline()`] presses a key once it is cancelled, so the user doesn't have to.
+pub(super) fn bind_cancel<H: Helper>(
+	editor: &mut Editor<H, DefaultHistory>,
+	token: Option<&CancelToken>,
+) -> Arc<Mutex<String>> {
+	let line = Arc::new(Mutex::new(String::new()));
+	let handler = KeepLine(Arc::clone(&line));
+	let handler = EventHandler::Conditional(Box::new(handler));
+	editor.bind_sequence(KeyEvent::ctrl('C'), handler);
+
+	if let Some(token) = token {
+		let next = editor.unbind_sequence(Event::Any);
+		let handler = Abort {
+			token: token.clone(),
+			line: Arc::clone(&line),
+			next,
+		};
+		editor.bind_sequence(Event::Any, EventHandler::Conditional(Box::new(handler)));
+	}
+
+	line
+}
+
+/// Read a line, starting with the `initial` text, and wake the editor once the `token` is cancelled.
+pub(super) fn readline<H: Helper>(
+	editor: &mut Editor<H, DefaultHistory>,
+	prompt: &str,
+	initial: Option<&str>,
+	token: Option<&CancelToken>,
+) -> rustyline::Result<String> {
+	let _wake = token.map(Wake::new);
+	match initial {
+		Some(initial) => editor.readline_with_initial(prompt, (initial, "")),
+		None => editor.readline(prompt),
+	}
+}
+
+/// Polls the token while the editor reads a line, until it is dropped.
+///
+/// The editor only runs its handlers when a key is pressed, so [`Abort`] can't notice the cancelled token on its own.
+/// Once the token is cancelled, a ctrl+@ key press is put into the input of the terminal, which [`Abort`] handles.
+struct Wake {
+	stop: Arc<AtomicBool>,
+	thread: Option<JoinHandle<()>>,
+}
+
+impl Wake {
+	fn new(token: &CancelToken) -> Wake {
+		let stop = Arc::new(AtomicBool::new(false));
+		let thread = thread::spawn({
+			let (stop, token) = (Arc::clone(&stop), token.clone());
+			move || {
+				while !stop.load(Ordering::Relaxed) {
+					if token.is_cancelled() {
+						press_key();
+						return;
+					}
+
+					thread::park_timeout(render::TICK);
+				}
+			}
+		});
+
+		Wake {
+			stop,
+			thread: Some(thread),
+		}
+	}
+}
+
+impl Drop for Wake {
+	fn drop(&mut self) {
+		self.stop.store(true, Ordering::Relaxed);
+		if let Some(thread) = self.thread.take() {
+			thread.thread().unpark();
+			let _ = thread.join();
+		}
+	}
+}
+
+/// Put a ctrl+@ key press into the input of the terminal, as if it was typed.
+///
+/// Does nothing if the terminal doesn't allow it, then the editor is cancelled with the next key press.
+#[cfg(unix)]
+fn press_key() {
+	use std::{fs::File, os::fd::AsRawFd};
+
+	let Ok(tty) = File::open("/dev/tty") else {
+		return;
+	};
+
+	let key: libc::c_char = 0;
+	// SAFETY: `TIOCSTI` reads a single byte from the pointer, which is valid for the duration of the call,
+	// and the file descriptor stays open until `tty` is dropped.
+	unsafe { libc::ioctl(tty.as_raw_fd(), libc::TIOCSTI, &key) };
+}
+
+/// The terminal input can't be written to, so the editor is cancelled with the next key press.
+#[cfg(not(unix))]
+fn press_key() {}
+
+/// The line kept by [`bind_cancel()`].
+pub(super) fn cancelled_line(line: &Mutex<String>) -> String {
+	let mut line = line.lock().unwrap_or_else(PoisonError::into_inner);
+	std::mem::take(&mut line)
+}
+
+/// Stop typing once the line is at the maximum length, if the `limit` is hard.
+pub(super) fn bind_limit<H: Helper>(editor: &mut Editor<H, DefaultHistory>, limit: Option<Limit>) {
+	if let Some(Limit::Hard(max)) = limit {
+		let handler = EventHandler::Conditional(Box::new(HardLimit(max)));
+		editor.bind_sequence(Event::Any, handler);
+	}
+}
+
+/// The `placeholder` drawn dimmed as ghost text, for prompts that draw their own cursor.
+///
+/// The first char is drawn as the cursor.
+pub(super) fn ghost(placeholder: &str) -> String {
+	let mut placeholder = placeholder.chars();
+	let cursor = placeholder.next().map_or(" ".into(), String::from);
+	format!("{}{}", cursor.reversed(), placeholder.as_str().dimmed())
+}
+
+/// Character counter, drawn as a hint at the end of the line.
+pub(super) struct Counter(String);
+
+impl Hint for Counter {
+	fn display(&self) -> &str {
+		&self.0
+	}
+
+	fn completion(&self) -> Option<&str> {
+		None
+	}
+}
+
+/// Helper of the line editor, that draws the placeholder as ghost text in the empty line,
+/// and the prefix, suffix and character counter around it.
+///
+/// Everything but the placeholder is opt-in with the builder methods,
+/// so it can be used by any prompt that reads a line with [`editor()`].
+#[derive(Helper, Validator)]
+pub(super) struct PlaceholderHighlighter<'a> {
+	placeholder: Option<&'a str>,
+	complete: Option<&'a CompleteFn>,
+	suffix: Option<&'a str>,
+	limit: Option<Limit>,
+	/// Whether the line is longer than the limit, set when the counter is updated.
+	is_over: Cell<bool>,
+	pub is_val: bool,
+}
+
+impl<'a> PlaceholderHighlighter<'a> {
+	pub fn new(placeholder: Option<&'a str>) -> Self {
+		PlaceholderHighlighter {
+			placeholder,
+			complete: None,
+			suffix: None,
+			limit: None,
+			is_over: Cell::new(false),
+			is_val: false,
+		}
+	}
+
+	/// Draw the `suffix` dimmed after the line.
+	pub fn suffix(mut self, suffix: Option<&'a str>) -> Self {
+		self.suffix = suffix;
+		self
+	}
+
+	/// Complete the word in front of the cursor with the candidates of `complete`.
+	pub fn complete(mut self, complete: Option<&'a CompleteFn>) -> Self {
+		self.complete = complete;
+		self
+	}
+
+	/// Draw a character counter for the `limit` at the end of the line.
+	pub fn limit(mut self, limit: Option<Limit>) -> Self {
+		self.limit = limit;
+		self
+	}
+}
+
+impl Completer for PlaceholderHighlighter<'_> {
+	type Candidate = String;
+
+	fn complete(
+		&self,
+		line: &str,
+		pos: usize,
+		_ctx: &Context<'_>,
+	) -> rustyline::Result<(usize, Vec<String>)> {
+		let Some(complete) = self.complete else {
+			return Ok((0, vec![]));
+		};
+
+		// the word in front of the cursor is replaced
+		let word = line[..pos]
+			.chars()
+			.rev()
+			.take_while(|c| !c.is_whitespace())
+			.map(char::len_utf8)
+			.sum::<usize>();
+		Ok((pos - word, complete(line, pos)))
+	}
+}
+
+impl Hinter for PlaceholderHighlighter<'_> {
+	type Hint = Counter;
+
+	fn hint(&self, line: &str, _pos: usize, _ctx: &Context<'_>) -> Option<Counter> {
+		let suffix = self.suffix.unwrap_or_default();
+		let Some(max) = self.limit.map(Limit::max) else {
+			return (!suffix.is_empty()).then(|| Counter(suffix.to_owned()));
+		};
+
+		let len = line.chars().count();
+		self.is_over.set(len > max);
+
+		Some(Counter(format!("{}  {}/{}", suffix, len, max)))
+	}
+}
+
+impl Highlighter for PlaceholderHighlighter<'_> {
+	fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+		if let Some(placeholder) = self.placeholder {
+			if line.is_empty() {
+				Cow::Owned(placeholder.dimmed().to_string())
+			} else {
+				Cow::Borrowed(line)
+			}
+		} else {
+			Cow::Borrowed(line)
+		}
+	}
+
+	fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+		let suffix = self.suffix.map_or(0, str::len).min(hint.len());
+		let (suffix, counter) = hint.split_at(suffix);
+
+		if self.is_over.get() {
+			Cow::Owned(format!("{}{}", suffix.dimmed(), counter.yellow()))
+		} else {
+			Cow::Owned(hint.dimmed().to_string())
+		}
+	}
+
+	fn highlight_char(&self, _line: &str, _pos: usize, _forced: bool) -> bool {
+		true
+	}
+
+	fn highlight_prompt<'b, 's: 'b, 'p: 'b>(
+		&'s self,
+		prompt: &'p str,
+		default: bool,
+	) -> Cow<'b, str> {
+		if !default {
+			// i honestly don't know what this even does
+			Cow::Borrowed(prompt)
+		} else {
+			// everything after the bar is the prefix of the input
+			let bar = chars::BAR.len().min(prompt.len());
+			let (bar, prefix) = prompt.split_at(bar);
+
+			if self.is_val {
+				Cow::Owned(format!("{}{}", bar.yellow(), prefix.dimmed()))
+			} else {
+				Cow::Owned(format!("{}{}", bar.cyan(), prefix.dimmed()))
+			}
+		}
+	}
+}
@@ -0,0 +1,147 @@
+//! Buffered frame output
+//!
+//! Everything a prompt draws is collected into a frame and written to stdout at once,
+//! so that redraws don't tear on slow connections.
+
+use super::{debug, summary, text};
+use crate::style::chars;
+use crossterm::{
+	queue,
+	terminal::{self, Clear, ClearType},
+};
+use std::{
+	cell::RefCell,
+	fmt::Display,
+	io::{self, Write},
+	sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Whether the prompts are written to stderr in [`stderr_mode()`](super::stderr_mode).
+static STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Write the prompts and messages to stderr instead of stdout.
+pub(super) fn set_stderr(stderr: bool) {
+	STDERR.store(stderr, Ordering::Relaxed);
+}
+
+/// Whether [`stderr_mode()`](super::stderr_mode) is enabled.
+pub(super) fn is_stderr_mode() -> bool {
+	STDERR.load(Ordering::Relaxed)
+}
+
+/// Whether the prompts and messages are written to stderr,
+/// in [`stderr_mode()`](super::stderr_mode) or [`json_mode()`](super::json_mode).
+pub(super) fn is_stderr() -> bool {
+	is_stderr_mode() || summary::is_enabled()
+}
+
+/// Whether the decorative bars around the prompts and messages are left out.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Leave out the bars, and draw the finished prompts as a single `message: answer` line.
+pub(super) fn set_quiet(quiet: bool) {
+	QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether the decorative bars are left out.
+pub(super) fn is_quiet() -> bool {
+	QUIET.load(Ordering::Relaxed)
+}
+
+/// Write a line that only has a bar, unless the bars are left out.
+pub(super) fn bar() {
+	if !is_quiet() {
+		line(*chars::BAR);
+	}
+}
+
+/// Whether the prompts and messages are mirrored for right-to-left text.
+static RTL: AtomicBool = AtomicBool::new(false);
+
+/// Mirror the prompts and messages, drawing the bars on the right edge of the terminal.
+pub(super) fn set_rtl(rtl: bool) {
+	RTL.store(rtl, Ordering::Relaxed);
+}
+
+/// Whether the prompts and messages are mirrored.
+pub(super) fn is_rtl() -> bool {
+	RTL.load(Ordering::Relaxed)
+}
+
+/// The line mirrored to the terminal width, if the prompts are mirrored and the width is known.
+pub(super) fn mirror(line: String) -> String {
+	let width = terminal::size().ok().filter(|&(width, _)| width > 0);
+	match width.filter(|_| is_rtl()) {
+		Some((width, _)) => text::mirror(&line, width as usize),
+		None => line,
+	}
+}
+
+/// Write bytes to stdout, or stderr if the prompts are written to stderr.
+fn write(bytes: &[u8]) -> io::Result<()> {
+	if is_stderr() {
+		let mut stderr = io::stderr().lock();
+		stderr.write_all(bytes)?;
+		stderr.flush()
+	} else {
+		let mut stdout = io::stdout().lock();
+		stdout.write_all(bytes)?;
+		stdout.flush()
+	}
+}
+
+/// Write a line outside of a frame, like [`println!`].
+pub(super) fn line<D: Display>(line: D) {
+	let line = mirror(line.to_string());
+	debug::log(|| format!("write line: {:?}", line));
+	let _ = write(format!("{}\n", line).as_bytes());
+}
+
+thread_local! {
+	static FRAME: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Writer that appends to the current frame, written to stdout on [`flush()`].
+///
+/// Flushing it writes the frame, so on consoles without ansi support, where crossterm flushes the writer
+/// and runs its commands through the console api right away, the text before a command is written first.
+pub(super) struct Out;
+
+impl Write for Out {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		FRAME.with_borrow_mut(|frame| frame.extend_from_slice(buf));
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		flush()
+	}
+}
+
+/// Write the current frame to stdout.
+pub(super) fn flush() -> io::Result<()> {
+	let frame = FRAME.with_borrow_mut(std::mem::take);
+	if frame.is_empty() {
+		return Ok(());
+	}
+
+	write(&frame)
+}
+
+/// Like [`print!`], but writes to the current frame.
+macro_rules! out {
+	($($arg:tt)*) => {{
+		let _ = ::std::io::Write::write_fmt(&mut $crate::prompt::frame::Out, format_args!($($arg)*));
+	}};
+}
+
+pub(super) use out;
+
+/// Clear the line the cursor is on in the current frame.
+///
+/// Uses the clear command of crossterm, like the cursor movements,
+/// so it falls back to the console api where escape codes don't work.
+/// The fallback flushes [`Out`] first, so the line is cleared after the text in front of it is written.
+pub(super) fn clear_line() {
+	let _ = queue!(Out, Clear(ClearType::CurrentLine));
+}
@@ -1,69 +1,243 @@
 //! Text input
 
+use super::{
+	cache::Cached,
+	editor::{self, Limit, PlaceholderHighlighter},
+	frame,
+	render::{self, RawMode, RenderFn, Renderer},
+	session::Context,
+	transcript,
+};
 use crate::{
 	error::ClackError,
-	style::{ansi, chars},
+	style::chars,
+	validate::{Attempt, Validator},
 };
-use crossterm::{cursor, QueueableCommand};
+use crossterm::event::{self as term, KeyEventKind, KeyModifiers};
 use owo_colors::OwoColorize;
-use rustyline::{highlight::Highlighter, Completer, Editor, Helper, Hinter, Validator};
+use rustyline::{
+	history::{DefaultHistory, FileHistory, History as _},
+	Editor, Helper,
+};
 use std::{
 	borrow::{Borrow, Cow},
 	error::Error,
 	fmt::Display,
-	io::{stdout, Write},
+	path::PathBuf,
 	str::FromStr,
+	sync::{Arc, Mutex, PoisonError},
 };
 
-#[derive(Completer, Helper, Hinter, Validator)]
-pub(super) struct PlaceholderHighlighter<'a> {
-	placeholder: Option<&'a str>,
-	pub is_val: bool,
+/// Text of a [multiline](Input::multiline()) input, with the cursor as a byte index.
+struct Multiline {
+	value: String,
+	cursor: usize,
 }
 
-impl<'a> PlaceholderHighlighter<'a> {
-	pub fn new(placeholder: Option<&'a str>) -> Self {
-		PlaceholderHighlighter {
-			placeholder,
-			is_val: false,
+impl Multiline {
+	fn new(value: &str) -> Self {
+		Multiline {
+			value: value.to_owned(),
+			cursor: value.len(),
 		}
 	}
-}
 
-impl Highlighter for PlaceholderHighlighter<'_> {
-	fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
-		if let Some(placeholder) = self.placeholder {
-			if line.is_empty() {
-				Cow::Owned(placeholder.dimmed().to_string())
-			} else {
-				Cow::Borrowed(line)
-			}
-		} else {
-			Cow::Borrowed(line)
+	/// Start of the line the cursor is on.
+	fn line_start(&self) -> usize {
+		self.value[..self.cursor].rfind('\n').map_or(0, |i| i + 1)
+	}
+
+	/// End of the line the cursor is on.
+	fn line_end(&self) -> usize {
+		self.value[self.cursor..]
+			.find('\n')
+			.map_or(self.value.len(), |i| self.cursor + i)
+	}
+
+	/// Column of the cursor, in chars.
+	fn column(&self) -> usize {
+		self.value[self.line_start()..self.cursor].chars().count()
+	}
+
+	/// Move the cursor to the `column` of the line from `start` to `end`,
+	/// or to the end of the line if it is shorter.
+	fn goto(&mut self, start: usize, end: usize, column: usize) {
+		self.cursor = self.value[start..end]
+			.char_indices()
+			.nth(column)
+			.map_or(end, |(i, _)| start + i);
+	}
+
+	fn insert(&mut self, c: char) {
+		self.value.insert(self.cursor, c);
+		self.cursor += c.len_utf8();
+	}
+
+	fn backspace(&mut self) {
+		if let Some(c) = self.value[..self.cursor].chars().next_back() {
+			self.cursor -= c.len_utf8();
+			self.value.remove(self.cursor);
 		}
 	}
 
-	fn highlight_char(&self, _line: &str, _pos: usize, _forced: bool) -> bool {
-		true
+	fn delete(&mut self) {
+		if self.cursor < self.value.len() {
+			self.value.remove(self.cursor);
+		}
 	}
 
-	fn highlight_prompt<'b, 's: 'b, 'p: 'b>(
-		&'s self,
-		prompt: &'p str,
-		default: bool,
-	) -> Cow<'b, str> {
-		if !default {
-			// i honestly don't know what this even does
-			Cow::Borrowed(prompt)
-		} else if self.is_val {
-			Cow::Owned(prompt.yellow().to_string())
-		} else {
-			Cow::Owned(prompt.cyan().to_string())
+	fn left(&mut self) {
+		if let Some(c) = self.value[..self.cursor].chars().next_back() {
+			self.cursor -= c.len_utf8();
+		}
+	}
+
+	fn right(&mut self) {
+		if let Some(c) = self.value[self.cursor..].chars().next() {
+			self.cursor += c.len_utf8();
+		}
+	}
+
+	fn up(&mut self) {
+		let start = self.line_start();
+		if start > 0 {
+			let column = self.column();
+			let above = self.value[..start - 1].rfind('\n').map_or(0, |i| i + 1);
+			self.goto(above, start - 1, column);
 		}
 	}
+
+	fn down(&mut self) {
+		let end = self.line_end();
+		if end < self.value.len() {
+			let column = self.column();
+			let below = self.value[end + 1..]
+				.find('\n')
+				.map_or(self.value.len(), |i| end + 1 + i);
+			self.goto(end + 1, below, column);
+		}
+	}
+
+	fn home(&mut self) {
+		self.cursor = self.line_start();
+	}
+
+	fn end(&mut self) {
+		self.cursor = self.line_end();
+	}
+
+	/// Lines of the text with the cursor drawn in, and the line the cursor is on.
+	fn fmt_lines(&self) -> (Vec<String>, usize) {
+		let (before, after) = self.value.split_at(self.cursor);
+		let (cursor, after) = match after.chars().next() {
+			Some(c) if c != '\n' => after.split_at(c.len_utf8()),
+			_ => (" ", after),
+		};
+
+		let text = format!("{}{}{}", before, cursor.reversed(), after);
+		let lines = text.split('\n').map(str::to_owned).collect();
+		(lines, before.matches('\n').count())
+	}
+}
+
+/// `History` struct.
+///
+/// Previous answers of an [`Input`], recalled with the up and down arrow keys.
+///
+/// The history is shared between its clones, so it can be passed to [`Input::history()`]
+/// and still be read after the prompt.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{input, input::History};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let history = History::from(vec!["PROJ-12".to_string()]);
+///
+/// let issue = input("issue id").history(&history).required()?;
+/// let issue = input("another issue id").history(&history).required()?;
+/// println!("{:?}", history.entries());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct History {
+	entries: Arc<Mutex<Vec<String>>>,
 }
 
-pub(super) type ValidateFn = dyn Fn(&str) -> Result<(), Cow<'static, str>>;
+impl History {
+	/// Creates a new, empty `History` struct.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::input::History;
+	///
+	/// let history = History::new();
+	/// ```
+	pub fn new() -> Self {
+		History::default()
+	}
+
+	/// Add an entry to the end of the history.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::input::History;
+	///
+	/// let history = History::new();
+	/// history.push("PROJ-12");
+	/// assert_eq!(history.entries(), ["PROJ-12"]);
+	/// ```
+	pub fn push<S: ToString>(&self, entry: S) {
+		let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+		entries.push(entry.to_string());
+	}
+
+	/// The entries of the history, oldest first.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::input::History;
+	///
+	/// let history = History::from(vec!["PROJ-12".to_string()]);
+	/// assert_eq!(history.entries(), ["PROJ-12"]);
+	/// ```
+	pub fn entries(&self) -> Vec<String> {
+		let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+		entries.clone()
+	}
+}
+
+impl From<Vec<String>> for History {
+	fn from(entries: Vec<String>) -> Self {
+		History {
+			entries: Arc::new(Mutex::new(entries)),
+		}
+	}
+}
+
+pub(super) type ValidateFn = dyn FnMut(&str, &Attempt) -> Result<(), Cow<'static, str>> + Send;
+pub(super) type FormatFn<A> = dyn Fn(&A) -> String + Send + Sync;
+type MapFn = dyn Fn(&str) -> String + Send + Sync;
+pub(super) type CompleteFn = dyn Fn(&str, usize) -> Vec<String> + Send + Sync;
+pub(super) type CancelFn = dyn Fn(&str) + Send + Sync;
+pub(super) type SubmitFn<A> = dyn Fn(&A) + Send + Sync;
+pub(super) type CancelReturnFn<T> = dyn Fn() -> T + Send + Sync;
+type ValidateParsedFn<'a, T> = dyn Fn(&T) -> Result<(), Cow<'static, str>> + 'a;
+
+/// Parse a value with [`FromStr`], with the error as the message.
+fn from_str<T: FromStr>(value: &str) -> Result<T, Cow<'static, str>>
+where
+	T::Err: Error,
+{
+	value
+		.parse()
+		.map_err(|err: T::Err| Cow::Owned(err.to_string()))
+}
 
 /// `Input` struct
 ///
@@ -96,10 +270,29 @@ pub(super) type ValidateFn = dyn Fn(&str) -> Result<(), Cow<'static, str>>;
 /// ````
 pub struct Input<M: Display> {
 	message: M,
+	extra: Option<String>,
+	help: Option<String>,
 	initial_value: Option<String>,
+	default_value: Option<String>,
+	multiline: bool,
+	complete: Option<Arc<CompleteFn>>,
 	placeholder: Option<String>,
-	validate: Option<Box<ValidateFn>>,
-	cancel: Option<Box<dyn Fn()>>,
+	prefix: Option<String>,
+	suffix: Option<String>,
+	validate: Option<Mutex<Box<ValidateFn>>>,
+	max_attempts: Option<usize>,
+	trim: bool,
+	lowercase: bool,
+	map: Option<Arc<MapFn>>,
+	limit: Option<Limit>,
+	history: Option<History>,
+	history_file: Option<PathBuf>,
+	cancel: Option<Arc<CancelFn>>,
+	cancel_return: Option<Arc<CancelReturnFn<String>>>,
+	format: Option<Arc<FormatFn<str>>>,
+	on_render: Option<Arc<RenderFn>>,
+	context: Context,
+	on_submit: Option<Arc<SubmitFn<str>>>,
 }
 
 impl<M: Display> Input<M> {
@@ -119,32 +312,682 @@ impl<M: Display> Input<M> {
 	pub fn new(message: M) -> Self {
 		Input {
 			message,
+			extra: None,
+			help: None,
 			initial_value: None,
+			default_value: None,
+			multiline: false,
+			complete: None,
 			placeholder: None,
+			prefix: None,
+			suffix: None,
 			validate: None,
+			max_attempts: None,
+			trim: false,
+			lowercase: false,
+			map: None,
+			limit: None,
+			history: None,
+			history_file: None,
 			cancel: None,
+			cancel_return: None,
+			format: None,
+			on_render: None,
+			context: Context::default(),
+			on_submit: None,
+		}
+	}
+
+	/// Specify a placeholder.
+	///
+	/// It can be accepted into the empty line with tab or the right arrow key.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("message").placeholder("placeholder").required()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn placeholder<S: ToString>(&mut self, placeholder: S) -> &mut Self {
+		self.placeholder = Some(placeholder.to_string());
+		self
+	}
+
+	/// Draw a dimmed `prefix` in front of the value, like a currency sign.
+	///
+	/// It is not part of the returned value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let price: f64 = input("price").prefix("$ ").parse()?;
+	/// println!("price {}", price);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn prefix<S: ToString>(&mut self, prefix: S) -> &mut Self {
+		self.prefix = Some(prefix.to_string());
+		self
+	}
+
+	/// Draw a dimmed `suffix` after the value, like a unit.
+	///
+	/// It is not part of the returned value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let memory: u32 = input("memory").suffix(" MiB").parse()?;
+	/// println!("memory {}", memory);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn suffix<S: ToString>(&mut self, suffix: S) -> &mut Self {
+		self.suffix = Some(suffix.to_string());
+		self
+	}
+
+	/// Maybe specify an initial value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("message").maybe_initial(Some("initial")).required()?;
+	/// let answer = input("message").maybe_initial(None::<&str>).required()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn maybe_initial<T: Borrow<Option<S>>, S: ToString>(&mut self, initial: T) -> &mut Self {
+		if let Some(initial) = initial.borrow() {
+			self.initial_value = Some(initial.to_string());
 		}
+
+		self
+	}
+
+	/// Specify the initial value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("message").initial_value("initial_value").interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn initial_value<S: ToString>(&mut self, initial_value: S) -> &mut Self {
+		self.initial_value = Some(initial_value.to_string());
+		self
+	}
+
+	/// Specify the default value, returned when an empty line is submitted.
+	///
+	/// Unlike the [initial value](Input::initial_value()), it is not put in the line to be edited,
+	/// but drawn dimmed next to the message.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("branch").default_value("main").required()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn default_value<S: ToString>(&mut self, default_value: S) -> &mut Self {
+		self.default_value = Some(default_value.to_string());
+		self
+	}
+
+	/// Let the value span multiple lines.
+	///
+	/// Enter starts a new line, and alt+enter, or esc followed by enter, submits the value.
+	/// The value is edited without the history and the character counter.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let description = input("description").multiline().interact()?;
+	/// println!("description {:?}", description);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn multiline(&mut self) -> &mut Self {
+		self.multiline = true;
+		self
+	}
+
+	/// Specify a function to complete the value with tab.
+	///
+	/// It is called with the line and the position of the cursor,
+	/// and returns the candidates for the word in front of the cursor.
+	/// Pressing tab again cycles through them.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let branches = ["main", "develop", "feature/input"];
+	/// let branch = input("branch")
+	///     .complete(move |line, pos| {
+	///         let word = line[..pos].rsplit(' ').next().unwrap_or_default();
+	///         branches
+	///             .iter()
+	///             .filter(|branch| branch.starts_with(word))
+	///             .map(|branch| branch.to_string())
+	///             .collect()
+	///     })
+	///     .required()?;
+	/// println!("branch {}", branch);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn complete<F>(&mut self, complete: F) -> &mut Self
+	where
+		F: Fn(&str, usize) -> Vec<String> + Send + Sync + 'static,
+	{
+		let complete = Arc::new(complete);
+		self.complete = Some(complete);
+		self
+	}
+
+	/// Specify a validation function.
+	///
+	/// On a successful validation, return `Ok(())` from the closure,
+	/// and on an unsuccessful validation return an `Err` with the error message.
+	/// The same function can be passed to [`MultiInput::validate()`](crate::multi_input::MultiInput::validate()).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	/// # use std::borrow::Cow;
+	///
+	/// let answer = input("message")
+	///     .validate(|x| {
+	///         if x.is_ascii() {
+	///             Ok(())
+	///         } else {
+	///             Err(Cow::Borrowed("only use ascii characters"))
+	///         }
+	///     })
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok::<(), may_clack::error::ClackError>(())
+	/// ```
+	pub fn validate<F>(&mut self, mut validate: F) -> &mut Self
+	where
+		F: FnMut(&str) -> Result<(), Cow<'static, str>> + Send + 'static,
+	{
+		let validate = Box::new(move |value: &str, _: &Attempt| validate(value));
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Like [`Input::validate()`], but the function can keep state between the submissions,
+	/// and gets the [`Attempt`] that is validated.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("name")
+	///     .validate_with(|value, attempt| {
+	///         if value.len() >= 8 {
+	///             Ok(())
+	///         } else if attempt.number >= 3 {
+	///             Err(Cow::Owned(format!("attempt {}: use at least 8 characters", attempt.number)))
+	///         } else {
+	///             Err(Cow::Borrowed("too short"))
+	///         }
+	///     })
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validate_with<F>(&mut self, validate: F) -> &mut Self
+	where
+		F: FnMut(&str, &Attempt) -> Result<(), Cow<'static, str>> + Send + 'static,
+	{
+		let validate = Box::new(validate);
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Specify a [`Validator`], like [`Input::validate()`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{
+	///     input,
+	///     validate::{self, Validator},
+	/// };
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("name")
+	///     .validator(validate::not_empty().and(validate::max_len(20)))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validator<V: Validator + Send + 'static>(&mut self, validator: V) -> &mut Self {
+		let validate = Box::new(move |value: &str, _: &Attempt| validator.validate(value));
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Abort the prompt with [`ClackError::TooManyAttempts`] after `max` rejected submissions,
+	/// instead of asking again.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{error::ClackError, input};
+	///
+	/// let answer = input("port").max_attempts(3).required();
+	/// if let Err(ClackError::TooManyAttempts { last_error }) = answer {
+	///     eprintln!("giving up: {}", last_error);
+	/// }
+	/// ```
+	pub fn max_attempts(&mut self, max: usize) -> &mut Self {
+		self.max_attempts = Some(max);
+		self
+	}
+
+	/// Specify a validation function for the parsed value, like [`Input::validate()`].
+	///
+	/// Returns a [`ParsedInput`], which parses the value to a `T` and validates it before returning,
+	/// so the type of the validation always matches the type the value is parsed to.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let port = input("port")
+	///     .validate_parsed(|port: &u16| {
+	///         if *port > 1024 {
+	///             Ok(())
+	///         } else {
+	///             Err(Cow::Borrowed("port must be above 1024"))
+	///         }
+	///     })
+	///     .parse()?;
+	/// println!("port {}", port);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validate_parsed<'a, T, F>(&'a self, validate: F) -> ParsedInput<'a, M, T>
+	where
+		F: Fn(&T) -> Result<(), Cow<'static, str>> + 'a,
+	{
+		ParsedInput {
+			input: self,
+			validate: Box::new(validate),
+		}
+	}
+
+	/// Trim whitespace from the start and end of the value.
+	///
+	/// Like the other transforms, it is applied after the validation,
+	/// and the transformed value is drawn after submitting and returned.
+	/// An empty value after trimming counts as an empty line.
+	///
+	/// Default: `false`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("name").trim(true).required()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn trim(&mut self, trim: bool) -> &mut Self {
+		self.trim = trim;
+		self
+	}
+
+	/// Convert the value to lowercase, after trimming it.
+	///
+	/// Default: `false`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("email").trim(true).lowercase(true).required()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn lowercase(&mut self, lowercase: bool) -> &mut Self {
+		self.lowercase = lowercase;
+		self
+	}
+
+	/// Transform the value with the `map` function, after trimming and converting it to lowercase.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("branch")
+	///     .trim(true)
+	///     .map(|branch| branch.replace(' ', "-"))
+	///     .required()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn map<F>(&mut self, map: F) -> &mut Self
+	where
+		F: Fn(&str) -> String + Send + Sync + 'static,
+	{
+		self.map = Some(Arc::new(map));
+		self
+	}
+
+	/// Apply the transforms to the value.
+	fn transform<'v>(&self, value: &'v str) -> Cow<'v, str> {
+		let value = if self.trim { value.trim() } else { value };
+		let mut value = Cow::Borrowed(value);
+
+		if self.lowercase {
+			value = Cow::Owned(value.to_lowercase());
+		}
+
+		if let Some(map) = self.map.as_deref() {
+			value = Cow::Owned(map(&value));
+		}
+
+		value
+	}
+
+	/// Limit the length of the input to `max` chars, with a counter like `12/80` at the end of the line.
+	///
+	/// No more chars can be typed once the limit is reached.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("commit message").max_len(72).required()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn max_len(&mut self, max: usize) -> &mut Self {
+		self.limit = Some(Limit::Hard(max));
+		self
+	}
+
+	/// Like [`Input::max_len()`], but more chars can be typed and submitted,
+	/// the counter turns yellow instead.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("commit summary").soft_max_len(50).required()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn soft_max_len(&mut self, max: usize) -> &mut Self {
+		self.limit = Some(Limit::Soft(max));
+		self
+	}
+
+	fn do_validate(&self, input: &str, attempt: &Attempt) -> Result<(), Cow<'static, str>> {
+		if let Some(Limit::Hard(max)) = self.limit {
+			if input.chars().count() > max {
+				return Err(Cow::Owned(format!("use at most {} characters", max)));
+			}
+		}
+
+		if let Some(validate) = &self.validate {
+			let mut validate = validate.lock().unwrap_or_else(PoisonError::into_inner);
+			validate(input, attempt)
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Recall the entries of the `history` with the up and down arrow keys,
+	/// and add the answer to it.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, input::History};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let history = History::new();
+	/// loop {
+	///     let command = input("command").history(&history).required()?;
+	///     if command == "exit" {
+	///         break;
+	///     }
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn history(&mut self, history: &History) -> &mut Self {
+		self.history = Some(history.clone());
+		self
+	}
+
+	/// Recall the previous answers from the file at the `path` with the up and down arrow keys,
+	/// and add the answer to it.
+	///
+	/// The file is created if it doesn't exist yet. Failing to read or write it is ignored.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let issue = input("issue id")
+	///     .history_file(".issue_history")
+	///     .required()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn history_file<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+		self.history_file = Some(path.into());
+		self
+	}
+
+	/// Add the previous answers to the history of the editor.
+	fn load_history<H: Helper>(&self, editor: &mut Editor<H, DefaultHistory>) {
+		if let Some(history) = &self.history {
+			for entry in history.entries() {
+				let _ = editor.add_history_entry(entry);
+			}
+		}
+
+		if let Some(path) = &self.history_file {
+			let _ = editor.load_history(path);
+		}
+	}
+
+	/// Add the answer to the history and the history file.
+	///
+	/// The history file is written separately from the history of the editor,
+	/// so that the entries of the [`History`] don't end up in it.
+	fn save_history(&self, value: &str) {
+		if let Some(history) = &self.history {
+			history.push(value);
+		}
+
+		if let Some(path) = &self.history_file {
+			let mut file = FileHistory::new();
+			let _ = file.load(path);
+			let _ = file.add(value);
+			let _ = file.append(path);
+		}
+	}
+
+	/// Add secondary lines below the message, drawn dimmed while the prompt is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("name")
+	///     .message_extra("used for the commit author")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn message_extra<S: ToString>(&mut self, extra: S) -> &mut Self {
+		self.extra = Some(extra.to_string());
+		self
+	}
+
+	/// Show a dimmed help line at the end of the prompt while it is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("name")
+	///     .help("enter to submit")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn help<S: ToString>(&mut self, help: S) -> &mut Self {
+		self.help = Some(help.to_string());
+		self
+	}
+
+	/// Change what is printed after submitting, instead of the submitted value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("api token")
+	///     .format_submitted(|token| "*".repeat(token.chars().count()))
+	///     .required()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn format_submitted<F>(&mut self, format: F) -> &mut Self
+	where
+		F: Fn(&str) -> String + Send + Sync + 'static,
+	{
+		self.format = Some(Arc::new(format));
+		self
+	}
+
+	/// Call `on_render` with the lines of every frame that is drawn, e.g. to record the prompt in tests.
+	///
+	/// The line that is typed is drawn by the line editor, so it isn't part of the frames.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	/// use std::sync::{Arc, Mutex};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let frames = Arc::new(Mutex::new(vec![]));
+	/// let recorded = Arc::clone(&frames);
+	/// let answer = input("name")
+	///     .on_render(move |lines| recorded.lock().unwrap().push(lines.to_vec()))
+	///     .interact()?;
+	/// println!("{} frames", frames.lock().unwrap().len());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_render<F>(&mut self, on_render: F) -> &mut Self
+	where
+		F: Fn(&[String]) + Send + Sync + 'static,
+	{
+		self.on_render = Some(Arc::new(on_render));
+		self
 	}
 
-	/// Specify a placeholder.
+	/// Draw the prompt as part of a [`Session`](crate::session::Session),
+	/// with the [`Context`] the session passes to [`Session::prompt()`](crate::session::Session::prompt()).
+	///
+	/// The prompt is pre-filled with its [cached](crate::session::Session::cache()) answer,
+	/// which takes precedence over the [initial value](Input::initial_value()).
 	///
 	/// # Examples
 	///
 	/// ```no_run
-	/// use may_clack::input;
+	/// use may_clack::{input, session};
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
-	/// let answer = input("message").placeholder("placeholder").required()?;
+	/// let mut session = session("intro");
+	/// session.steps(1);
+	///
+	/// let answer = session.prompt("name", |cx| input("name").context(cx).required())?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn placeholder<S: ToString>(&mut self, placeholder: S) -> &mut Self {
-		self.placeholder = Some(placeholder.to_string());
+	pub fn context(&mut self, cx: &Context) -> &mut Self {
+		self.context = cx.clone();
 		self
 	}
 
-	/// Maybe specify an initial value.
+	/// Call `on_submit` with the answer when the prompt is submitted.
 	///
 	/// # Examples
 	///
@@ -152,160 +995,210 @@ impl<M: Display> Input<M> {
 	/// use may_clack::input;
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
-	/// let answer = input("message").maybe_initial(Some("initial")).required()?;
-	/// let answer = input("message").maybe_initial(None::<&str>).required()?;
+	/// let answer = input("name")
+	///     .on_submit(|name| println!("name {}", name))
+	///     .interact()?;
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn maybe_initial<T: Borrow<Option<S>>, S: ToString>(&mut self, initial: T) -> &mut Self {
-		if let Some(initial) = initial.borrow() {
-			self.initial_value = Some(initial.to_string());
-		}
-
+	pub fn on_submit<F>(&mut self, on_submit: F) -> &mut Self
+	where
+		F: Fn(&str) + Send + Sync + 'static,
+	{
+		self.on_submit = Some(Arc::new(on_submit));
 		self
 	}
 
-	/// Specify the initial value.
+	/// Specify function to call on cancel.
 	///
 	/// # Examples
 	///
 	/// ```no_run
-	/// use may_clack::input;
+	/// use may_clack::{input, cancel};
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
-	/// let answer = input("message").initial_value("initial_value").interact()?;
+	/// let answer = input("message").cancel(do_cancel).interact()?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
-	/// ```
-	pub fn initial_value<S: ToString>(&mut self, initial_value: S) -> &mut Self {
-		self.initial_value = Some(initial_value.to_string());
+	///
+	/// fn do_cancel() {
+	///     cancel!("operation cancelled");
+	///     panic!("operation cancelled");
+	/// }
+	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn() + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(move |_: &str| cancel());
+		self.cancel = Some(cancel);
 		self
 	}
 
-	/// Specify a validation function.
-	///
-	/// On a successful validation, return a `None` from the closure,
-	/// and on an unsuccessful validation return a `Some<&'static str>` with the error message.
+	/// Like [`Input::cancel()`], but the function gets the text that was typed so far,
+	/// e.g. to save it as a draft.
 	///
 	/// # Examples
 	///
 	/// ```no_run
 	/// use may_clack::input;
-	/// # use std::borrow::Cow;
 	///
-	/// let answer = input("message")
-	///     .validate(|x| {
-	///         if x.is_ascii() {
-	///             Ok(())
-	///         } else {
-	///             Err(Cow::Borrowed("only use ascii characters"))
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("commit message")
+	///     .cancel_with(|draft| {
+	///         if !draft.is_empty() {
+	///             let _ = std::fs::write(".draft", draft);
 	///         }
 	///     })
 	///     .interact()?;
-	/// println!("answer {:?}", answer);
-	/// # Ok::<(), may_clack::error::ClackError>(())
+	/// # Ok(())
+	/// # }
 	/// ```
-	pub fn validate<F>(&mut self, validate: F) -> &mut Self
+	pub fn cancel_with<F>(&mut self, cancel: F) -> &mut Self
 	where
-		F: Fn(&str) -> Result<(), Cow<'static, str>> + 'static,
+		F: Fn(&str) + Send + Sync + 'static,
 	{
-		let validate = Box::new(validate);
-		self.validate = Some(validate);
+		let cancel = Arc::new(cancel);
+		self.cancel = Some(cancel);
 		self
 	}
 
-	fn do_validate(&self, input: &str) -> Result<(), Cow<'static, str>> {
-		if let Some(validate) = self.validate.as_deref() {
-			validate(input)
-		} else {
-			Ok(())
-		}
-	}
-
-	/// Specify function to call on cancel.
+	/// Return the text of the `cancel_return` function instead of an `Err` when the prompt is cancelled,
+	/// as if it was submitted.
+	///
+	/// The text isn't validated, and is parsed by the parse methods, which are still cancelled if it can't be parsed.
 	///
 	/// # Examples
 	///
 	/// ```no_run
-	/// use may_clack::{input, cancel};
+	/// use may_clack::input;
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
-	/// let answer = input("message").cancel(do_cancel).interact()?;
-	/// println!("answer {:?}", answer);
+	/// // ctrl+c skips the question
+	/// let nickname = input("nickname").on_cancel_return(String::new).interact()?;
+	/// let port = input("port").on_cancel_return(|| "8080".into()).parse::<u16>()?;
 	/// # Ok(())
 	/// # }
-	///
-	/// fn do_cancel() {
-	///     cancel!("operation cancelled");
-	///     panic!("operation cancelled");
-	/// }
-	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
+	/// ```
+	pub fn on_cancel_return<F>(&mut self, cancel_return: F) -> &mut Self
 	where
-		F: Fn() + 'static,
+		F: Fn() -> String + Send + Sync + 'static,
 	{
-		let cancel = Box::new(cancel);
-		self.cancel = Some(cancel);
+		self.cancel_return = Some(Arc::new(cancel_return));
 		self
 	}
 
-	fn interact_once<T: FromStr>(&self, enforce_non_empty: bool) -> Result<Option<T>, ClackError>
+	/// Replace a cancelled `result` with the text of [`Input::on_cancel_return()`], parsed with `parse`.
+	fn or_cancel_return<T, F>(
+		&self,
+		result: Result<T, ClackError>,
+		parse: F,
+	) -> Result<T, ClackError>
 	where
-		T::Err: Error,
+		F: FnOnce(String) -> Option<T>,
+	{
+		match (result, self.cancel_return.as_deref()) {
+			(Err(ClackError::Cancelled), Some(cancel_return)) => {
+				parse(cancel_return()).ok_or(ClackError::Cancelled)
+			}
+			(result, _) => result,
+		}
+	}
+
+	fn interact_once<T: 'static, F>(
+		&self,
+		r: &mut Renderer,
+		enforce_non_empty: bool,
+		parse: F,
+	) -> Result<Option<T>, ClackError>
+	where
+		F: Fn(&str) -> Result<T, Cow<'static, str>>,
 	{
-		let prompt = format!("{}  ", *chars::BAR);
+		let prefix = self.prefix.as_deref().unwrap_or_default();
+		let prompt = format!("{}  {}", *chars::BAR, prefix);
 
-		let mut editor = Editor::new()?;
-		let helper = PlaceholderHighlighter::new(self.placeholder.as_deref());
+		let mut editor = editor::editor()?;
+		let helper = PlaceholderHighlighter::new(self.placeholder.as_deref())
+			.suffix(self.suffix.as_deref())
+			.complete(self.complete.as_deref())
+			.limit(self.limit);
 		editor.set_helper(Some(helper));
+		self.load_history(&mut editor);
+		editor::bind_placeholder(&mut editor, self.placeholder.as_deref());
+		editor::bind_limit(&mut editor, self.limit);
+		let cancelled = editor::bind_cancel(&mut editor, self.context.token());
 
-		let mut initial_value = self.initial_value.as_deref().map(Cow::Borrowed);
+		let cached = self.context.cached().and_then(Cached::as_str);
+		let initial_value = cached.or(self.initial_value.as_deref());
+		let mut initial_value = initial_value.map(Cow::Borrowed);
+		let mut attempt = Attempt::default();
 		loop {
-			let line = if let Some(ref init) = initial_value {
-				editor.readline_with_initial(&prompt, (init, ""))
+			let line = if self.multiline {
+				let error = attempt.previous_error.as_deref();
+				self.read_multiline(r, initial_value.as_deref(), error)?
+			} else if self.context.is_cancelled() {
+				Err(initial_value.as_deref().unwrap_or_default().to_owned())
 			} else {
-				editor.readline(&prompt)
+				let initial = initial_value.as_deref();
+				let line = editor::readline(&mut editor, &prompt, initial, self.context.token());
+				r.moved_down();
+				line.map_err(|_| editor::cancelled_line(&cancelled))
 			};
 
-			// todo this looks refactor-able
-			if let Ok(value) = line {
-				if value.is_empty() {
-					if enforce_non_empty {
-						initial_value = None;
-
-						if let Some(helper) = editor.helper_mut() {
-							helper.is_val = true;
-						}
-
-						self.w_val("value is required");
-					} else {
-						break Ok(None);
+			let value = match line {
+				Ok(value) => value,
+				Err(partial) => {
+					self.w_cancel(r);
+					if let Some(cancel) = self.cancel.as_deref() {
+						cancel(&partial);
 					}
-				} else if let Err(text) = self.do_validate(&value) {
-					initial_value = Some(Cow::Owned(value));
 
-					if let Some(helper) = editor.helper_mut() {
-						helper.is_val = true;
-					}
+					break Err(ClackError::Cancelled);
+				}
+			};
+			attempt.next();
 
-					self.w_val(&text);
-				} else {
-					match value.parse::<T>() {
-						Ok(val) => break Ok(Some(val)),
-						Err(err) => {
-							initial_value = Some(Cow::Owned(value));
+			let value = match &self.default_value {
+				Some(default) if self.transform(&value).is_empty() => default.clone(),
+				_ => value,
+			};
 
-							if let Some(helper) = editor.helper_mut() {
-								helper.is_val = true;
-							}
+			let answer = self.transform(&value);
+			let text = if answer.is_empty() {
+				if !enforce_non_empty {
+					break Ok(None);
+				}
 
-							self.w_val(&err.to_string());
-						}
+				initial_value = None;
+				Cow::Borrowed("value is required")
+			} else if let Err(text) = self.do_validate(&value, &attempt) {
+				initial_value = Some(Cow::Owned(value));
+				text
+			} else {
+				match parse(&answer) {
+					Ok(val) => {
+						self.save_history(&value);
+						break Ok(Some(val));
+					}
+					Err(text) => {
+						initial_value = Some(Cow::Owned(value));
+						text
 					}
 				}
-			} else {
-				break Err(ClackError::Cancelled);
+			};
+
+			if let Some(helper) = editor.helper_mut() {
+				helper.is_val = true;
+			}
+
+			attempt.reject(&text);
+			if attempt.is_last(self.max_attempts) {
+				self.w_abort(r, &text);
+				let last_error = text.into_owned();
+				break Err(ClackError::TooManyAttempts { last_error });
 			}
+
+			self.w_val(r, &text);
 		}
 	}
 
@@ -324,29 +1217,59 @@ impl<M: Display> Input<M> {
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn parse<T: FromStr + Display>(&self) -> Result<T, ClackError>
+	pub fn parse<T: FromStr + Display + 'static>(&self) -> Result<T, ClackError>
 	where
 		T::Err: Error,
 	{
-		self.w_init();
+		self.parse_with(from_str::<T>)
+	}
+
+	/// Like [`Input::parse()`], but parses the value with the `parse` function,
+	/// for types that don't implement [`FromStr`].
+	///
+	/// If it returns an error, the error is shown and the user can edit the value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let base = 8000;
+	/// let port = input("port").placeholder("+80").parse_with(|value| {
+	///     // either relative to the base port or absolute
+	///     let port = match value.strip_prefix('+') {
+	///         Some(offset) => offset.parse::<u16>().map(|offset| base + offset),
+	///         None => value.parse::<u16>(),
+	///     };
+	///     port.map_err(|_| Cow::Borrowed("invalid port"))
+	/// })?;
+	/// println!("port {}", port);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn parse_with<T, F>(&self, parse: F) -> Result<T, ClackError>
+	where
+		T: Display + 'static,
+		F: Fn(&str) -> Result<T, Cow<'static, str>>,
+	{
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_init(&mut r);
 
-		let interact = self.interact_once::<T>(true);
-		match interact {
+		let interact = self.interact_once(&mut r, true, &parse);
+		let answer = match interact {
 			Ok(Some(value)) => {
-				self.w_out(&value);
+				self.w_out(&mut r, &value);
 				Ok(value)
 			}
 			Ok(None) => unreachable!(),
-			Err(ClackError::Cancelled) => {
-				self.w_cancel();
-				if let Some(cancel) = self.cancel.as_deref() {
-					cancel();
-				}
-
-				Err(ClackError::Cancelled)
-			}
 			Err(err) => Err(err),
-		}
+		};
+
+		self.or_cancel_return(answer, |text| parse(&text).ok())
 	}
 
 	/// Like [`Input::parse()`], but it also allows empty line submits like [`Input::interact()`].
@@ -361,33 +1284,45 @@ impl<M: Display> Input<M> {
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn maybe_parse<T: FromStr + Display>(&self) -> Result<Option<T>, ClackError>
+	pub fn maybe_parse<T: FromStr + Display + 'static>(&self) -> Result<Option<T>, ClackError>
 	where
 		T::Err: Error,
 	{
-		self.w_init();
+		self.maybe_parse_with(from_str::<T>)
+	}
+
+	/// Like [`Input::maybe_parse()`], but parses the value with the `parse` function.
+	fn maybe_parse_with<T, F>(&self, parse: F) -> Result<Option<T>, ClackError>
+	where
+		T: Display + 'static,
+		F: Fn(&str) -> Result<T, Cow<'static, str>>,
+	{
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_init(&mut r);
 
-		let interact = self.interact_once::<T>(false);
-		match interact {
+		let interact = self.interact_once(&mut r, false, &parse);
+		let answer = match interact {
 			Ok(val) => {
 				if let Some(val) = &val {
-					self.w_out(val);
+					self.w_out(&mut r, val);
 				} else {
-					self.w_out("");
+					self.w_out(&mut r, "");
 				}
 
 				Ok(val)
 			}
-			Err(ClackError::Cancelled) => {
-				self.w_cancel();
-				if let Some(cancel) = self.cancel.as_deref() {
-					cancel();
-				}
+			Err(err) => Err(err),
+		};
 
-				Err(ClackError::Cancelled)
+		self.or_cancel_return(answer, |text| {
+			if text.is_empty() {
+				Some(None)
+			} else {
+				parse(&text).ok().map(Some)
 			}
-			Err(err) => Err(err),
-		}
+		})
 	}
 
 	/// Like [`Input::interact()`], but does not return an empty line.
@@ -404,25 +1339,22 @@ impl<M: Display> Input<M> {
 	/// # }
 	/// ```
 	pub fn required(&self) -> Result<String, ClackError> {
-		self.w_init();
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_init(&mut r);
 
-		let interact = self.interact_once::<String>(true);
-		match interact {
+		let interact = self.interact_once(&mut r, true, |value| Ok(value.to_owned()));
+		let answer = match interact {
 			Ok(Some(value)) => {
-				self.w_out(&value);
+				self.w_out(&mut r, &value);
 				Ok(value)
 			}
 			Ok(None) => unreachable!(),
-			Err(ClackError::Cancelled) => {
-				self.w_cancel();
-				if let Some(cancel) = self.cancel.as_deref() {
-					cancel();
-				}
-
-				Err(ClackError::Cancelled)
-			}
 			Err(err) => Err(err),
-		}
+		};
+
+		self.or_cancel_return(answer, Some)
 	}
 
 	/// Waits for the user to submit a line of text.
@@ -455,82 +1387,265 @@ impl<M: Display> Input<M> {
 	/// }
 	/// ```
 	pub fn interact(&self) -> Result<Option<String>, ClackError> {
-		self.w_init();
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_init(&mut r);
 
-		let interact = self.interact_once(false);
-		match interact {
+		let interact = self.interact_once(&mut r, false, |value| Ok(value.to_owned()));
+		let answer = match interact {
 			Ok(val) => {
 				let v = val.as_deref().unwrap_or("");
-				self.w_out(v);
+				self.w_out(&mut r, v);
 				Ok(val)
 			}
-			Err(ClackError::Cancelled) => {
-				self.w_cancel();
-				if let Some(cancel) = self.cancel.as_deref() {
-					cancel();
-				}
-
-				Err(ClackError::Cancelled)
-			}
 			Err(err) => Err(err),
-		}
+		};
+
+		self.or_cancel_return(answer, |text| Some((!text.is_empty()).then_some(text)))
+	}
+
+	/// Draw the prompt as if the `value` was submitted, without waiting for the user.
+	///
+	/// Useful to show the answers of an earlier run as static lines before the first live prompt.
+	/// The [`on_submit`](Input::on_submit()) hook isn't called,
+	/// and the answer isn't added to the [transcript](crate::transcript) or the [`json_mode()`](crate::json_mode) summary.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// input("name").render_submitted("may");
+	/// let answer = input("description").interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn render_submitted(&self, value: &str) {
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_submitted(&mut r, value, true);
 	}
 }
 
 impl<M: Display> Input<M> {
-	fn w_init(&self) {
-		let mut stdout = stdout();
+	/// Lines of the message, with the default value next to it.
+	fn fmt_message<S: Display, B: Display>(&self, r: &Renderer, step: S, bar: B) -> Vec<String> {
+		let mut lines = r.message(step, bar, &self.message, self.extra.as_deref());
+		if let Some(default) = &self.default_value {
+			let default = format!("(default: {})", default);
+			lines[0] = format!("{} {}", lines[0], default.dimmed());
+		}
+
+		lines
+	}
+
+	fn w_init(&self, r: &mut Renderer) {
+		r.keys(&[("enter", "submit")]);
+
+		let mut lines = vec![chars::BAR.to_string()];
+		lines.extend(self.fmt_message(r, (*chars::STEP_ACTIVE).cyan(), (*chars::BAR).cyan()));
+
+		let row = lines.len();
+		lines.push((*chars::BAR).cyan().to_string());
+		lines.push(render::end(self.help.as_deref()));
+
+		r.draw(lines, row);
+
+		let _ = frame::flush();
+	}
+
+	/// Read the value of a multiline input, or the text typed so far as an `Err` if it was cancelled.
+	fn read_multiline(
+		&self,
+		r: &mut Renderer,
+		initial_value: Option<&str>,
+		error: Option<&str>,
+	) -> Result<Result<String, String>, ClackError> {
+		r.hide_cursor();
+		r.keys(&[("enter", "newline"), ("alt+enter", "submit")]);
+
+		let mut text = Multiline::new(initial_value.unwrap_or_default());
+		self.draw_multiline(r, &text, error);
+
+		let _raw = RawMode::enable()?;
 
-		println!("{}", *chars::BAR);
-		println!("{}  {}", (*chars::STEP_ACTIVE).cyan(), self.message);
-		println!("{}", (*chars::BAR).cyan());
-		print!("{}", (*chars::BAR_END).cyan());
+		// whether esc was pressed, so enter submits the value
+		let mut escaped = false;
+		let value = loop {
+			frame::flush()?;
 
-		let _ = stdout.queue(cursor::MoveToPreviousLine(1));
-		let _ = stdout.flush();
+			let Some(event) = r.read_event(None)? else {
+				continue;
+			};
+			if let term::Event::Resize(width, height) = event {
+				r.resize(width, height);
+				self.draw_multiline(r, &text, error);
+			}
+
+			let term::Event::Key(key) = event else {
+				continue;
+			};
+
+			if key.kind != KeyEventKind::Press {
+				continue;
+			}
+
+			let is_full =
+				matches!(self.limit, Some(Limit::Hard(max)) if text.value.chars().count() >= max);
+			match (key.code, key.modifiers) {
+				(term::KeyCode::Char('c' | 'd'), KeyModifiers::CONTROL) => break Err(text.value),
+				(term::KeyCode::Enter, modifiers)
+					if escaped
+						|| modifiers.intersects(KeyModifiers::ALT | KeyModifiers::CONTROL) =>
+				{
+					break Ok(text.value);
+				}
+				(term::KeyCode::Enter, _) if !is_full => text.insert('\n'),
+				(term::KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) if !is_full => {
+					text.insert(c);
+				}
+				(term::KeyCode::Tab | term::KeyCode::Right, _) if text.value.is_empty() => {
+					text = Multiline::new(self.placeholder.as_deref().unwrap_or_default());
+				}
+				(term::KeyCode::Backspace, _) => text.backspace(),
+				(term::KeyCode::Delete, _) => text.delete(),
+				(term::KeyCode::Left, _) => text.left(),
+				(term::KeyCode::Right, _) => text.right(),
+				(term::KeyCode::Up, _) => text.up(),
+				(term::KeyCode::Down, _) => text.down(),
+				(term::KeyCode::Home, _) => text.home(),
+				(term::KeyCode::End, _) => text.end(),
+				_ => {}
+			}
+
+			escaped = key.code == term::KeyCode::Esc;
+			self.draw_multiline(r, &text, error);
+		};
+
+		Ok(value)
+	}
+
+	fn draw_multiline(&self, r: &mut Renderer, text: &Multiline, error: Option<&str>) {
+		let mut lines = vec![chars::BAR.to_string()];
+		let bar = if error.is_some() {
+			lines.extend(self.fmt_message(
+				r,
+				(*chars::STEP_ERROR).yellow(),
+				(*chars::BAR).yellow(),
+			));
+			(*chars::BAR).yellow().to_string()
+		} else {
+			lines.extend(self.fmt_message(r, (*chars::STEP_ACTIVE).cyan(), (*chars::BAR).cyan()));
+			(*chars::BAR).cyan().to_string()
+		};
 
-		print!("{}  ", (*chars::BAR).cyan());
-		let _ = stdout.flush();
+		let placeholder = self
+			.placeholder
+			.as_deref()
+			.filter(|_| text.value.is_empty());
+		let (value, row) = match placeholder {
+			Some(placeholder) => (vec![editor::ghost(placeholder)], 0),
+			None => text.fmt_lines(),
+		};
+
+		let row = lines.len() + row;
+		let prefix = self.prefix.as_deref().unwrap_or_default();
+		for (i, line) in value.iter().enumerate() {
+			let prefix = if i == 0 { prefix } else { "" };
+			lines.push(format!("{}  {}{}", bar, prefix.dimmed(), line));
+		}
+
+		match error {
+			Some(error) => lines.push(format!(
+				"{}  {}",
+				(*chars::BAR_END).yellow(),
+				error.yellow()
+			)),
+			None => lines.push(render::end(self.help.as_deref())),
+		}
+
+		r.draw(lines, row);
 	}
 
-	fn w_val(&self, text: &str) {
-		let mut stdout = stdout();
-		let _ = stdout.queue(cursor::MoveToPreviousLine(2));
-		let _ = stdout.flush();
+	fn w_val(&self, r: &mut Renderer, text: &str) {
+		let mut lines = vec![chars::BAR.to_string()];
+		lines.extend(self.fmt_message(r, (*chars::STEP_ERROR).yellow(), (*chars::BAR).yellow()));
+
+		let row = lines.len();
+		lines.push((*chars::BAR).yellow().to_string());
+		lines.push(format!("{}  {}", (*chars::BAR_END).yellow(), text.yellow()));
 
-		println!("{}  {}", (*chars::STEP_ERROR).yellow(), self.message);
-		println!("{}", (*chars::BAR).yellow());
+		r.draw(lines, row);
+
+		let _ = frame::flush();
+	}
 
-		print!("{}", ansi::CLEAR_LINE);
-		print!("{}  {}", (*chars::BAR_END).yellow(), text.yellow());
+	fn w_out<D: Display>(&self, r: &mut Renderer, value: D) {
+		let text = value.to_string();
+		self.w_submitted(r, &text, false);
 
-		let _ = stdout.queue(cursor::MoveToPreviousLine(1));
-		let _ = stdout.flush();
+		if let Some(on_submit) = self.on_submit.as_deref() {
+			on_submit(&text);
+		}
 	}
 
-	fn w_out<D: Display>(&self, value: D) {
-		let mut stdout = stdout();
-		let _ = stdout.queue(cursor::MoveToPreviousLine(2));
-		let _ = stdout.flush();
+	fn w_submitted(&self, r: &mut Renderer, text: &str, preview: bool) {
+		let value = match self.format.as_deref() {
+			Some(format) => format(text),
+			None if text.is_empty() => String::new(),
+			None => format!(
+				"{}{}{}",
+				self.prefix.as_deref().unwrap_or_default(),
+				text,
+				self.suffix.as_deref().unwrap_or_default()
+			),
+		};
+		if preview {
+			transcript::preview(&self.message, &value);
+		} else {
+			transcript::prompt(&self.message, Some(&value));
+		}
 
-		println!("{}  {}", (*chars::STEP_SUBMIT).green(), self.message);
-		print!("{}", ansi::CLEAR_LINE);
-		println!("{}  {}", *chars::BAR, value.dimmed());
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_SUBMIT).green();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.extend(
+			value
+				.split('\n')
+				.map(|line| format!("{}  {}", *chars::BAR, line.dimmed())),
+		);
 
-		print!("{}", ansi::CLEAR_LINE);
+		r.finish(lines);
 	}
 
-	fn w_cancel(&self) {
-		let mut stdout = stdout();
-		let _ = stdout.queue(cursor::MoveToPreviousLine(2));
-		let _ = stdout.flush();
+	/// Draw the prompt as aborted after too many attempts, with the last `error`.
+	fn w_abort(&self, r: &mut Renderer, error: &str) {
+		transcript::prompt(&self.message, None);
+
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_CANCEL).red();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!("{}  {}", *chars::BAR, error.red()));
 
-		println!("{}  {}", (*chars::STEP_CANCEL).red(), self.message);
+		r.finish(lines);
+	}
+
+	fn w_cancel(&self, r: &mut Renderer) {
+		transcript::prompt(&self.message, None);
 
-		print!("{}", ansi::CLEAR_LINE);
-		println!("{}  {}", *chars::BAR, "cancelled".strikethrough().dimmed());
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_CANCEL).red();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!(
+			"{}  {}",
+			*chars::BAR,
+			"cancelled".strikethrough().dimmed()
+		));
 
-		print!("{}", ansi::CLEAR_LINE);
+		r.finish(lines);
 	}
 }
 
@@ -538,3 +1653,114 @@ impl<M: Display> Input<M> {
 pub fn input<M: Display>(message: M) -> Input<M> {
 	Input::new(message)
 }
+
+/// `ParsedInput` struct
+///
+/// An [`Input`] that parses the value to a `T` and validates it, see [`Input::validate_parsed()`].
+///
+/// If the validation fails, the error is shown and the user can edit the value.
+pub struct ParsedInput<'a, M: Display, T> {
+	input: &'a Input<M>,
+	validate: Box<ValidateParsedFn<'a, T>>,
+}
+
+impl<M: Display, T: Display + 'static> ParsedInput<'_, M, T> {
+	/// Parse the `value` with `parse` and validate it.
+	fn check<'s, F>(&'s self, parse: F) -> impl Fn(&str) -> Result<T, Cow<'static, str>> + 's
+	where
+		F: Fn(&str) -> Result<T, Cow<'static, str>> + 's,
+	{
+		move |value| parse(value).and_then(|value| (self.validate)(&value).map(|()| value))
+	}
+
+	/// Like [`Input::parse()`], but validates the parsed value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("even number")
+	///     .validate_parsed(|value: &i32| {
+	///         if value % 2 == 0 {
+	///             Ok(())
+	///         } else {
+	///             Err(Cow::Borrowed("number must be even"))
+	///         }
+	///     })
+	///     .parse()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn parse(&self) -> Result<T, ClackError>
+	where
+		T: FromStr,
+		T::Err: Error,
+	{
+		self.input.parse_with(self.check(from_str::<T>))
+	}
+
+	/// Like [`Input::maybe_parse()`], but validates the parsed value.
+	///
+	/// An empty line isn't validated.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("port")
+	///     .validate_parsed(|port: &u16| {
+	///         if *port > 1024 {
+	///             Ok(())
+	///         } else {
+	///             Err(Cow::Borrowed("port must be above 1024"))
+	///         }
+	///     })
+	///     .maybe_parse()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn maybe_parse(&self) -> Result<Option<T>, ClackError>
+	where
+		T: FromStr,
+		T::Err: Error,
+	{
+		self.input.maybe_parse_with(self.check(from_str::<T>))
+	}
+
+	/// Like [`Input::parse_with()`], but validates the parsed value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::input;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = input("hex color")
+	///     .validate_parsed(|color: &u32| {
+	///         if *color <= 0xffffff {
+	///             Ok(())
+	///         } else {
+	///             Err(Cow::Borrowed("color must have 6 digits"))
+	///         }
+	///     })
+	///     .parse_with(|value| {
+	///         let value = value.trim_start_matches('#');
+	///         u32::from_str_radix(value, 16).map_err(|_| Cow::Borrowed("invalid color"))
+	///     })?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn parse_with<F>(&self, parse: F) -> Result<T, ClackError>
+	where
+		F: Fn(&str) -> Result<T, Cow<'static, str>>,
+	{
+		self.input.parse_with(self.check(parse))
+	}
+}
@@ -0,0 +1,123 @@
+//! Intro
+
+use super::{frame, transcript};
+use crate::style::{chars, BadgeStyle};
+use owo_colors::OwoColorize;
+use std::fmt::Display;
+
+/// `Intro` struct.
+///
+/// Builder for the message that starts a prompt session, see also [`intro!`](crate::intro!).
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::{intro::Intro, style::BadgeStyle};
+///
+/// Intro::new("create-app")
+///     .badge_style(BadgeStyle::Reversed)
+///     .subtitle("v1.0.0")
+///     .print();
+/// ```
+pub struct Intro<M: Display> {
+	title: M,
+	badge_style: BadgeStyle,
+	subtitle: Option<String>,
+	blank_line: bool,
+}
+
+impl<M: Display> Intro<M> {
+	/// Creates a new `Intro` struct.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::intro::Intro;
+	///
+	/// Intro::new("title").print();
+	/// ```
+	pub fn new(title: M) -> Intro<M> {
+		Intro {
+			title,
+			badge_style: BadgeStyle::Reversed,
+			subtitle: None,
+			blank_line: true,
+		}
+	}
+
+	/// Change the style of the title.
+	///
+	/// Default: [`BadgeStyle::Reversed`]
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::{intro::Intro, style::BadgeStyle};
+	/// use owo_colors::AnsiColors;
+	///
+	/// Intro::new("title")
+	///     .badge_style(BadgeStyle::Color(AnsiColors::Cyan))
+	///     .print();
+	/// ```
+	pub fn badge_style(&mut self, style: BadgeStyle) -> &mut Self {
+		self.badge_style = style;
+		self
+	}
+
+	/// Add a dimmed subtitle below the title.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::intro::Intro;
+	///
+	/// Intro::new("title").subtitle("subtitle").print();
+	/// ```
+	pub fn subtitle<S: ToString>(&mut self, subtitle: S) -> &mut Self {
+		self.subtitle = Some(subtitle.to_string());
+		self
+	}
+
+	/// Whether to write an empty line before the intro.
+	///
+	/// Default: `true`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::intro::Intro;
+	///
+	/// Intro::new("title").blank_line(false).print();
+	/// ```
+	pub fn blank_line(&mut self, blank_line: bool) -> &mut Self {
+		self.blank_line = blank_line;
+		self
+	}
+
+	/// Write the intro.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::intro::Intro;
+	///
+	/// Intro::new("title").print();
+	/// ```
+	pub fn print(&self) {
+		let title = self.badge_style.badge(&self.title);
+		if frame::is_quiet() {
+			frame::line(title);
+		} else {
+			if self.blank_line {
+				frame::line("");
+			}
+
+			frame::line(format!("{}  {}", *chars::BAR_START, title));
+			if let Some(subtitle) = &self.subtitle {
+				frame::line(format!("{}  {}", *chars::BAR, subtitle.dimmed()));
+			}
+		}
+
+		transcript::message("intro", &self.title.to_string());
+	}
+}
@@ -0,0 +1,209 @@
+//! Tracing layer
+
+use super::render;
+use crate::style::chars;
+use owo_colors::OwoColorize;
+use std::{
+	fmt::{Debug, Write},
+	time::Instant,
+};
+use tracing_core::{
+	field::{Field, Visit},
+	span, Event, Level, LevelFilter, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan};
+
+/// `Layer` struct.
+///
+/// A [`tracing_subscriber`] layer that writes the events like the [`info!`](crate::info!),
+/// [`warn!`](crate::warn!) and [`err!`](crate::err!) messages, and the opening and closing of spans.
+///
+/// Events and spans recorded while a prompt is active are held back and written once the prompt is finished,
+/// so instrumented code running on other threads doesn't break the prompt.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{confirm, layer::Layer};
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let subscriber = tracing_subscriber::registry().with(Layer::new());
+/// tracing::subscriber::set_global_default(subscriber)?;
+///
+/// std::thread::spawn(|| tracing::info!(files = 3, "downloading"));
+/// let answer = confirm("continue?").interact()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Layer {
+	level: LevelFilter,
+	spans: bool,
+}
+
+impl Layer {
+	/// Creates a new `Layer` struct.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::layer::Layer;
+	/// use tracing_subscriber::layer::SubscriberExt;
+	///
+	/// let subscriber = tracing_subscriber::registry().with(Layer::new());
+	/// ```
+	pub fn new() -> Layer {
+		Layer {
+			level: LevelFilter::INFO,
+			spans: true,
+		}
+	}
+
+	/// Change the maximum level of the events and spans that are written.
+	///
+	/// Default: [`LevelFilter::INFO`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::layer::Layer;
+	/// use tracing::level_filters::LevelFilter;
+	/// use tracing_subscriber::layer::SubscriberExt;
+	///
+	/// let layer = Layer::new().level(LevelFilter::DEBUG).clone();
+	/// let subscriber = tracing_subscriber::registry().with(layer);
+	/// ```
+	pub fn level(&mut self, level: LevelFilter) -> &mut Self {
+		self.level = level;
+		self
+	}
+
+	/// Whether to write a line when a span is opened and closed.
+	///
+	/// Default: `true`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::layer::Layer;
+	/// use tracing_subscriber::layer::SubscriberExt;
+	///
+	/// let layer = Layer::new().spans(false).clone();
+	/// let subscriber = tracing_subscriber::registry().with(layer);
+	/// ```
+	pub fn spans(&mut self, spans: bool) -> &mut Self {
+		self.spans = spans;
+		self
+	}
+
+	fn is_enabled(&self, level: &Level) -> bool {
+		self.level >= *level
+	}
+}
+
+impl Default for Layer {
+	fn default() -> Self {
+		Layer::new()
+	}
+}
+
+/// Time a span was opened at, stored in the extensions of the span.
+struct Opened(Instant);
+
+impl<S> tracing_subscriber::Layer<S> for Layer
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+		let level = event.metadata().level();
+		if !self.is_enabled(level) {
+			return;
+		}
+
+		let step = match *level {
+			Level::ERROR => (*chars::STEP_CANCEL).red().to_string(),
+			Level::WARN => (*chars::STEP_ERROR).yellow().to_string(),
+			Level::INFO => (*chars::STEP_SUBMIT).cyan().to_string(),
+			_ => (*chars::STEP_SUBMIT).dimmed().to_string(),
+		};
+
+		let mut fields = Fields::default();
+		event.record(&mut fields);
+		render::log(step, &fields.fmt());
+	}
+
+	fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+		if !self.spans || !self.is_enabled(attrs.metadata().level()) {
+			return;
+		}
+
+		let Some(span) = ctx.span(id) else {
+			return;
+		};
+		span.extensions_mut().insert(Opened(Instant::now()));
+
+		let mut fields = Fields::default();
+		attrs.record(&mut fields);
+
+		let message = format!("{}{}", span.name(), fields.fmt_fields());
+		render::log((*chars::STEP_ACTIVE).magenta(), &message);
+	}
+
+	fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+		let Some(span) = ctx.span(&id) else {
+			return;
+		};
+
+		let extensions = span.extensions();
+		if let Some(Opened(opened)) = extensions.get::<Opened>() {
+			let elapsed = format!("({:.2?})", opened.elapsed());
+			let message = format!("{} {}", span.name(), elapsed.dimmed());
+			render::log((*chars::STEP_SUBMIT).green(), &message);
+		}
+	}
+}
+
+/// Visitor that collects the message and the other fields of an event or span.
+#[derive(Default)]
+struct Fields {
+	message: String,
+	fields: Vec<String>,
+}
+
+impl Fields {
+	/// Message followed by the dimmed fields.
+	fn fmt(&self) -> String {
+		if self.message.is_empty() {
+			self.fields.join(" ").dimmed().to_string()
+		} else {
+			format!("{}{}", self.message, self.fmt_fields())
+		}
+	}
+
+	/// Dimmed fields, each with a space in front.
+	fn fmt_fields(&self) -> String {
+		self.fields
+			.iter()
+			.map(|field| format!(" {}", field.dimmed()))
+			.collect()
+	}
+}
+
+impl Visit for Fields {
+	fn record_str(&mut self, field: &Field, value: &str) {
+		if field.name() == "message" {
+			self.message.push_str(value);
+		} else {
+			self.fields.push(format!("{}={}", field.name(), value));
+		}
+	}
+
+	fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+		if field.name() == "message" {
+			let _ = write!(self.message, "{:?}", value);
+		} else {
+			self.fields.push(format!("{}={:?}", field.name(), value));
+		}
+	}
+}
@@ -0,0 +1,441 @@
+//! Option list helpers shared by [`super::select`] and [`super::multi_select`]
+
+use super::{
+	frame,
+	render::{self, Renderer},
+	spinner::{self, SpinRow},
+	text, transcript,
+};
+use crate::{
+	error::ClackError,
+	style::{chars, is_unicode},
+};
+use crossterm::{
+	event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+	terminal,
+};
+use owo_colors::OwoColorize;
+use std::{
+	cmp::Ordering,
+	error::Error,
+	fmt::Display,
+	ops::Deref,
+	sync::{Arc, Mutex, PoisonError},
+	time::Instant,
+};
+
+/// A row in an option list.
+#[derive(Debug, Clone)]
+pub(super) enum Row<O> {
+	/// Selectable option
+	Opt(O),
+	/// Non-selectable separator line
+	Separator,
+	/// Non-selectable header label
+	Header(String),
+}
+
+impl<O> Row<O> {
+	pub fn opt(&self) -> Option<&O> {
+		match self {
+			Row::Opt(opt) => Some(opt),
+			_ => None,
+		}
+	}
+
+	pub fn opt_mut(&mut self) -> Option<&mut O> {
+		match self {
+			Row::Opt(opt) => Some(opt),
+			_ => None,
+		}
+	}
+
+	pub fn is_opt(&self) -> bool {
+		matches!(self, Row::Opt(_))
+	}
+
+	/// Borrow the option of the row, see [`Slot`].
+	pub fn borrowed(&self) -> Row<Slot<'_, O>> {
+		match self {
+			Row::Opt(opt) => Row::Opt(Slot::Built(opt)),
+			Row::Separator => Row::Separator,
+			Row::Header(label) => Row::Header(label.clone()),
+		}
+	}
+
+	/// Format a non-selectable row.
+	pub fn fmt_separator(&self, width: Option<usize>) -> String {
+		match self {
+			Row::Opt(_) => unreachable!("options are not separators"),
+			Row::Separator => chars::SEPARATOR.repeat(10).dimmed().to_string(),
+			Row::Header(label) => text::fit(label, width, 3).bold().to_string(),
+		}
+	}
+}
+
+/// An option of an active prompt, borrowed from the builder or loaded while the prompt is active,
+/// so interacting with the prompt doesn't change the builder.
+pub(super) enum Slot<'a, O> {
+	/// Option added to the builder
+	Built(&'a O),
+	/// Option loaded, received or refreshed while the prompt is active
+	Loaded(O),
+}
+
+impl<O> Deref for Slot<'_, O> {
+	type Target = O;
+
+	fn deref(&self) -> &O {
+		match self {
+			Slot::Built(opt) => opt,
+			Slot::Loaded(opt) => opt,
+		}
+	}
+}
+
+/// Rows of the options loaded while the prompt is active.
+pub(super) fn loaded<'a, O>(opts: Vec<O>) -> Vec<Row<Slot<'a, O>>> {
+	opts.into_iter()
+		.map(|opt| Row::Opt(Slot::Loaded(opt)))
+		.collect()
+}
+
+/// Icon drawn in front of an option label.
+#[derive(Debug, Clone)]
+pub(super) struct Icon {
+	icon: String,
+	/// Drawn instead of the icon in terminals without unicode support.
+	ascii: String,
+}
+
+impl Icon {
+	pub fn new(icon: String, ascii: String) -> Self {
+		Icon { icon, ascii }
+	}
+
+	/// The icon, or the ascii fallback if the terminal doesn't support unicode.
+	pub fn glyph(&self) -> &str {
+		if is_unicode() {
+			&self.icon
+		} else {
+			&self.ascii
+		}
+	}
+}
+
+/// Width of the icon column, the width of the widest icon.
+pub(super) fn icon_column<'a, I: Iterator<Item = Option<&'a Icon>>>(icons: I) -> usize {
+	icons
+		.flatten()
+		.map(|icon| text::width(icon.glyph()))
+		.max()
+		.unwrap_or(0)
+}
+
+/// Format the `icon` padded to the width of the `column` and followed by a space,
+/// so the labels line up, or nothing if none of the options have an icon.
+pub(super) fn fmt_icon(icon: Option<&Icon>, column: usize) -> String {
+	if column == 0 {
+		return String::new();
+	}
+
+	let glyph = icon.map_or("", Icon::glyph);
+	let pad = column.saturating_sub(text::width(glyph));
+	format!("{}{} ", glyph, " ".repeat(pad))
+}
+
+/// Row index of the first option.
+pub(super) fn first<O>(rows: &[Row<O>]) -> Option<usize> {
+	rows.iter().position(Row::is_opt)
+}
+
+/// Row index of the last option.
+pub(super) fn last<O>(rows: &[Row<O>]) -> Option<usize> {
+	rows.iter().rposition(Row::is_opt)
+}
+
+/// Row index of the next option after `idx`.
+pub(super) fn next<O>(rows: &[Row<O>], idx: usize) -> Option<usize> {
+	rows.iter()
+		.skip(idx + 1)
+		.position(Row::is_opt)
+		.map(|pos| pos + idx + 1)
+}
+
+/// Row index of the previous option before `idx`.
+pub(super) fn prev<O>(rows: &[Row<O>], idx: usize) -> Option<usize> {
+	rows[..idx].iter().rposition(Row::is_opt)
+}
+
+/// Row index of the option at or nearest to `idx`, preferring later options.
+pub(super) fn snap<O>(rows: &[Row<O>], idx: usize) -> Option<usize> {
+	let idx = idx.min(rows.len().checked_sub(1)?);
+	if rows[idx].is_opt() {
+		Some(idx)
+	} else {
+		next(rows, idx).or_else(|| prev(rows, idx))
+	}
+}
+
+/// Row index of the nth option.
+pub(super) fn nth<O>(rows: &[Row<O>], n: usize) -> Option<usize> {
+	rows.iter()
+		.enumerate()
+		.filter(|(_, row)| row.is_opt())
+		.nth(n)
+		.map(|(idx, _)| idx)
+}
+
+/// Amount of options.
+pub(super) fn count<O>(rows: &[Row<O>]) -> usize {
+	rows.iter().filter(|row| row.is_opt()).count()
+}
+
+/// Amount of options up to and including the row `idx`.
+pub(super) fn position<O>(rows: &[Row<O>], idx: usize) -> usize {
+	count(&rows[..=idx])
+}
+
+/// Resolve a `:` jump target in less mode.
+///
+/// Accepts either an item number (`12`) or a page number prefixed with `p` (`p3`),
+/// and returns the new `(idx, less_idx)`.
+///
+/// Pages are counted in options, like in the status line of [`fmt_less()`].
+pub(super) fn less_jump<O>(
+	target: &str,
+	rows: &[Row<O>],
+	less: u16,
+	less_idx: u16,
+) -> Option<(usize, u16)> {
+	let (idx, less_idx) = if let Some(page) = target.strip_prefix('p') {
+		let page = page.parse::<usize>().ok().filter(|&page| page > 0)?;
+		let item = (page - 1).saturating_mul(less as usize);
+		let item = item.min(count(rows).checked_sub(1)?);
+		(nth(rows, item)?, 0)
+	} else {
+		let item = target.parse::<usize>().ok().filter(|&item| item > 0)?;
+		let item = item.min(count(rows));
+		(nth(rows, item - 1)?, less_idx)
+	};
+
+	Some((idx, less_place(rows, idx, less, less_idx)))
+}
+
+/// Place the focused row `idx` on the page, starting from the proposed `less_idx`.
+///
+/// Keeps separators directly above the focused row visible and the page filled where possible.
+pub(super) fn less_place<O>(rows: &[Row<O>], idx: usize, less: u16, less_idx: u16) -> u16 {
+	let less_idx = less_idx.min(less - 1);
+
+	let above = rows[..idx]
+		.iter()
+		.rev()
+		.take_while(|row| !row.is_opt())
+		.count();
+	let above = above.min(less as usize - 1) as u16;
+
+	less_clamp(idx, rows.len(), less, less_idx.max(above))
+}
+
+/// Clamp `less_idx` so that the page around `idx` stays within the rows.
+pub(super) fn less_clamp(idx: usize, max: usize, less: u16, less_idx: u16) -> u16 {
+	let less_idx = less_idx.min(idx.min(u16::MAX as usize) as u16);
+	let min_less = (less as usize).saturating_sub(max - idx) as u16;
+	less_idx.max(min_less)
+}
+
+pub(super) type LoadFn<O> = dyn Fn() -> Result<Vec<O>, Box<dyn Error + Send + Sync>> + Send + Sync;
+pub(super) type Loader<O> = Option<Arc<LoadFn<O>>>;
+pub(super) type RefreshFn<O> = LoadFn<O>;
+pub(super) type EqFn<T> = dyn Fn(&T, &T) -> bool + Send + Sync;
+pub(super) type CmpFn<O> = dyn Fn(&O, &O) -> Ordering + Send + Sync;
+
+/// Hook for re-fetching the options mid-prompt.
+pub(super) struct Refresh<T, O> {
+	pub fetch: Arc<RefreshFn<O>>,
+	/// Used to find the previously focused and selected values in the new options.
+	pub eq: fn(&T, &T) -> bool,
+}
+
+/// Option indices of the first pair of equal values.
+pub(super) fn duplicate<'a, T: 'a>(
+	values: impl IntoIterator<Item = &'a T>,
+	eq: &EqFn<T>,
+) -> Option<(usize, usize)> {
+	let values = values.into_iter().collect::<Vec<_>>();
+	values.iter().enumerate().find_map(|(i, a)| {
+		let pos = values[i + 1..].iter().position(|b| eq(a, b));
+		pos.map(|pos| (i, i + 1 + pos))
+	})
+}
+
+/// Sort the options between the separators and headers with `cmp`, keeping equal options in order.
+///
+/// Returns the new row index of every row.
+pub(super) fn sort<O, F>(rows: &mut Vec<Row<O>>, cmp: F) -> Vec<usize>
+where
+	F: Fn(&O, &O) -> Ordering,
+{
+	let mut order = (0..rows.len()).collect::<Vec<_>>();
+	for group in order.split_mut(|&i| !rows[i].is_opt()) {
+		group.sort_by(|&a, &b| match (&rows[a], &rows[b]) {
+			(Row::Opt(a), Row::Opt(b)) => cmp(a, b),
+			_ => Ordering::Equal,
+		});
+	}
+
+	let mut old = std::mem::take(rows)
+		.into_iter()
+		.map(Some)
+		.collect::<Vec<_>>();
+	*rows = order
+		.iter()
+		.map(|&i| old[i].take().expect("every row is moved once"))
+		.collect();
+
+	let mut moved = vec![0; order.len()];
+	for (new, &old) in order.iter().enumerate() {
+		moved[old] = new;
+	}
+
+	moved
+}
+
+/// Format the status line of a page in less mode.
+///
+/// The position and the pages are counted in options, leaving out separators and headers.
+pub(super) fn fmt_less<O>(rows: &[Row<O>], less: u16, idx: usize, jump: Option<&str>) -> String {
+	let max = count(rows);
+	let amt = max.to_string().len();
+	let pos = position(rows, idx);
+	let page = (pos - 1) / less as usize + 1;
+	let pages = max.div_ceil(less as usize);
+
+	let mut line = format!(
+		"{}  ......... ({:#0amt$}/{}) page {}/{}",
+		(*chars::BAR).cyan(),
+		pos,
+		max,
+		page,
+		pages,
+		amt = amt
+	);
+	if let Some(jump) = jump {
+		let jump = format!(":{}", jump);
+		line = format!("{}  {}", line, jump.cyan());
+	}
+
+	line
+}
+
+/// Run an option loader, drawing a spinner row below the message while it resolves.
+pub(super) fn load<M, O, F>(r: &mut Renderer, message: &M, loader: F) -> Result<Vec<O>, ClackError>
+where
+	M: Display,
+	F: FnOnce() -> Result<Vec<O>, Box<dyn Error + Send + Sync>>,
+{
+	let mut lines = vec![chars::BAR.to_string()];
+	let (step, bar) = ((*chars::STEP_ACTIVE).cyan(), (*chars::BAR).cyan());
+	lines.extend(r.message(step, bar, message, None));
+
+	let row = lines.len();
+	lines.push(String::new());
+	lines.push((*chars::BAR_END).cyan().to_string());
+
+	r.draw(lines, row);
+	let _ = frame::flush();
+
+	let spinner = SpinRow::start("loading".into());
+	let loaded = loader();
+	spinner.stop();
+
+	loaded.map_err(|err| {
+		fail(r, message, &err);
+		ClackError::Load(err)
+	})
+}
+
+/// Finish the prompt with the `error` below the message, when the options can't be shown.
+pub(super) fn fail<M: Display, E: Display + ?Sized>(r: &mut Renderer, message: &M, error: &E) {
+	let mut lines = vec![chars::BAR.to_string()];
+	let step = (*chars::STEP_ERROR).yellow();
+	lines.extend(r.message(step, *chars::BAR, message, None));
+	lines.push(format!("{}  {}", *chars::BAR, error.to_string().yellow()));
+
+	r.finish(lines);
+}
+
+/// Options sent through a [`Feed`](super::select::Feed) that the prompt hasn't received yet.
+pub(super) struct Pending<O> {
+	pub opts: Vec<O>,
+	/// Whether all options were sent.
+	pub done: bool,
+}
+
+pub(super) type Incoming<O> = Arc<Mutex<Pending<O>>>;
+
+/// Take the options that arrived, and whether all options were sent.
+pub(super) fn receive<O>(incoming: &Incoming<O>) -> (Vec<O>, bool) {
+	let mut pending = incoming.lock().unwrap_or_else(PoisonError::into_inner);
+	(std::mem::take(&mut pending.opts), pending.done)
+}
+
+/// Wait for the first options to arrive, drawing a spinner row below the message since it `started`.
+///
+/// Returns the options that arrived, and whether all options were sent.
+pub(super) fn wait<M: Display, O>(
+	r: &mut Renderer,
+	message: &M,
+	incoming: &Incoming<O>,
+	started: Instant,
+) -> Result<(Vec<O>, bool), ClackError> {
+	terminal::enable_raw_mode()?;
+	let waited = wait_raw(r, message, incoming, started);
+	terminal::disable_raw_mode()?;
+	waited
+}
+
+fn wait_raw<M: Display, O>(
+	r: &mut Renderer,
+	message: &M,
+	incoming: &Incoming<O>,
+	started: Instant,
+) -> Result<(Vec<O>, bool), ClackError> {
+	loop {
+		let (opts, done) = receive(incoming);
+		if !opts.is_empty() || done {
+			return Ok((opts, done));
+		}
+
+		let mut lines = vec![chars::BAR.to_string()];
+		let (step, bar) = ((*chars::STEP_ACTIVE).cyan(), (*chars::BAR).cyan());
+		lines.extend(r.message(step, bar, message, None));
+
+		let row = lines.len();
+		lines.push(spinner::fmt_row("loading", started));
+		lines.push((*chars::BAR_END).cyan().to_string());
+
+		r.draw(lines, row);
+		frame::flush()?;
+
+		match r.read_event(Some(render::TICK))? {
+			Some(Event::Resize(width, height)) => r.resize(width, height),
+			Some(Event::Key(key))
+				if key.kind == KeyEventKind::Press
+					&& matches!(key.code, KeyCode::Char('c' | 'd'))
+					&& key.modifiers == KeyModifiers::CONTROL =>
+			{
+				transcript::prompt(message, None);
+
+				let mut lines = vec![chars::BAR.to_string()];
+				let step = (*chars::STEP_CANCEL).red();
+				lines.extend(r.message(step, *chars::BAR, message, None));
+				r.finish(lines);
+
+				return Err(ClackError::Cancelled);
+			}
+			_ => {}
+		}
+	}
+}
@@ -0,0 +1,123 @@
+//! Logger
+
+use super::render;
+use crate::style::chars;
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use owo_colors::OwoColorize;
+
+/// `Logger` struct.
+///
+/// A [`log`] logger that writes the records like the [`info!`](crate::info!),
+/// [`warn!`](crate::warn!) and [`err!`](crate::err!) messages.
+///
+/// Records logged while a prompt is active are held back and written once the prompt is finished,
+/// so logging from other threads doesn't break the prompt.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{confirm, logger::Logger};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// Logger::new().init()?;
+///
+/// std::thread::spawn(|| log::info!("downloading"));
+/// let answer = confirm("continue?").interact()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Logger {
+	level: LevelFilter,
+}
+
+impl Logger {
+	/// Creates a new `Logger` struct.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::logger::Logger;
+	///
+	/// # fn main() -> Result<(), log::SetLoggerError> {
+	/// Logger::new().init()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn new() -> Logger {
+		Logger {
+			level: LevelFilter::Info,
+		}
+	}
+
+	/// Change the maximum level of the records that are written.
+	///
+	/// Default: [`LevelFilter::Info`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use log::LevelFilter;
+	/// use may_clack::logger::Logger;
+	///
+	/// # fn main() -> Result<(), log::SetLoggerError> {
+	/// Logger::new().level(LevelFilter::Debug).init()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn level(&mut self, level: LevelFilter) -> &mut Self {
+		self.level = level;
+		self
+	}
+
+	/// Set the logger as the global logger.
+	///
+	/// # Errors
+	///
+	/// Returns an error if a global logger was already set.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::logger::Logger;
+	///
+	/// # fn main() -> Result<(), log::SetLoggerError> {
+	/// Logger::new().init()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn init(&self) -> Result<(), SetLoggerError> {
+		log::set_boxed_logger(Box::new(self.clone()))?;
+		log::set_max_level(self.level);
+		Ok(())
+	}
+}
+
+impl Default for Logger {
+	fn default() -> Self {
+		Logger::new()
+	}
+}
+
+impl Log for Logger {
+	fn enabled(&self, metadata: &Metadata) -> bool {
+		metadata.level() <= self.level
+	}
+
+	fn log(&self, record: &Record) {
+		if !self.enabled(record.metadata()) {
+			return;
+		}
+
+		let step = match record.level() {
+			Level::Error => (*chars::STEP_CANCEL).red().to_string(),
+			Level::Warn => (*chars::STEP_ERROR).yellow().to_string(),
+			Level::Info => (*chars::STEP_SUBMIT).cyan().to_string(),
+			Level::Debug | Level::Trace => (*chars::STEP_SUBMIT).dimmed().to_string(),
+		};
+
+		render::log(step, &record.args().to_string());
+	}
+
+	fn flush(&self) {}
+}
@@ -0,0 +1,98 @@
+//! Inline markup for messages and hints
+//!
+//! Supports `**bold**`, `_dim_` and `` `code` ``, a marker can be escaped with a backslash.
+//! Underscores only count at word boundaries, so `snake_case` stays as is.
+
+use owo_colors::OwoColorize;
+
+const MARKERS: [char; 3] = ['*', '_', '`'];
+
+enum Span<'a> {
+	Bold(&'a str),
+	Dim(&'a str),
+	Code(&'a str),
+}
+
+/// Render the markup in the text, dimming the unmarked parts if `dim` is set.
+pub(super) fn render(text: &str, dim: bool) -> String {
+	let mut out = String::new();
+	let mut plain = String::new();
+	let mut prev = None;
+
+	let mut rest = text;
+	while let Some(c) = rest.chars().next() {
+		if c == '\\' {
+			if let Some(next) = rest[1..].chars().next().filter(|c| MARKERS.contains(c)) {
+				plain.push(next);
+				prev = Some(next);
+				rest = &rest[1 + next.len_utf8()..];
+				continue;
+			}
+		}
+
+		if let Some((span, len)) = span(rest, prev) {
+			push_plain(&mut out, &mut plain, dim);
+			match span {
+				Span::Bold(inner) => out.push_str(&inner.bold().to_string()),
+				Span::Dim(inner) => out.push_str(&inner.dimmed().to_string()),
+				Span::Code(inner) => out.push_str(&inner.cyan().to_string()),
+			}
+
+			prev = rest[..len].chars().next_back();
+			rest = &rest[len..];
+			continue;
+		}
+
+		plain.push(c);
+		prev = Some(c);
+		rest = &rest[c.len_utf8()..];
+	}
+
+	push_plain(&mut out, &mut plain, dim);
+	out
+}
+
+fn push_plain(out: &mut String, plain: &mut String, dim: bool) {
+	if plain.is_empty() {
+		return;
+	}
+
+	if dim {
+		out.push_str(&plain.dimmed().to_string());
+	} else {
+		out.push_str(plain);
+	}
+	plain.clear();
+}
+
+/// Parse the span at the start of the text, returning it and its length including the markers.
+fn span(text: &str, prev: Option<char>) -> Option<(Span<'_>, usize)> {
+	if let Some(rest) = text.strip_prefix("**") {
+		let end = rest.find("**")?;
+		let inner = trimmed(&rest[..end])?;
+		Some((Span::Bold(inner), end + 4))
+	} else if let Some(rest) = text.strip_prefix('`') {
+		let end = rest.find('`')?;
+		let inner = Some(&rest[..end]).filter(|inner| !inner.is_empty())?;
+		Some((Span::Code(inner), end + 2))
+	} else if let Some(rest) = text.strip_prefix('_') {
+		if prev.is_some_and(char::is_alphanumeric) {
+			return None;
+		}
+
+		let end = rest.match_indices('_').map(|(idx, _)| idx).find(|&idx| {
+			let next = rest[idx + 1..].chars().next();
+			!next.is_some_and(char::is_alphanumeric)
+		})?;
+		let inner = trimmed(&rest[..end])?;
+		Some((Span::Dim(inner), end + 2))
+	} else {
+		None
+	}
+}
+
+/// The inner text of a span, if it is not empty and does not start or end with whitespace.
+fn trimmed(inner: &str) -> Option<&str> {
+	let is_trimmed = !inner.is_empty() && inner.trim() == inner;
+	is_trimmed.then_some(inner)
+}
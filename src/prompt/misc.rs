@@ -1,3 +1,10 @@
+//! Messages around the prompts
+
+use super::{frame, summary, transcript};
+use crate::style::chars;
+use owo_colors::OwoColorize;
+use std::fmt::Display;
+
 /// Intro message.
 ///
 /// Write a message to start a prompt session.
@@ -20,15 +27,14 @@
 #[macro_export]
 macro_rules! intro {
 	() => {
-		println!("{}", *$crate::style::chars::BAR_START);
+		$crate::__message("intro", None)
 	};
 	($arg:expr) => {
-		$crate::intro!("{}", $arg);
+		$crate::intro!("{}", $arg)
+	};
+	($($arg:tt)*) => {
+		$crate::__message("intro", Some(&format!($($arg)*)))
 	};
-	($($arg:tt)*) => {{
-		print!("{}  ", *$crate::style::chars::BAR_START);
-		println!($($arg)*);
-	}}
 }
 
 /// Setup outro
@@ -52,20 +58,15 @@ macro_rules! intro {
 /// ```
 #[macro_export]
 macro_rules! outro {
-	() => {{
-		println!("{}", *$crate::style::chars::BAR);
-		println!("{}", *$crate::style::chars::BAR_END);
-		println!();
-	}};
+	() => {
+		$crate::__message("outro", None)
+	};
 	($arg:expr) => {
-		$crate::outro!("{}", $arg);
+		$crate::outro!("{}", $arg)
+	};
+	($($arg:tt)*) => {
+		$crate::__message("outro", Some(&format!($($arg)*)))
 	};
-	($($arg:tt)*) => {{
-		println!("{}", *$crate::style::chars::BAR);
-		print!("{}  ", *$crate::style::chars::BAR_END);
-		println!($($arg)*);
-		println!();
-	}};
 }
 
 /// Cancel message.
@@ -89,6 +90,172 @@ macro_rules! cancel {
 	}};
 }
 
+/// Cancel outro.
+///
+/// Write a message to end a cancelled prompt session, see [`outro_cancel()`](crate::outro_cancel()).
+///
+/// Can take either a [fmt](std::fmt) string like [`format!`], a type that implements [`std::fmt::Display`], or nothing.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::outro_cancel;
+///
+/// // empty
+/// outro_cancel!();
+/// // fmt string
+/// outro_cancel!("fmt {:?}", "string");
+/// // impl Display
+/// outro_cancel!("operation cancelled");
+/// ```
+#[macro_export]
+macro_rules! outro_cancel {
+	() => {
+		$crate::__message("outro_cancel", None)
+	};
+	($arg:expr) => {
+		$crate::outro_cancel!("{}", $arg)
+	};
+	($($arg:tt)*) => {
+		$crate::__message("outro_cancel", Some(&format!($($arg)*)))
+	};
+}
+
+/// Error outro.
+///
+/// Write a message to end a failed prompt session, see [`outro_error()`](crate::outro_error()).
+///
+/// Can take either a [fmt](std::fmt) string like [`format!`], a type that implements [`std::fmt::Display`], or nothing.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::outro_error;
+///
+/// // empty
+/// outro_error!();
+/// // fmt string
+/// outro_error!("fmt {:?}", "string");
+/// // impl Display
+/// outro_error!("installation failed");
+/// ```
+#[macro_export]
+macro_rules! outro_error {
+	() => {
+		$crate::__message("outro_error", None)
+	};
+	($arg:expr) => {
+		$crate::outro_error!("{}", $arg)
+	};
+	($($arg:tt)*) => {
+		$crate::__message("outro_error", Some(&format!($($arg)*)))
+	};
+}
+
+/// Write a message to end a cancelled prompt session.
+///
+/// The end of the session is drawn in red, with the same glyph as a cancelled prompt.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::outro_cancel;
+///
+/// outro_cancel("operation cancelled");
+/// ```
+pub fn outro_cancel<M: Display>(message: M) {
+	let message = message.to_string();
+	__message(
+		"outro_cancel",
+		Some(message.as_str()).filter(|message| !message.is_empty()),
+	);
+}
+
+/// Write a message to end a failed prompt session.
+///
+/// The end of the session is drawn in yellow, with the same glyph as a failed prompt.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::outro_error;
+///
+/// outro_error("installation failed");
+/// ```
+pub fn outro_error<M: Display>(message: M) {
+	let message = message.to_string();
+	__message(
+		"outro_error",
+		Some(message.as_str()).filter(|message| !message.is_empty()),
+	);
+}
+
+/// Write a message of the `kind`, used by the message macros.
+#[doc(hidden)]
+pub fn __message(kind: &str, message: Option<&str>) {
+	transcript::message(kind, message.unwrap_or_default());
+
+	let (step, message) = match kind {
+		"intro" if frame::is_quiet() => {
+			frame::line(message.unwrap_or_default());
+			return;
+		}
+		"intro" => {
+			frame::line(fmt_step(*chars::BAR_START, message));
+			return;
+		}
+		"outro" if frame::is_quiet() => (String::new(), message.map(str::to_owned)),
+		"outro" => (chars::BAR_END.to_string(), message.map(str::to_owned)),
+		"info" => (
+			(*chars::STEP_SUBMIT).cyan().to_string(),
+			message.map(str::to_owned),
+		),
+		"warn" => (
+			(*chars::STEP_ERROR).yellow().to_string(),
+			message.map(str::to_owned),
+		),
+		"error" => (
+			(*chars::STEP_CANCEL).red().to_string(),
+			message.map(str::to_owned),
+		),
+		"outro_cancel" => (
+			(*chars::STEP_CANCEL).red().to_string(),
+			message.map(|message| message.red().to_string()),
+		),
+		"outro_error" => (
+			(*chars::STEP_ERROR).yellow().to_string(),
+			message.map(|message| message.yellow().to_string()),
+		),
+		_ => unreachable!("unknown message kind {:?}", kind),
+	};
+
+	frame::bar();
+	match message.as_deref() {
+		Some(message) if step.is_empty() => frame::line(message),
+		None if step.is_empty() => {}
+		message => frame::line(fmt_step(step, message)),
+	}
+
+	if kind.starts_with("outro") && !frame::is_quiet() {
+		frame::line("");
+	}
+
+	match kind {
+		"outro" => summary::emit("done"),
+		"outro_cancel" => summary::emit("cancelled"),
+		"outro_error" => summary::emit("error"),
+		_ => {}
+	}
+}
+
+/// The `step` followed by the message.
+fn fmt_step<S: Display>(step: S, message: Option<&str>) -> String {
+	match message {
+		Some(message) => format!("{}  {}", step, message),
+		None => step.to_string(),
+	}
+}
+
 /// Info message.
 ///
 /// Write an info message while in a prompt session.
@@ -120,22 +287,15 @@ macro_rules! cancel {
 /// ```
 #[macro_export]
 macro_rules! info {
-	() => {{
-		use owo_colors::OwoColorize;
-		println!("{}", *$crate::style::chars::BAR);
-		println!("{}", (*$crate::style::chars::STEP_SUBMIT).cyan());
-	}};
+	() => {
+		$crate::__message("info", None)
+	};
 	($arg:expr) => {
-		$crate::info!("{}", $arg);
+		$crate::info!("{}", $arg)
+	};
+	($($arg:tt)*) => {
+		$crate::__message("info", Some(&format!($($arg)*)))
 	};
-	($($arg:tt)*) => {{
-		{
-			use owo_colors::OwoColorize;
-			println!("{}", *$crate::style::chars::BAR);
-			print!("{}  ", (*$crate::style::chars::STEP_SUBMIT).cyan());
-		}
-		println!($($arg)*);
-	}}
 }
 
 /// Warn message.
@@ -169,22 +329,15 @@ macro_rules! info {
 /// ```
 #[macro_export]
 macro_rules! warn {
-	() => {{
-		use owo_colors::OwoColorize;
-		println!("{}", *$crate::style::chars::BAR);
-		println!("{}", (*$crate::style::chars::STEP_ERROR).yellow());
-	}};
+	() => {
+		$crate::__message("warn", None)
+	};
 	($arg:expr) => {
-		$crate::warn!("{}", $arg);
+		$crate::warn!("{}", $arg)
+	};
+	($($arg:tt)*) => {
+		$crate::__message("warn", Some(&format!($($arg)*)))
 	};
-	($($arg:tt)*) => {{
-		{
-			use owo_colors::OwoColorize;
-			println!("{}", *$crate::style::chars::BAR);
-			print!("{}  ", (*$crate::style::chars::STEP_ERROR).yellow());
-		}
-		println!($($arg)*);
-	}};
 }
 
 /// Error message.
@@ -218,20 +371,13 @@ macro_rules! warn {
 /// ```
 #[macro_export]
 macro_rules! err {
-	() => {{
-		use owo_colors::OwoColorize;
-		println!("{}", *$crate::style::chars::BAR);
-		println!("{}", (*$crate::style::chars::STEP_CANCEL).red());
-	}};
+	() => {
+		$crate::__message("error", None)
+	};
 	($arg:expr) => {
-		$crate::err!("{}", $arg);
+		$crate::err!("{}", $arg)
+	};
+	($($arg:tt)*) => {
+		$crate::__message("error", Some(&format!($($arg)*)))
 	};
-	($($arg:tt)*) => {{
-		{
-			use owo_colors::OwoColorize;
-			println!("{}", *$crate::style::chars::BAR);
-			print!("{}  ", (*$crate::style::chars::STEP_CANCEL).red());
-		}
-		println!($($arg)*);
-	}};
 }
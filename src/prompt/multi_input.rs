@@ -1,23 +1,118 @@
 //! Multiple text inputs
 
-use super::input::{PlaceholderHighlighter, ValidateFn};
+use super::{
+	cache::Cached,
+	editor::{self, PlaceholderHighlighter},
+	frame,
+	input::{CancelReturnFn, FormatFn, SubmitFn, ValidateFn},
+	render::{self, RenderFn, Renderer},
+	session::Context,
+	transcript,
+};
 use crate::{
 	error::ClackError,
-	style::{ansi, chars},
+	style::chars,
+	validate::{Attempt, Validator},
 };
-use crossterm::{cursor, QueueableCommand};
 use owo_colors::OwoColorize;
-use rustyline::Editor;
+use rustyline::{
+	error::ReadlineError, Cmd, ConditionalEventHandler, Event, EventContext, EventHandler, KeyCode,
+	KeyEvent, Modifiers, RepeatCount,
+};
 use std::{
 	borrow::Cow,
 	error::Error,
 	fmt::Display,
-	io::{stdout, Write},
 	str::FromStr,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex, PoisonError,
+	},
 };
 
+/// Line submitted to a [`MultiInput`].
+enum Line<T> {
+	Value(T),
+	/// The finish key was pressed.
+	Done,
+	/// Up was pressed to edit the previous line.
+	Back,
+	/// The prompt was cancelled, with the text typed so far.
+	Cancel(String),
+}
+
+/// Submits the line to edit the previous one, on the empty line or `always`.
+struct EditPrevious {
+	back: Arc<AtomicBool>,
+	always: bool,
+}
+
+impl ConditionalEventHandler for EditPrevious {
+	fn handle(&self, _: &Event, _: RepeatCount, _: bool, ctx: &EventContext) -> Option<Cmd> {
+		if self.always || ctx.line().is_empty() {
+			self.back.store(true, Ordering::Relaxed);
+			Some(Cmd::AcceptLine)
+		} else {
+			None
+		}
+	}
+}
+
+/// Value returned by [`MultiInput::try_parse()`],
+/// either the parsed value or the index and the text of a value that couldn't be parsed.
+pub type Parsed<T> = Result<T, (usize, String)>;
+
+/// How the list of a [`MultiInput`] is finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Finish {
+	/// Submitting an empty line.
+	#[default]
+	EmptyLine,
+	/// Pressing ctrl+d on an empty line, so empty lines can be added to the list.
+	CtrlD,
+	/// Submitting a line with only a `.`, so empty lines can be added to the list.
+	Dot,
+}
+
+impl Finish {
+	/// Whether the submitted `line` finishes the list, [`None`] if ctrl+d was pressed.
+	fn is_done(self, line: Option<&str>) -> bool {
+		match (self, line) {
+			(_, None) => true,
+			(Finish::EmptyLine, Some(line)) => line.is_empty(),
+			(Finish::Dot, Some(line)) => line == ".",
+			(Finish::CtrlD, Some(_)) => false,
+		}
+	}
+
+	fn key(self) -> &'static str {
+		match self {
+			Finish::EmptyLine => "enter on empty line",
+			Finish::CtrlD => "ctrl+d",
+			Finish::Dot => "enter on .",
+		}
+	}
+
+	fn hint(self) -> &'static str {
+		match self {
+			Finish::EmptyLine => "submit an empty line to finish",
+			Finish::CtrlD => "press ctrl+d to finish",
+			Finish::Dot => "submit . to finish",
+		}
+	}
+}
+
+/// Whether two values are the same, for [`MultiInput::unique()`].
+type EqFn = dyn Fn(&str, &str) -> bool + Send + Sync;
+type ValidateAllFn = dyn Fn(&[String]) -> Result<(), Cow<'static, str>> + Send + Sync;
+type CancelFn = dyn Fn(&[String], &str) + Send + Sync;
+
 /// `MultiInput` struct
 ///
+/// Pressing up on the empty line edits the previous value again, and ctrl+up goes further back.
+/// Finishing while a value is edited, by default by submitting an empty line, leaves the value unchanged
+/// and goes back to adding values, so the list is only finished from the new line.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -46,12 +141,27 @@ use std::{
 /// ```
 pub struct MultiInput<M: Display> {
 	message: M,
+	extra: Option<String>,
+	help: Option<String>,
 	initial_value: Option<String>,
+	initial_values: Vec<String>,
 	placeholder: Option<String>,
-	validate: Option<Box<ValidateFn>>,
-	cancel: Option<Box<dyn Fn()>>,
+	validate: Option<Mutex<Box<ValidateFn>>>,
+	max_attempts: Option<usize>,
+	cancel: Option<Arc<CancelFn>>,
+	cancel_return: Option<Arc<CancelReturnFn<Vec<String>>>>,
+	format: Option<Arc<FormatFn<[String]>>>,
+	on_render: Option<Arc<RenderFn>>,
+	context: Context,
+	on_submit: Option<Arc<SubmitFn<[String]>>>,
+	unique: Option<Arc<EqFn>>,
+	validate_all: Option<Arc<ValidateAllFn>>,
+	finish: Finish,
 	min: u16,
 	max: u16,
+	min_message: Option<String>,
+	max_message: Option<String>,
+	submit_at_max: bool,
 }
 
 impl<M: Display> MultiInput<M> {
@@ -71,12 +181,27 @@ impl<M: Display> MultiInput<M> {
 	pub fn new(message: M) -> Self {
 		MultiInput {
 			message,
+			extra: None,
+			help: None,
 			validate: None,
+			max_attempts: None,
 			initial_value: None,
+			initial_values: vec![],
 			placeholder: None,
 			cancel: None,
+			cancel_return: None,
+			format: None,
+			on_render: None,
+			context: Context::default(),
+			on_submit: None,
+			unique: None,
+			validate_all: None,
+			finish: Finish::EmptyLine,
 			min: 1,
 			max: u16::MAX,
+			min_message: None,
+			max_message: None,
+			submit_at_max: true,
 		}
 	}
 
@@ -100,8 +225,37 @@ impl<M: Display> MultiInput<M> {
 		self
 	}
 
+	/// Start with values already in the list, which can be edited like submitted values.
+	///
+	/// With [`MultiInput::parse()`], a value that can't be parsed fails the prompt
+	/// with [`ClackError::InvalidInitialValue`] before anything can be typed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let tags = multi_input("tags")
+	///     .initial_values(["rust", "cli"])
+	///     .interact()?;
+	/// println!("tags {:?}", tags);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn initial_values<I, S>(&mut self, values: I) -> &mut Self
+	where
+		I: IntoIterator<Item = S>,
+		S: ToString,
+	{
+		self.initial_values = values.into_iter().map(|value| value.to_string()).collect();
+		self
+	}
+
 	/// Specify a placeholder.
 	///
+	/// It can be accepted into the empty line with tab or the right arrow key.
+	///
 	/// # Examples
 	///
 	/// ```no_run
@@ -137,7 +291,7 @@ impl<M: Display> MultiInput<M> {
 	}
 
 	/// Specify the maximum amount of answers.
-	/// Will automatically submit when that amount is reached.
+	/// Will automatically submit when that amount is reached, unless disabled with [`MultiInput::submit_at_max()`].
 	///
 	/// # Examples
 	///
@@ -155,10 +309,94 @@ impl<M: Display> MultiInput<M> {
 		self
 	}
 
+	/// Change the error shown when the list is finished with less than the [minimum](MultiInput::min()) of answers.
+	///
+	/// Default: `minimum {min}`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answers = multi_input("tags")
+	///     .min(2)
+	///     .min_message("add at least two tags")
+	///     .interact()?;
+	/// println!("answers {:?}", answers);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn min_message<S: ToString>(&mut self, message: S) -> &mut Self {
+		self.min_message = Some(message.to_string());
+		self
+	}
+
+	/// Change the error shown when more than the [maximum](MultiInput::max()) of answers is added.
+	///
+	/// Default: `maximum {max}`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answers = multi_input("tags")
+	///     .max(3)
+	///     .submit_at_max(false)
+	///     .max_message("three tags at most")
+	///     .interact()?;
+	/// println!("answers {:?}", answers);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn max_message<S: ToString>(&mut self, message: S) -> &mut Self {
+		self.max_message = Some(message.to_string());
+		self
+	}
+
+	/// Whether to submit automatically when the [maximum](MultiInput::max()) of answers is reached.
+	///
+	/// If disabled, the list has to be finished like usual, and adding another answer shows an error.
+	///
+	/// Default: `true`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answers = multi_input("tags").max(3).submit_at_max(false).interact()?;
+	/// println!("answers {:?}", answers);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn submit_at_max(&mut self, submit: bool) -> &mut Self {
+		self.submit_at_max = submit;
+		self
+	}
+
+	fn fmt_min(&self) -> Cow<'static, str> {
+		match &self.min_message {
+			Some(message) => Cow::Owned(message.clone()),
+			None => Cow::Owned(format!("minimum {}", self.min)),
+		}
+	}
+
+	fn fmt_max(&self) -> Cow<'static, str> {
+		match &self.max_message {
+			Some(message) => Cow::Owned(message.clone()),
+			None => Cow::Owned(format!("maximum {}", self.max)),
+		}
+	}
+
 	/// Specify a validation function.
 	///
-	/// On a successful validation, return a `None` from the closure,
-	/// and on an unsuccessful validation return a `Some<&'static str>` with the error message.
+	/// On a successful validation, return `Ok(())` from the closure,
+	/// and on an unsuccessful validation return an `Err` with the error message.
+	/// The same function can be passed to [`Input::validate()`](crate::input::Input::validate()).
 	///
 	/// # Examples
 	///
@@ -180,23 +418,367 @@ impl<M: Display> MultiInput<M> {
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn validate<F>(&mut self, validate: F) -> &mut Self
+	pub fn validate<F>(&mut self, mut validate: F) -> &mut Self
 	where
-		F: Fn(&str) -> Result<(), Cow<'static, str>> + 'static,
+		F: FnMut(&str) -> Result<(), Cow<'static, str>> + Send + 'static,
+	{
+		let validate = Box::new(move |value: &str, _: &Attempt| validate(value));
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Like [`MultiInput::validate()`], but the function can keep state between the submissions,
+	/// and gets the [`Attempt`] that is validated.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_input("tags")
+	///     .validate_with(|value, attempt| {
+	///         if value.len() >= 8 {
+	///             Ok(())
+	///         } else if attempt.number >= 3 {
+	///             Err(Cow::Owned(format!("attempt {}: use at least 8 characters", attempt.number)))
+	///         } else {
+	///             Err(Cow::Borrowed("too short"))
+	///         }
+	///     })
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validate_with<F>(&mut self, validate: F) -> &mut Self
+	where
+		F: FnMut(&str, &Attempt) -> Result<(), Cow<'static, str>> + Send + 'static,
 	{
 		let validate = Box::new(validate);
-		self.validate = Some(validate);
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Specify a [`Validator`], like [`MultiInput::validate()`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{
+	///     multi_input,
+	///     validate::{self, Validator},
+	/// };
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_input("tags")
+	///     .validator(validate::max_len(20))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validator<V: Validator + Send + 'static>(&mut self, validator: V) -> &mut Self {
+		let validate = Box::new(move |value: &str, _: &Attempt| validator.validate(value));
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Abort the prompt with [`ClackError::TooManyAttempts`] after `max` rejected submissions of one value,
+	/// instead of asking again.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{error::ClackError, multi_input};
+	///
+	/// let answer = multi_input("ports").max_attempts(3).interact();
+	/// if let Err(ClackError::TooManyAttempts { last_error }) = answer {
+	///     eprintln!("giving up: {}", last_error);
+	/// }
+	/// ```
+	pub fn max_attempts(&mut self, max: usize) -> &mut Self {
+		self.max_attempts = Some(max);
+		self
+	}
+
+	/// Change how the list is finished, which is shown in a dimmed hint unless a [help](MultiInput::help()) is set.
+	///
+	/// Default: [`Finish::EmptyLine`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{multi_input, multi_input::Finish};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let lines = multi_input("lines").finish_on(Finish::Dot).interact()?;
+	/// println!("lines {:?}", lines);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn finish_on(&mut self, finish: Finish) -> &mut Self {
+		self.finish = finish;
 		self
 	}
 
-	fn do_validate(&self, input: &str) -> Result<(), Cow<'static, str>> {
-		if let Some(validate) = self.validate.as_deref() {
-			validate(input)
+	/// Specify a validation function for all values, called when the user tries to finish.
+	///
+	/// Like with [`MultiInput::validate()`], the values can't be submitted while it returns an `Err`,
+	/// but they can still be edited.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let shares = multi_input("shares")
+	///     .validate_all(|shares| {
+	///         let total = shares.iter().filter_map(|share| share.parse::<u32>().ok()).sum::<u32>();
+	///         if total == 100 {
+	///             Ok(())
+	///         } else {
+	///             Err(Cow::Owned(format!("total must be 100, not {}", total)))
+	///         }
+	///     })
+	///     .parse::<u32>()?;
+	/// println!("shares {:?}", shares);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validate_all<F>(&mut self, validate: F) -> &mut Self
+	where
+		F: Fn(&[String]) -> Result<(), Cow<'static, str>> + Send + Sync + 'static,
+	{
+		let validate = Arc::new(validate);
+		self.validate_all = Some(validate);
+		self
+	}
+
+	fn do_validate_all<T: Display>(&self, values: &[T]) -> Result<(), Cow<'static, str>> {
+		let Some(validate) = self.validate_all.as_deref() else {
+			return Ok(());
+		};
+
+		let values = values.iter().map(ToString::to_string).collect::<Vec<_>>();
+		validate(&values)
+	}
+
+	/// Reject values that are already in the list.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let tags = multi_input("tags").unique().interact()?;
+	/// println!("tags {:?}", tags);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn unique(&mut self) -> &mut Self {
+		self.unique = Some(Arc::new(|a, b| a == b));
+		self
+	}
+
+	/// Like [`MultiInput::unique()`], but compares the keys extracted from the values.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let emails = multi_input("emails")
+	///     .unique_by(str::to_lowercase)
+	///     .interact()?;
+	/// println!("emails {:?}", emails);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn unique_by<F, K>(&mut self, key: F) -> &mut Self
+	where
+		F: Fn(&str) -> K + Send + Sync + 'static,
+		K: PartialEq,
+	{
+		self.unique = Some(Arc::new(move |a, b| key(a) == key(b)));
+		self
+	}
+
+	/// Check that the `value` isn't in the list yet, except for the value that is `editing`.
+	fn check_unique<T: Display>(
+		&self,
+		value: &T,
+		values: &[T],
+		editing: Option<usize>,
+	) -> Result<(), Cow<'static, str>> {
+		let Some(eq) = self.unique.as_deref() else {
+			return Ok(());
+		};
+
+		let value = value.to_string();
+		let is_duplicate = values
+			.iter()
+			.enumerate()
+			.filter(|&(i, _)| Some(i) != editing)
+			.any(|(_, other)| eq(&value, &other.to_string()));
+
+		if is_duplicate {
+			Err(Cow::Borrowed("already in the list"))
+		} else {
+			Ok(())
+		}
+	}
+
+	fn do_validate(&self, input: &str, attempt: &Attempt) -> Result<(), Cow<'static, str>> {
+		if let Some(validate) = &self.validate {
+			let mut validate = validate.lock().unwrap_or_else(PoisonError::into_inner);
+			validate(input, attempt)
 		} else {
 			Ok(())
 		}
 	}
 
+	/// Add secondary lines below the message, drawn dimmed while the prompt is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_input("tags")
+	///     .message_extra("leave empty to finish")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn message_extra<S: ToString>(&mut self, extra: S) -> &mut Self {
+		self.extra = Some(extra.to_string());
+		self
+	}
+
+	/// Show a dimmed help line at the end of the prompt while it is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_input("tags")
+	///     .help("submit an empty line to finish")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn help<S: ToString>(&mut self, help: S) -> &mut Self {
+		self.help = Some(help.to_string());
+		self
+	}
+
+	/// Change what is printed after submitting, instead of one line per submitted value.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answers = multi_input("message")
+	///     .format_submitted(|values| values.join(", "))
+	///     .interact()?;
+	/// println!("answers {:?}", answers);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn format_submitted<F>(&mut self, format: F) -> &mut Self
+	where
+		F: Fn(&[String]) -> String + Send + Sync + 'static,
+	{
+		self.format = Some(Arc::new(format));
+		self
+	}
+
+	/// Call `on_render` with the lines of every frame that is drawn, e.g. to record the prompt in tests.
+	///
+	/// The line that is typed is drawn by the line editor, so it isn't part of the frames.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	/// use std::sync::{Arc, Mutex};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let frames = Arc::new(Mutex::new(vec![]));
+	/// let recorded = Arc::clone(&frames);
+	/// let answer = multi_input("tags")
+	///     .on_render(move |lines| recorded.lock().unwrap().push(lines.to_vec()))
+	///     .interact()?;
+	/// println!("{} frames", frames.lock().unwrap().len());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_render<F>(&mut self, on_render: F) -> &mut Self
+	where
+		F: Fn(&[String]) + Send + Sync + 'static,
+	{
+		self.on_render = Some(Arc::new(on_render));
+		self
+	}
+
+	/// Draw the prompt as part of a [`Session`](crate::session::Session),
+	/// with the [`Context`] the session passes to [`Session::prompt()`](crate::session::Session::prompt()).
+	///
+	/// The prompt is pre-filled with its [cached](crate::session::Session::cache()) answer,
+	/// which takes precedence over the [initial values](MultiInput::initial_values()).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{multi_input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.steps(1);
+	///
+	/// let answer = session.prompt("tags", |cx| multi_input("tags").context(cx).interact())?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn context(&mut self, cx: &Context) -> &mut Self {
+		self.context = cx.clone();
+		self
+	}
+
+	/// Call `on_submit` with the answer when the prompt is submitted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_input("tags")
+	///     .on_submit(|tags| println!("{} tags", tags.len()))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_submit<F>(&mut self, on_submit: F) -> &mut Self
+	where
+		F: Fn(&[String]) + Send + Sync + 'static,
+	{
+		self.on_submit = Some(Arc::new(on_submit));
+		self
+	}
+
 	/// Specify function to call on cancel.
 	///
 	/// # Examples
@@ -216,81 +798,267 @@ impl<M: Display> MultiInput<M> {
 	/// }
 	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
 	where
-		F: Fn() + 'static,
+		F: Fn() + Send + Sync + 'static,
 	{
-		let cancel = Box::new(cancel);
+		let cancel = Arc::new(move |_: &[String], _: &str| cancel());
 		self.cancel = Some(cancel);
 		self
 	}
 
-	fn interact_once<T: FromStr>(
+	/// Like [`MultiInput::cancel()`], but the function gets the values that were added
+	/// and the text that was typed in the current line.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answers = multi_input("hosts")
+	///     .cancel_with(|values, typed| eprintln!("cancelled with {:?} and {:?}", values, typed))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cancel_with<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn(&[String], &str) + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(cancel);
+		self.cancel = Some(cancel);
+		self
+	}
+
+	/// Return the values of the `cancel_return` function instead of an `Err` when the prompt is cancelled.
+	///
+	/// The values aren't validated, and are parsed by [`MultiInput::parse()`],
+	/// which is still cancelled if one of them can't be parsed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// // ctrl+c keeps the default hosts
+	/// let hosts = multi_input("hosts")
+	///     .on_cancel_return(|| vec!["localhost".into()])
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_cancel_return<F>(&mut self, cancel_return: F) -> &mut Self
+	where
+		F: Fn() -> Vec<String> + Send + Sync + 'static,
+	{
+		self.cancel_return = Some(Arc::new(cancel_return));
+		self
+	}
+
+	fn interact_once<T: FromStr + Display>(
 		&self,
+		r: &mut Renderer,
 		enforce_non_empty: bool,
-		amt: u16,
-	) -> Result<Option<T>, ClackError>
+		values: &[T],
+		editing: Option<usize>,
+		is_val: bool,
+	) -> Result<Line<T>, ClackError>
 	where
 		T::Err: Error,
 	{
 		let prompt = format!("{}  ", *chars::BAR);
-		let mut editor = Editor::new()?;
+		let mut editor = editor::editor()?;
 
-		let highlighter = PlaceholderHighlighter::new(self.placeholder.as_deref());
+		let mut highlighter = PlaceholderHighlighter::new(self.placeholder.as_deref());
+		highlighter.is_val = is_val;
 		editor.set_helper(Some(highlighter));
+		editor::bind_placeholder(&mut editor, self.placeholder.as_deref());
+		let cancelled = editor::bind_cancel(&mut editor, self.context.token());
+
+		let back = Arc::new(AtomicBool::new(false));
+		for modifiers in [Modifiers::NONE, Modifiers::CTRL] {
+			let handler = EditPrevious {
+				back: Arc::clone(&back),
+				always: modifiers == Modifiers::CTRL,
+			};
+			let handler = EventHandler::Conditional(Box::new(handler));
+			editor.bind_sequence(KeyEvent(KeyCode::Up, modifiers), handler);
+		}
+
+		let mut initial_value = match editing {
+			Some(i) => Some(Cow::Owned(values[i].to_string())),
+			None => self.initial_value.as_deref().map(Cow::Borrowed),
+		};
 
-		let mut initial_value = self.initial_value.as_deref().map(Cow::Borrowed);
+		let mut attempt = Attempt::default();
 		loop {
-			let line = if let Some(ref init) = initial_value {
-				editor.readline_with_initial(&prompt, (init, ""))
-			} else {
-				editor.readline(&prompt)
+			if self.context.is_cancelled() {
+				let line = initial_value.as_deref().unwrap_or_default();
+				break Ok(Line::Cancel(line.to_owned()));
+			}
+
+			let initial = initial_value.as_deref();
+			let line = editor::readline(&mut editor, &prompt, initial, self.context.token());
+			r.moved_down();
+
+			let value = match line {
+				Ok(value) => Some(value),
+				Err(ReadlineError::Eof) if self.finish == Finish::CtrlD => None,
+				Err(_) => break Ok(Line::Cancel(editor::cancelled_line(&cancelled))),
 			};
+			attempt.next();
 
-			// todo this looks refactor-able
-			if let Ok(value) = line {
-				if value.is_empty() {
-					if enforce_non_empty {
-						initial_value = None;
+			if back.swap(false, Ordering::Relaxed) {
+				break Ok(Line::Back);
+			}
 
-						if let Some(helper) = editor.helper_mut() {
-							helper.is_val = true;
-						}
+			let is_done = self.finish.is_done(value.as_deref());
+			let value = value.unwrap_or_default();
+			let text = if is_done {
+				if !enforce_non_empty {
+					break Ok(Line::Done);
+				}
 
-						let text = format!("minimum {}", self.min);
-						self.w_val(&text, amt);
-					} else {
-						break Ok(None);
+				initial_value = None;
+				self.fmt_min()
+			} else if let Err(text) = self.do_validate(&value, &attempt) {
+				initial_value = Some(Cow::Owned(value));
+				text
+			} else {
+				let parsed = value
+					.parse::<T>()
+					.map_err(|err| Cow::Owned(err.to_string()));
+				match parsed.and_then(|val| self.check_unique(&val, values, editing).map(|()| val))
+				{
+					Ok(val) => break Ok(Line::Value(val)),
+					Err(text) => {
+						initial_value = Some(Cow::Owned(value));
+						text
 					}
-				} else if let Err(text) = self.do_validate(&value) {
-					initial_value = Some(Cow::Owned(value));
+				}
+			};
 
-					if let Some(helper) = editor.helper_mut() {
-						helper.is_val = true;
-					}
+			if let Some(helper) = editor.helper_mut() {
+				helper.is_val = true;
+			}
 
-					self.w_val(&text, amt);
-				} else {
-					match value.parse::<T>() {
-						Ok(value) => break Ok(Some(value)),
-						Err(err) => {
-							initial_value = Some(Cow::Owned(value));
+			attempt.reject(&text);
+			if attempt.is_last(self.max_attempts) {
+				self.w_abort(r, values, &text);
+				let last_error = text.into_owned();
+				break Err(ClackError::TooManyAttempts { last_error });
+			}
 
-							if let Some(helper) = editor.helper_mut() {
-								helper.is_val = true;
-							}
+			self.w_frame(r, values, editing, Some(&text));
+		}
+	}
 
-							self.w_val(&err.to_string(), amt);
+	/// Read lines until the list is finished or the maximum is reached.
+	///
+	/// The previous lines can be edited again, finishing one leaves it unchanged.
+	fn interact_lines<T: FromStr + Display>(&self) -> Result<Vec<T>, ClackError>
+	where
+		T::Err: Error,
+	{
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		r.keys(&[
+			("enter", "add"),
+			(self.finish.key(), "submit"),
+			("up", "edit previous"),
+		]);
+
+		let cached = self.context.cached().and_then(Cached::as_list);
+		let initial_values = cached.unwrap_or(&self.initial_values);
+		let mut values = Vec::with_capacity(initial_values.len());
+		for (index, value) in initial_values.iter().enumerate() {
+			match value.parse::<T>() {
+				Ok(value) => values.push(value),
+				Err(err) => {
+					let error = err.to_string();
+					self.w_abort(&mut r, initial_values, &format!("{:?}: {}", value, error));
+					return Err(ClackError::InvalidInitialValue { index, error });
+				}
+			}
+		}
+		let mut editing = None;
+		let mut error: Option<Cow<'static, str>> = None;
+		self.w_frame(&mut r, &values, editing, None);
+		loop {
+			let enforce_non_empty = editing.is_none() && (values.len() as u16) < self.min;
+			let once = self.interact_once::<T>(
+				&mut r,
+				enforce_non_empty,
+				&values,
+				editing,
+				error.take().is_some(),
+			);
+
+			match once {
+				Ok(Line::Value(value)) => match editing.take() {
+					Some(i) => values[i] = value,
+					None if values.len() as u16 >= self.max => {
+						error = Some(self.fmt_max());
+					}
+					None => {
+						values.push(value);
+
+						if self.submit_at_max && values.len() as u16 == self.max {
+							match self.do_validate_all(&values) {
+								Ok(()) => {
+									self.w_out(&mut r, &values);
+									break;
+								}
+								Err(text) => error = Some(text),
+							}
 						}
 					}
-					// break Ok(Some(value));
+				},
+				Ok(Line::Done) => match editing.take() {
+					Some(_) => {}
+					None => match self.do_validate_all(&values) {
+						Ok(()) => {
+							self.w_out(&mut r, &values);
+							break;
+						}
+						Err(text) => error = Some(text),
+					},
+				},
+				Ok(Line::Back) => {
+					editing = match editing {
+						Some(i) => Some(i.saturating_sub(1)),
+						None => values.len().checked_sub(1),
+					};
 				}
-			} else {
-				break Err(ClackError::Cancelled);
+				Ok(Line::Cancel(typed)) => {
+					self.w_cancel(&mut r, &values);
+					if let Some(cancel) = self.cancel.as_deref() {
+						let values = values.iter().map(T::to_string).collect::<Vec<_>>();
+						cancel(&values, &typed);
+					}
+
+					let cancel_return = self.cancel_return.as_deref().and_then(|cancel_return| {
+						let values = cancel_return()
+							.into_iter()
+							.map(|value| value.parse::<T>().ok());
+						values.collect::<Option<Vec<_>>>()
+					});
+					return cancel_return.ok_or(ClackError::Cancelled);
+				}
+				Err(err) => return Err(err),
 			}
+
+			self.w_frame(&mut r, &values, editing, error.as_deref());
 		}
+
+		Ok(values)
 	}
 
 	/// Like [`MultiInput::interact()`], but parses the value before returning.
 	///
+	/// A value that can't be parsed shows the error, and can be fixed without retyping it or the values before.
+	///
 	/// # Examples
 	///
 	/// ```no_run
@@ -307,48 +1075,42 @@ impl<M: Display> MultiInput<M> {
 	where
 		T::Err: Error,
 	{
-		self.w_init();
-
-		let mut v = vec![];
-		loop {
-			let amt = v.len() as u16;
-
-			let enforce_non_empty = amt < self.min;
-			let once = self.interact_once::<T>(enforce_non_empty, amt);
-
-			match once {
-				Ok(Some(value)) => {
-					self.w_line(&value, amt);
-					v.push(value);
-
-					if v.len() as u16 == self.max {
-						println!();
-						self.w_out(&v);
-						break;
-					}
-				}
-				Ok(None) => {
-					self.w_out(&v);
-					break;
-				}
-				Err(ClackError::Cancelled) => {
-					self.w_cancel(v.len());
-					if let Some(cancel) = self.cancel.as_deref() {
-						cancel();
-					}
-
-					return Err(ClackError::Cancelled);
-				}
-				Err(err) => return Err(err),
-			}
-		}
-
-		Ok(v)
+		self.interact_lines()
 	}
 
-	/// Waits for the user to submit a line of text.
+	/// Like [`MultiInput::interact()`], but tries to parse the values after the list is submitted.
 	///
-	/// Returns [`None`] on an empty line and [`Some::<String>`] otherwise.
+	/// The values are submitted without being parsed,
+	/// and the values that can't be parsed are returned as an `Err` with their index and the submitted text.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let ports = multi_input("ports").try_parse::<u16>()?;
+	/// for port in ports {
+	///     match port {
+	///         Ok(port) => println!("port {}", port),
+	///         Err((i, text)) => println!("skipping entry {}, {:?} is not a port", i, text),
+	///     }
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn try_parse<T: FromStr>(&self) -> Result<Vec<Parsed<T>>, ClackError> {
+		let values = self.interact()?;
+		let values = values
+			.into_iter()
+			.enumerate()
+			.map(|(i, value)| value.parse::<T>().map_err(|_| (i, value)))
+			.collect();
+
+		Ok(values)
+	}
+
+	/// Waits for the user to submit lines of text, until the list is [finished](MultiInput::finish_on()).
 	///
 	/// # Examples
 	///
@@ -377,146 +1139,163 @@ impl<M: Display> MultiInput<M> {
 	/// }
 	/// ```
 	pub fn interact(&self) -> Result<Vec<String>, ClackError> {
-		self.w_init();
-
-		let mut v = vec![];
-		loop {
-			let amt = v.len() as u16;
-
-			let enforce_non_empty = amt < self.min;
-			let once = self.interact_once::<String>(enforce_non_empty, amt);
-
-			match once {
-				Ok(Some(value)) => {
-					self.w_line(&value, amt);
-					v.push(value);
-
-					if v.len() as u16 == self.max {
-						println!();
-						self.w_out(&v);
-						break;
-					}
-				}
-				Ok(None) => {
-					self.w_out(&v);
-					break;
-				}
-				Err(ClackError::Cancelled) => {
-					self.w_cancel(v.len());
-					if let Some(cancel) = self.cancel.as_deref() {
-						cancel();
-					}
-
-					return Err(ClackError::Cancelled);
-				}
-				Err(err) => return Err(err),
-			}
-		}
+		self.interact_lines()
+	}
 
-		Ok(v)
+	/// Draw the prompt as if the `values` were submitted, without waiting for the user.
+	///
+	/// Useful to show the answers of an earlier run as static lines before the first live prompt.
+	/// The [`on_submit`](MultiInput::on_submit()) hook isn't called,
+	/// and the answer isn't added to the [transcript](crate::transcript) or the [`json_mode()`](crate::json_mode) summary.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_input;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// multi_input("packages").render_submitted(&["serde", "thiserror"]);
+	/// let answer = multi_input("dev dependencies").interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn render_submitted<S: Display>(&self, values: &[S]) {
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_submitted(&mut r, values, true);
 	}
 }
 
 impl<M: Display> MultiInput<M> {
-	fn w_init(&self) {
-		let mut stdout = stdout();
+	/// Draw the prompt with the submitted values above the input line,
+	/// either in the error or the active style.
+	fn w_frame<V: Display>(
+		&self,
+		r: &mut Renderer,
+		values: &[V],
+		editing: Option<usize>,
+		error: Option<&str>,
+	) {
+		let (step, bar) = if error.is_some() {
+			(
+				(*chars::STEP_ERROR).yellow().to_string(),
+				(*chars::BAR).yellow().to_string(),
+			)
+		} else {
+			(
+				(*chars::STEP_ACTIVE).cyan().to_string(),
+				(*chars::BAR).cyan().to_string(),
+			)
+		};
+
+		let mut lines = vec![chars::BAR.to_string()];
+		lines.extend(r.message(step, &bar, &self.message, self.extra.as_deref()));
+
+		// the input line is drawn in place of the value that is edited
+		let editing = editing.unwrap_or(values.len());
+		let mut row = lines.len();
+		for (i, val) in values.iter().enumerate() {
+			if i == editing {
+				row = lines.len();
+				lines.push(bar.clone());
+			} else {
+				lines.push(format!("{}  {}", bar, val.dimmed()));
+			}
+		}
 
-		println!("{}", *chars::BAR);
-		println!("{}  {}", (*chars::STEP_ACTIVE).cyan(), self.message);
-		println!("{}", (*chars::BAR).cyan());
-		print!("{}", (*chars::BAR_END).cyan());
+		if editing == values.len() {
+			row = lines.len();
+			lines.push(bar);
+		}
 
-		let _ = stdout.queue(cursor::MoveToPreviousLine(1));
-		let _ = stdout.flush();
+		if let Some(error) = error {
+			lines.push(format!(
+				"{}  {}",
+				(*chars::BAR_END).yellow(),
+				error.yellow()
+			));
+		} else {
+			let help = self.help.as_deref().unwrap_or(self.finish.hint());
+			lines.push(render::end(Some(help)));
+		}
 
-		print!("{}  ", (*chars::BAR).cyan());
-		let _ = stdout.flush();
-	}
+		r.draw(lines, row);
 
-	fn w_line<V: Display>(&self, value: V, amt: u16) {
-		let mut stdout = stdout();
-		let _ = stdout.queue(cursor::MoveToPreviousLine(amt + 2));
-		let _ = stdout.flush();
+		let _ = frame::flush();
+	}
 
-		println!("{}  {}", (*chars::STEP_ACTIVE).cyan(), self.message);
+	fn w_out<V: Display>(&self, r: &mut Renderer, values: &[V]) {
+		self.w_submitted(r, values, false);
 
-		for _ in 0..amt {
-			println!("{}", (*chars::BAR).cyan());
+		if let Some(on_submit) = self.on_submit.as_deref() {
+			let answer = values.iter().map(ToString::to_string).collect::<Vec<_>>();
+			on_submit(&answer);
 		}
-
-		println!("{}  {}", (*chars::BAR).cyan(), value.dimmed());
-		println!("{}", (*chars::BAR).cyan());
-
-		print!("{}", ansi::CLEAR_LINE);
-		print!("{}", (*chars::BAR_END).cyan());
-
-		let _ = stdout.queue(cursor::MoveToPreviousLine(1));
-		let _ = stdout.flush();
 	}
 
-	fn w_val(&self, text: &str, amt: u16) {
-		let mut stdout = stdout();
-		let _ = stdout.queue(cursor::MoveToPreviousLine(amt + 2));
-		let _ = stdout.flush();
+	fn w_submitted<V: Display>(&self, r: &mut Renderer, values: &[V], preview: bool) {
+		let answer = values.iter().map(ToString::to_string).collect::<Vec<_>>();
+		let text = match self.format.as_deref() {
+			Some(format) => format(&answer),
+			None => answer.join(", "),
+		};
+		if preview {
+			transcript::preview(&self.message, &text);
+		} else {
+			transcript::prompt(&self.message, Some(&text));
+		}
 
-		println!("{}  {}", (*chars::STEP_ERROR).yellow(), self.message);
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_SUBMIT).green();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
 
-		for _ in 0..=amt {
-			println!("{}", (*chars::BAR).yellow());
+		if let Some(format) = self.format.as_deref() {
+			lines.push(format!("{}  {}", *chars::BAR, format(&answer).dimmed()));
+		} else if values.is_empty() {
+			lines.push(chars::BAR.to_string());
+		} else {
+			for val in values {
+				lines.push(format!("{}  {}", *chars::BAR, val.dimmed()));
+			}
 		}
 
-		print!("{}", ansi::CLEAR_LINE);
-		print!("{}  {}", (*chars::BAR_END).yellow(), text.yellow());
-
-		let _ = stdout.queue(cursor::MoveToPreviousLine(1));
-		let _ = stdout.flush();
+		r.finish(lines);
 	}
 
-	fn w_out<V: Display>(&self, values: &[V]) {
-		let amt = values.len();
-
-		let mut stdout = stdout();
-		let _ = stdout.queue(cursor::MoveToPreviousLine(amt as u16 + 2));
-		let _ = stdout.flush();
+	/// Draw the prompt as aborted after too many attempts, with the last `error`.
+	fn w_abort<V: Display>(&self, r: &mut Renderer, values: &[V], error: &str) {
+		transcript::prompt(&self.message, None);
 
-		println!("{}  {}", (*chars::STEP_SUBMIT).green(), self.message);
-
-		if amt == 0 {
-			println!("{}", *chars::BAR);
-		}
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_CANCEL).red();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
 
 		for val in values {
-			println!("{}  {}", *chars::BAR, val.dimmed());
+			lines.push(format!("{}  {}", *chars::BAR, val.dimmed()));
 		}
+		lines.push(format!("{}  {}", *chars::BAR, error.red()));
 
-		println!("{}", ansi::CLEAR_LINE);
-		println!("{}", ansi::CLEAR_LINE);
-
-		let _ = stdout.queue(cursor::MoveToPreviousLine(2));
-		let _ = stdout.flush();
+		r.finish(lines);
 	}
 
-	fn w_cancel(&self, amt: usize) {
-		let mut stdout = stdout();
-		let _ = stdout.queue(cursor::MoveToPreviousLine(1));
-		let _ = stdout.flush();
-
-		print!("{}", ansi::CLEAR_LINE);
-		println!("{}  {}", *chars::BAR, "cancelled".strikethrough().dimmed());
+	fn w_cancel<V: Display>(&self, r: &mut Renderer, values: &[V]) {
+		transcript::prompt(&self.message, None);
 
-		print!("{}", ansi::CLEAR_LINE);
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_CANCEL).red();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
 
-		let _ = stdout.queue(cursor::MoveToPreviousLine(amt as u16 + 2));
-		let _ = stdout.flush();
-
-		println!("{}  {}", (*chars::STEP_CANCEL).red(), self.message);
-
-		for _ in 0..amt {
-			println!("{}", *chars::BAR);
+		for val in values {
+			lines.push(format!("{}  {}", *chars::BAR, val.dimmed()));
 		}
+		lines.push(format!(
+			"{}  {}",
+			*chars::BAR,
+			"cancelled".strikethrough().dimmed()
+		));
 
-		let _ = stdout.queue(cursor::MoveToNextLine(1));
-		let _ = stdout.flush();
+		r.finish(lines);
 	}
 }
 
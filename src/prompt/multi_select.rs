@@ -1,20 +1,31 @@
 //! Select multiple options
 
+use super::{
+	cache::Prefill,
+	frame,
+	input::{CancelReturnFn, FormatFn, SubmitFn},
+	list::{self, CmpFn, EqFn, Icon, Incoming, Loader, Refresh, Row},
+	markup,
+	render::{self, RenderFn, Renderer},
+	select::{Feed, Selection},
+	session::Context,
+	spinner, text, transcript,
+};
 use crate::{
 	error::ClackError,
-	style::{ansi, chars, IS_UNICODE},
+	style::{chars, is_unicode},
 };
 use crossterm::{
-	cursor,
-	event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
-	execute, terminal,
-};
-use owo_colors::OwoColorize;
-use std::{
-	fmt::Display,
-	io::{stdout, Write},
+	event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+	terminal,
 };
-use unicode_truncate::UnicodeTruncateStr;
+use owo_colors::{OwoColorize, Style};
+use std::{cmp::Ordering, error::Error, fmt::Display, sync::Arc, time::Instant};
+
+const HELP: &str = "space to toggle, a to toggle all, i to invert";
+const CONFIRM_EMPTY: &str = "press enter again to select none";
+
+type CancelFn = dyn Fn(&[usize]) + Send + Sync;
 
 /// `MultiSelect` `Opt` struct
 #[derive(Debug, Clone)]
@@ -22,7 +33,11 @@ pub struct Opt<T: Clone, O: Display + Clone> {
 	value: T,
 	label: O,
 	hint: Option<String>,
+	style: Option<Style>,
+	icon: Option<Icon>,
 	active: bool,
+	/// When the option was selected, options that start out selected have `0`.
+	stamp: usize,
 }
 
 impl<T: Clone, O: Display + Clone> Opt<T, O> {
@@ -40,7 +55,10 @@ impl<T: Clone, O: Display + Clone> Opt<T, O> {
 			value,
 			label,
 			hint: hint.map(|hint| hint.to_string()),
+			style: None,
+			icon: None,
 			active: false,
+			stamp: 0,
 		}
 	}
 
@@ -70,28 +88,101 @@ impl<T: Clone, O: Display + Clone> Opt<T, O> {
 		Opt::new(value, label, Some(hint))
 	}
 
-	fn toggle(&mut self) {
-		self.active = !self.active;
+	/// Set whether the option starts out selected.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::multi_select::Opt;
+	///
+	/// let option = Opt::simple("value", "label").active(true);
+	/// ```
+	pub fn active(mut self, active: bool) -> Self {
+		self.active = active;
+		self
+	}
+
+	/// Draw an `icon` in front of the label, like an emoji.
+	///
+	/// The `ascii` fallback is drawn instead in terminals without unicode support,
+	/// the labels of all options are lined up after the widest icon.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::multi_select::Opt;
+	///
+	/// let option = Opt::simple("rust", "Rust").icon("🦀", "rs");
+	/// ```
+	pub fn icon<I: ToString, A: ToString>(mut self, icon: I, ascii: A) -> Self {
+		self.icon = Some(Icon::new(icon.to_string(), ascii.to_string()));
+		self
+	}
+
+	/// Select or deselect the option, recording the `stamp` if it is newly selected.
+	fn set(&mut self, active: bool, stamp: usize) {
+		if active && !self.active {
+			self.stamp = stamp;
+		}
+		self.active = active;
+	}
+
+	fn toggle(&mut self, stamp: usize) {
+		self.set(!self.active, stamp);
+	}
+
+	/// Style the label, e.g. to draw a destructive option in red.
+	///
+	/// The style is kept when the option isn't focused, with the label dimmed on top of it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::multi_select::Opt;
+	/// use owo_colors::Style;
+	///
+	/// let option = Opt::simple("rm", "delete everything").style(Style::new().red().bold());
+	/// ```
+	pub fn style(mut self, style: Style) -> Self {
+		self.style = Some(style);
+		self
 	}
 
-	fn trunc(&self, hint: usize) -> String {
-		let size = crossterm::terminal::size();
+	fn trunc(&self, width: Option<usize>, hint: usize) -> String {
 		let label = format!("{}", self.label);
 
-		let one_three = if *IS_UNICODE { 1 } else { 3 };
+		let one_three = if is_unicode() { 1 } else { 3 };
+
+		text::fit(&label, width, 4 + one_three + hint).into_owned()
+	}
+
+	/// Apply the style of the option to the `label`, dimmed on top of it if `dimmed` is `true`.
+	fn paint(&self, label: String, dimmed: bool) -> String {
+		match self.style {
+			Some(style) if dimmed => label.style(style.dimmed()).to_string(),
+			Some(style) => label.style(style).to_string(),
+			None if dimmed => label.dimmed().to_string(),
+			None => label,
+		}
+	}
 
-		match size {
-			Ok((width, _height)) => label
-				.unicode_truncate(width as usize - 4 - one_three - hint)
-				.0
-				.to_owned(),
-			Err(_) => label,
+	/// Format the icon and the label, leaving `extra` columns for the rest of the line.
+	fn fmt_label(&self, width: Option<usize>, column: usize, extra: usize, dimmed: bool) -> String {
+		let icon = list::fmt_icon(self.icon.as_ref(), column);
+		let label = self.paint(self.trunc(width, extra + text::width(&icon)), dimmed);
+
+		if dimmed {
+			format!("{}{}", icon.dimmed(), label)
+		} else {
+			format!("{}{}", icon, label)
 		}
 	}
 
-	fn focus(&self) -> String {
-		let hint_len = self.hint.as_deref().map_or(0, |hint| hint.len() + 3);
-		let label = self.trunc(hint_len);
+	fn focus(&self, width: Option<usize>, column: usize) -> String {
+		let hint = self.hint.as_deref().map(|hint| markup::render(hint, true));
+		let hint = hint.as_deref().map(|hint| text::fit(hint, width, 10));
+		let hint_len = hint.as_deref().map_or(0, |hint| text::width(hint) + 3);
+		let label = self.fmt_label(width, column, hint_len, false);
 
 		let fmt = if self.active {
 			format!("{} {}", (*chars::CHECKBOX_SELECTED).green(), label)
@@ -99,31 +190,55 @@ impl<T: Clone, O: Display + Clone> Opt<T, O> {
 			format!("{} {}", (*chars::CHECKBOX_ACTIVE).cyan(), label)
 		};
 
-		if let Some(hint) = &self.hint {
-			let hint = format!("({})", hint);
-			format!("{} {}", fmt, hint.dimmed())
+		if let Some(hint) = hint {
+			format!("{} {}{}{}", fmt, "(".dimmed(), hint, ")".dimmed())
 		} else {
 			fmt
 		}
 	}
 
-	fn unfocus(&self) -> String {
-		let label = self.trunc(0);
+	fn unfocus(&self, width: Option<usize>, column: usize) -> String {
+		let label = self.fmt_label(width, column, 0, true);
 
 		if self.active {
-			format!("{} {}", (*chars::CHECKBOX_SELECTED).green(), label.dimmed())
+			format!("{} {}", (*chars::CHECKBOX_SELECTED).green(), label)
 		} else {
-			format!(
-				"{} {}",
-				(*chars::CHECKBOX_INACTIVE).dimmed(),
-				label.dimmed()
-			)
+			format!("{} {}", (*chars::CHECKBOX_INACTIVE).dimmed(), label)
 		}
 	}
 }
 
+impl<T: Clone + Display> Opt<T, T> {
+	/// Creates a new `Opt` struct, using the value as the label.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::multi_select::Opt;
+	///
+	/// let option = Opt::auto("value");
+	/// ```
+	pub fn auto(value: T) -> Self {
+		Opt::simple(value.clone(), value)
+	}
+}
+
+impl<T: Clone, O: Display + Clone> From<(T, O)> for Opt<T, O> {
+	fn from((value, label): (T, O)) -> Self {
+		Opt::simple(value, label)
+	}
+}
+
+impl<T: Clone, O: Display + Clone, S: ToString> From<(T, O, S)> for Opt<T, O> {
+	fn from((value, label, hint): (T, O, S)) -> Self {
+		Opt::hint(value, label, hint)
+	}
+}
+
 /// `MultiSelect` struct
 ///
+/// Press space to toggle the focused option, `a` to toggle all options and `i` to invert the selection.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -139,13 +254,68 @@ impl<T: Clone, O: Display + Clone> Opt<T, O> {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Interacting doesn't change the builder, so it can be shared between threads and asked again.
+///
+/// ```no_run
+/// use may_clack::multi_select;
+///
+/// fn assert_send_sync<P: Send + Sync>(_: &P) {}
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let mut prompt = multi_select("message");
+/// prompt.option("val1", "value 1").option("val2", "value 2");
+/// assert_send_sync(&prompt);
+///
+/// let first = prompt.interact()?;
+/// let again = prompt.interact()?;
+/// println!("answers {:?} {:?}", first, again);
+/// # Ok(())
+/// # }
+/// ```
 pub struct MultiSelect<M: Display, T: Clone, O: Display + Clone> {
 	message: M,
+	extra: Option<String>,
+	help: Option<String>,
 	less: bool,
 	less_amt: Option<u16>,
 	less_max: Option<u16>,
-	cancel: Option<Box<dyn Fn()>>,
-	options: Vec<Opt<T, O>>,
+	less_fraction: Option<f32>,
+	cancel: Option<Arc<CancelFn>>,
+	cancel_return: Option<Arc<CancelReturnFn<Vec<T>>>>,
+	format: Option<Arc<FormatFn<[T]>>>,
+	on_render: Option<Arc<RenderFn>>,
+	context: Context,
+	on_submit: Option<Arc<SubmitFn<[T]>>>,
+	options: Vec<Row<Opt<T, O>>>,
+	initial: Option<Initial<T>>,
+	cached: Option<Prefill<T>>,
+	loader: Loader<Opt<T, O>>,
+	incoming: Option<Incoming<Opt<T, O>>>,
+	refresh: Option<Refresh<T, Opt<T, O>>>,
+	refresh_key: char,
+	dedup: Option<Arc<EqFn<T>>>,
+	sort: Option<Arc<CmpFn<Opt<T, O>>>>,
+	more: Option<Arc<dyn Fn(usize) -> String + Send + Sync>>,
+	min: u16,
+	max: u16,
+	required: Option<String>,
+	confirm_empty: bool,
+	ordered_by_selection: bool,
+	show_count: bool,
+}
+
+/// Values selected when the prompt starts, see [`MultiSelect::initial_values()`].
+struct Initial<T> {
+	values: Vec<T>,
+	eq: fn(&T, &T) -> bool,
+}
+
+/// Options of the prompt while it is active, so interacting with it doesn't change the builder.
+struct State<T: Clone, O: Display + Clone> {
+	rows: Vec<Row<Opt<T, O>>>,
+	/// When the prompt started loading, until all options arrived through the feed.
+	loading: Option<Instant>,
 }
 
 impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
@@ -168,11 +338,34 @@ impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
 	pub fn new(message: M) -> Self {
 		MultiSelect {
 			message,
+			extra: None,
+			help: None,
 			less: false,
 			less_amt: None,
 			less_max: None,
+			less_fraction: None,
 			cancel: None,
+			cancel_return: None,
+			format: None,
+			on_render: None,
+			context: Context::default(),
+			on_submit: None,
 			options: vec![],
+			initial: None,
+			cached: None,
+			loader: None,
+			incoming: None,
+			refresh: None,
+			refresh_key: 'r',
+			dedup: None,
+			sort: None,
+			more: None,
+			min: 0,
+			max: u16::MAX,
+			required: None,
+			confirm_empty: false,
+			ordered_by_selection: false,
+			show_count: false,
 		}
 	}
 
@@ -194,7 +387,7 @@ impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
 	/// ```
 	pub fn option(&mut self, val: T, label: O) -> &mut Self {
 		let opt = Opt::new(val, label, None::<String>);
-		self.options.push(opt);
+		self.options.push(Row::Opt(opt));
 		self
 	}
 
@@ -217,10 +410,70 @@ impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
 	/// ```
 	pub fn option_hint<S: ToString>(&mut self, val: T, label: O, hint: S) -> &mut Self {
 		let opt = Opt::new(val, label, Some(hint));
-		self.options.push(opt);
+		self.options.push(Row::Opt(opt));
+		self
+	}
+
+	/// Add an option with an `icon` in front of the label, like an emoji.
+	///
+	/// The icon is left out in terminals without unicode support,
+	/// use [`Opt::icon()`] to draw an ascii fallback instead.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("language")
+	///     .option_icon("🦀", "rust", "Rust")
+	///     .option_icon("🐹", "go", "Go")
+	///     .option_icon("🐍", "python", "Python")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn option_icon<I: ToString>(&mut self, icon: I, val: T, label: O) -> &mut Self {
+		let opt = Opt::simple(val, label).icon(icon, "");
+		self.options.push(Row::Opt(opt));
 		self
 	}
 
+	/// Open the prompt in a loading state, and get a [`Feed`] to send the options through.
+	///
+	/// A spinner row is drawn below the message until the first options arrive,
+	/// and below the options until the feed is done.
+	/// The options can be navigated and selected as soon as they arrive.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	/// use std::thread;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut prompt = multi_select("containers");
+	/// let feed = prompt.loading();
+	///
+	/// thread::spawn(move || {
+	///     for name in ["web", "db", "cache"] {
+	///         // slow api call
+	///         feed.push((name, name));
+	///     }
+	/// });
+	///
+	/// let answer = prompt.interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn loading(&mut self) -> Feed<Opt<T, O>> {
+		let (feed, incoming) = Feed::new();
+		self.incoming = Some(incoming);
+		feed
+	}
+
 	/// Add multiple options.
 	///
 	/// # Examples
@@ -237,16 +490,137 @@ impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
 	///
 	/// let answer = multi_select("message").options(opts).interact()?;
 	/// println!("answer {:?}", answer);
+	///
+	/// let opts = [("val1", "label 1"), ("val2", "label 2")];
+	/// let answer = multi_select("message").options(opts).interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn options<I, P>(&mut self, options: I) -> &mut Self
+	where
+		I: IntoIterator<Item = P>,
+		P: Into<Opt<T, O>>,
+	{
+		self.options.clear();
+		self.extend_options(options)
+	}
+
+	/// Add multiple options, keeping the existing ones.
+	///
+	/// Accepts anything that converts into an [`Opt`], like `(value, label)` or `(value, label, hint)` tuples.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	/// use std::collections::BTreeMap;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let map = BTreeMap::from([("val2", "label 2"), ("val3", "label 3")]);
+	///
+	/// let answer = multi_select("message")
+	///     .option("val1", "label 1")
+	///     .extend_options(map)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn extend_options<I, P>(&mut self, options: I) -> &mut Self
+	where
+		I: IntoIterator<Item = P>,
+		P: Into<Opt<T, O>>,
+	{
+		let options = options.into_iter().map(|opt| Row::Opt(opt.into()));
+		self.options.extend(options);
+		self
+	}
+
+	/// Add a non-selectable separator line.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .option("val1", "label 1")
+	///     .separator()
+	///     .option("val2", "label 2")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn separator(&mut self) -> &mut Self {
+		self.options.push(Row::Separator);
+		self
+	}
+
+	/// Add a non-selectable header label.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .header("fruits")
+	///     .option("mango", "Mango")
+	///     .option("peach", "Peach")
+	///     .header("vegetables")
+	///     .option("carrot", "Carrot")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn header<S: ToString>(&mut self, label: S) -> &mut Self {
+		self.options.push(Row::Header(label.to_string()));
+		self
+	}
+
+	/// Preselect the options with the given values.
+	///
+	/// Options that are already [active](Opt::active()) stay selected.
+	/// The values are looked up when the prompt starts,
+	/// so options added or loaded after calling this are considered too.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .option("val1", "label 1")
+	///     .option("val2", "label 2")
+	///     .option("val3", "label 3")
+	///     .initial_values(&["val1", "val3"])
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn options(&mut self, options: Vec<Opt<T, O>>) -> &mut Self {
-		self.options = options;
+	pub fn initial_values(&mut self, values: &[T]) -> &mut Self
+	where
+		T: PartialEq,
+	{
+		self.initial = Some(Initial {
+			values: values.to_vec(),
+			eq: T::eq,
+		});
 		self
 	}
 
 	/// Enable paging with the amount of terminal rows.
 	///
+	/// While paging, press `:` and type an item number (or `p` and a page number)
+	/// followed by enter to jump directly to it.
+	///
 	/// # Examples
 	///
 	/// ```no_run
@@ -311,7 +685,7 @@ impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
 	/// # Panics
 	///
 	/// Panics when the given value is 0.  
-	/// Panics when called after [`MultiSelect::less_max`] has already been called.
+	/// Panics when called after [`MultiSelect::less_max`] or [`MultiSelect::less_fraction`] has already been called.
 	///
 	/// # Examples
 	///
@@ -334,67 +708,54 @@ impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
 	pub fn less_amt(&mut self, less: u16) -> &mut Self {
 		assert!(less > 0, "less value has to be greater than zero");
 		assert!(
-			self.less_amt.is_none(),
-			"cannot set both less_amt and less_max"
+			self.less_max.is_none() && self.less_fraction.is_none(),
+			"cannot set less_amt together with less_max or less_fraction"
 		);
 		self.less = true;
 		self.less_amt = Some(less);
 		self
 	}
 
-	/// Specify function to call on cancel.
+	/// Enable paging with a `fraction` of the terminal rows, like `0.5` for half of them.
+	///
+	/// The page is resized with the terminal, and can be combined with [`MultiSelect::less_max`].
+	///
+	/// # Panics
+	///
+	/// Panics when the given value is not greater than 0 and at most 1.  
+	/// Panics when called after [`MultiSelect::less_amt`] has already been called.
 	///
 	/// # Examples
 	///
 	/// ```no_run
-	/// use may_clack::{multi_select, cancel};
+	/// use may_clack::multi_select;
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
-	/// let answer = multi_select("select")
-	///     .option("val1", "value 1")
-	///     .option("val2", "value 2")
-	///     .option_hint("val 3", "value 3", "hint")
-	///     .cancel(do_cancel)
+	/// let answer = multi_select("message")
+	///     .options((1..=100).map(|i| (i, format!("value {}", i))))
+	///     .less_fraction(0.5)
 	///     .interact()?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
-	///
-	/// fn do_cancel() {
-	///     cancel!("operation cancelled");
-	///     panic!("operation cancelled");
-	/// }
-	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
-	where
-		F: Fn() + 'static,
-	{
-		let cancel = Box::new(cancel);
-		self.cancel = Some(cancel);
-
+	/// ```
+	pub fn less_fraction(&mut self, fraction: f32) -> &mut Self {
+		assert!(
+			fraction > 0.0 && fraction <= 1.0,
+			"less fraction has to be greater than zero and at most one"
+		);
+		assert!(
+			self.less_amt.is_none(),
+			"cannot set both less_amt and less_fraction"
+		);
+		self.less = true;
+		self.less_fraction = Some(fraction);
 		self
 	}
 
-	fn mk_less(&self) -> Option<u16> {
-		if !self.less {
-			return None;
-		}
-
-		if let Some(less) = self.less_amt {
-			let is_less = self.options.len() > less as usize;
-			is_less.then_some(less)
-		} else if let Ok((_, rows)) = crossterm::terminal::size() {
-			let len = self.options.len();
-			let rows = rows.saturating_sub(4);
-			let rows = self.less_max.map_or(rows, |max| u16::min(rows, max));
-
-			let is_less = rows > 0 && len > rows as usize;
-			is_less.then_some(rows)
-		} else {
-			None
-		}
-	}
-
-	/// Wait for the user to submit the selected options.
+	/// Specify the minimum amount of selected options.
+	///
+	/// Submitting with fewer selected options shows an error instead.
 	///
 	/// # Examples
 	///
@@ -402,208 +763,1168 @@ impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
 	/// use may_clack::multi_select;
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
-	/// let answer = multi_select("select")
-	///     .option("val1", "value 1")
-	///     .option("val2", "value 2")
-	///     .option_hint("val 3", "value 3", "hint")
+	/// let answer = multi_select("message")
+	///     .option("val1", "label 1")
+	///     .option("val2", "label 2")
+	///     .option("val3", "label 3")
+	///     .min_choices(2)
 	///     .interact()?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn interact(&self) -> Result<Vec<T>, ClackError> {
-		if self.options.is_empty() {
-			return Err(ClackError::NoOptions);
-		}
+	pub fn min_choices(&mut self, min: u16) -> &mut Self {
+		self.min = min;
+		self
+	}
 
-		let mut options = self.options.clone();
+	/// Specify the maximum amount of selected options.
+	///
+	/// Selecting more options than that is blocked.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .option("val1", "label 1")
+	///     .option("val2", "label 2")
+	///     .option("val3", "label 3")
+	///     .max_choices(2)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn max_choices(&mut self, max: u16) -> &mut Self {
+		self.max = max;
+		self
+	}
+
+	/// Show how many options are selected next to the message, like `(3 selected)`,
+	/// so the selected options that are paged out of view with [`MultiSelect::less()`] are counted too.
+	///
+	/// With [`MultiSelect::max_choices()`] the maximum is shown as well, like `(2/3 selected)`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .options((1..=50).map(|i| (i, format!("label {}", i))))
+	///     .less()
+	///     .show_count()
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn show_count(&mut self) -> &mut Self {
+		self.show_count = true;
+		self
+	}
+
+	/// Require at least one option to be selected.
+	///
+	/// Submitting without a selected option shows the message `"selection is required"`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .option("val1", "label 1")
+	///     .option("val2", "label 2")
+	///     .required()
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn required(&mut self) -> &mut Self {
+		self.required_message("selection is required")
+	}
+
+	/// Like [`MultiSelect::required()`], but with a custom message.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .option("val1", "label 1")
+	///     .option("val2", "label 2")
+	///     .required_message("pick at least one topping")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn required_message<S: ToString>(&mut self, message: S) -> &mut Self {
+		self.required = Some(message.to_string());
+		self
+	}
+
+	/// Ask for a second enter when submitting without a selected option,
+	/// with the hint `"press enter again to select none"`.
+	///
+	/// Any other key cancels the confirmation.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .option("val1", "label 1")
+	///     .option("val2", "label 2")
+	///     .confirm_empty()
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn confirm_empty(&mut self) -> &mut Self {
+		self.confirm_empty = true;
+		self
+	}
+
+	/// Return the selected options in the order they were selected, instead of the order of the options.
+	///
+	/// Options that start out selected come first, options selected at once with `a` or `i` keep their order.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let steps = multi_select("build steps, in order")
+	///     .option("lint", "lint")
+	///     .option("test", "test")
+	///     .option("build", "build")
+	///     .ordered_by_selection()
+	///     .interact()?;
+	/// println!("steps {:?}", steps);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn ordered_by_selection(&mut self) -> &mut Self {
+		self.ordered_by_selection = true;
+		self
+	}
+
+	fn do_validate(&self, amt: usize) -> Result<(), String> {
+		match self.required {
+			Some(ref required) if amt == 0 => Err(required.clone()),
+			_ if amt < self.min as usize => Err(format!("minimum {}", self.min)),
+			_ => Ok(()),
+		}
+	}
+
+	/// Change the suffix of the submitted line, when not all selected labels fit the terminal width.
+	///
+	/// The closure gets the amount of labels that were cut off.
+	/// Defaults to `… and {n} more`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .options_auto(1..=100)
+	///     .more_label(|n| format!("+{}", n))
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn more_label<F>(&mut self, more: F) -> &mut Self
+	where
+		F: Fn(usize) -> String + Send + Sync + 'static,
+	{
+		self.more = Some(Arc::new(more));
+		self
+	}
+
+	/// Add secondary lines below the message, drawn dimmed while the prompt is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("select")
+	///     .message_extra("can be changed later")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn message_extra<S: ToString>(&mut self, extra: S) -> &mut Self {
+		self.extra = Some(extra.to_string());
+		self
+	}
+
+	/// Show a dimmed help line at the end of the prompt while it is active.
+	///
+	/// Replaces the default key hints.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("select")
+	///     .help("arrow keys to move, space to toggle")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn help<S: ToString>(&mut self, help: S) -> &mut Self {
+		self.help = Some(help.to_string());
+		self
+	}
+
+	/// Change what is printed after submitting, instead of the labels of the selected options.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .options_auto(1..=10)
+	///     .format_submitted(|values| format!("{} items selected", values.len()))
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn format_submitted<F>(&mut self, format: F) -> &mut Self
+	where
+		F: Fn(&[T]) -> String + Send + Sync + 'static,
+	{
+		self.format = Some(Arc::new(format));
+		self
+	}
+
+	/// Call `on_render` with the lines of every frame that is drawn, e.g. to record the prompt in tests.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	/// use std::sync::{Arc, Mutex};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let frames = Arc::new(Mutex::new(vec![]));
+	/// let recorded = Arc::clone(&frames);
+	/// let answer = multi_select("toppings")
+	///     .option("fruits", "Dried fruits")
+	///     .option("chocolate", "Chocolate chips")
+	///     .on_render(move |lines| recorded.lock().unwrap().push(lines.to_vec()))
+	///     .interact()?;
+	/// println!("{} frames", frames.lock().unwrap().len());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_render<F>(&mut self, on_render: F) -> &mut Self
+	where
+		F: Fn(&[String]) + Send + Sync + 'static,
+	{
+		self.on_render = Some(Arc::new(on_render));
+		self
+	}
+
+	/// Draw the prompt as part of a [`Session`](crate::session::Session),
+	/// with the [`Context`] the session passes to [`Session::prompt()`](crate::session::Session::prompt()).
+	///
+	/// The options in its [cached](crate::session::Session::cache()) answer are selected,
+	/// instead of the ones with the [initial values](MultiSelect::initial_values()).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{multi_select, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.steps(1);
+	///
+	/// let answer = session.prompt("regions", |cx| {
+	///     multi_select("regions")
+	///         .option("eu", "Europe")
+	///         .option("us", "America")
+	///         .context(cx)
+	///         .interact()
+	/// })?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn context(&mut self, cx: &Context) -> &mut Self
+	where
+		T: 'static,
+	{
+		self.context = cx.clone();
+		self.cached = cx.cached().map(Prefill::list);
+		self
+	}
+
+	/// Call `on_submit` with the answer when the prompt is submitted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("toppings")
+	///     .option("fruits", "Dried fruits")
+	///     .option("chocolate", "Chocolate chips")
+	///     .on_submit(|toppings| println!("picked {:?}", toppings))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_submit<F>(&mut self, on_submit: F) -> &mut Self
+	where
+		F: Fn(&[T]) + Send + Sync + 'static,
+	{
+		self.on_submit = Some(Arc::new(on_submit));
+		self
+	}
+
+	/// Specify function to call on cancel.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{multi_select, cancel};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("select")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .option_hint("val 3", "value 3", "hint")
+	///     .cancel(do_cancel)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	///
+	/// fn do_cancel() {
+	///     cancel!("operation cancelled");
+	///     panic!("operation cancelled");
+	/// }
+	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn() + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(move |_: &[usize]| cancel());
+		self.cancel = Some(cancel);
+
+		self
+	}
+
+	/// Like [`MultiSelect::cancel()`], but the function gets the indices of the selected options, not counting separators or headers.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("select")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .cancel_with(|selected| eprintln!("cancelled with {:?} selected", selected))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cancel_with<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn(&[usize]) + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(cancel);
+		self.cancel = Some(cancel);
+		self
+	}
+
+	/// Return the values of the `cancel_return` function instead of an `Err` when the prompt is cancelled.
+	///
+	/// Only [`MultiSelect::interact()`] returns them, since the values don't have to be options.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// // ctrl+c selects nothing
+	/// let features = multi_select("features")
+	///     .option("tests", "Tests")
+	///     .option("docs", "Docs")
+	///     .on_cancel_return(Vec::new)
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_cancel_return<F>(&mut self, cancel_return: F) -> &mut Self
+	where
+		F: Fn() -> Vec<T> + Send + Sync + 'static,
+	{
+		self.cancel_return = Some(Arc::new(cancel_return));
+		self
+	}
+
+	/// Load the options with a closure when the prompt starts, instead of specifying them upfront.
+	///
+	/// A spinner is shown below the message while the closure runs.
+	/// The loaded options replace the ones added with the other methods,
+	/// the closure runs again every time the prompt is interacted with.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{multi_select, multi_select::Opt};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .loader(|| {
+	///         let entries = std::fs::read_dir(".")?;
+	///         let opts = entries
+	///             .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+	///             .map(|name| name.map(Opt::auto))
+	///             .collect::<Result<Vec<_>, _>>();
+	///         opts
+	///     })
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn loader<F, E>(&mut self, loader: F) -> &mut Self
+	where
+		F: Fn() -> Result<Vec<Opt<T, O>>, E> + Send + Sync + 'static,
+		E: Into<Box<dyn Error + Send + Sync>>,
+	{
+		self.loader = Some(Arc::new(move || loader().map_err(Into::into)));
+		self
+	}
+
+	/// Re-fetch the options with a closure when the refresh key (`r` by default) is pressed.
+	///
+	/// The focus and the selected options are kept if their values are still in the new options.
+	/// If the closure fails, the prompt is aborted with [`ClackError::Load`],
+	/// or with [`ClackError::NoOptions`] and [`ClackError::DuplicateOptions`] if the new options can't be shown.
+	/// The error is drawn below the message.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{multi_select, multi_select::Opt};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// fn containers() -> std::io::Result<Vec<Opt<String, String>>> {
+	///     // ...
+	/// #   Ok(vec![])
+	/// }
+	///
+	/// let answer = multi_select("pick containers")
+	///     .loader(containers)
+	///     .on_refresh(containers)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_refresh<F, E>(&mut self, refresh: F) -> &mut Self
+	where
+		T: PartialEq,
+		F: Fn() -> Result<Vec<Opt<T, O>>, E> + Send + Sync + 'static,
+		E: Into<Box<dyn Error + Send + Sync>>,
+	{
+		self.refresh = Some(Refresh {
+			fetch: Arc::new(move || refresh().map_err(Into::into)),
+			eq: T::eq,
+		});
+		self
+	}
+
+	/// Change the key that re-fetches the options, see [`MultiSelect::on_refresh()`].
+	///
+	/// Defaults to `r`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .on_refresh(|| Ok::<_, std::io::Error>(vec![("val1", "value 1").into()]))
+	///     .refresh_key('u')
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn refresh_key(&mut self, key: char) -> &mut Self {
+		self.refresh_key = key;
+		self
+	}
+
+	/// Return [`ClackError::DuplicateOptions`] from `interact` if two options have the same value.
+	///
+	/// Duplicate values make it ambiguous which option was submitted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{multi_select, error::ClackError};
+	///
+	/// let answer = multi_select("message")
+	///     .option("val1", "value 1")
+	///     .option("val1", "value 2")
+	///     .deny_duplicates()
+	///     .interact();
+	/// assert!(matches!(answer, Err(ClackError::DuplicateOptions(0, 1))));
+	/// ```
+	pub fn deny_duplicates(&mut self) -> &mut Self
+	where
+		T: PartialEq + 'static,
+	{
+		self.dedup = Some(Arc::new(T::eq));
+		self
+	}
+
+	/// Like [`MultiSelect::deny_duplicates()`], but compares the keys extracted from the values.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .option(("a", 1), "value 1")
+	///     .option(("b", 2), "value 2")
+	///     .deny_duplicates_by(|&(name, _)| name)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn deny_duplicates_by<F, K>(&mut self, key: F) -> &mut Self
+	where
+		F: Fn(&T) -> K + Send + Sync + 'static,
+		K: PartialEq,
+	{
+		self.dedup = Some(Arc::new(move |a, b| key(a) == key(b)));
+		self
+	}
+
+	/// Sort the options by their labels before they are drawn.
+	///
+	/// Only options between the same separators or headers are sorted,
+	/// the selected options stay selected.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("branches")
+	///     .options(["main", "feature", "dev"].map(|branch| (branch, branch)))
+	///     .sort_by_label()
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn sort_by_label(&mut self) -> &mut Self {
+		self.sort = Some(Arc::new(|a, b| {
+			text::strip(&a.label.to_string()).cmp(&text::strip(&b.label.to_string()))
+		}));
+		self
+	}
+
+	/// Like [`MultiSelect::sort_by_label()`], but sorts the options by their values with a closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("sizes")
+	///     .option(500, "500 MB")
+	///     .option(20, "20 MB")
+	///     .option(1000, "1 GB")
+	///     .sort_by(|a, b| b.cmp(a))
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn sort_by<F>(&mut self, cmp: F) -> &mut Self
+	where
+		F: Fn(&T, &T) -> Ordering + Send + Sync + 'static,
+	{
+		self.sort = Some(Arc::new(move |a, b| cmp(&a.value, &b.value)));
+		self
+	}
+
+	/// Finish the prompt with an error if two options have the same value.
+	fn check_duplicates(
+		&self,
+		r: &mut Renderer,
+		rows: &[Row<Opt<T, O>>],
+	) -> Result<(), ClackError> {
+		let Some(eq) = self.dedup.as_deref() else {
+			return Ok(());
+		};
+
+		let values = rows.iter().filter_map(Row::opt).map(|opt| &opt.value);
+		match list::duplicate(values, eq) {
+			Some((a, b)) => {
+				let err = ClackError::DuplicateOptions(a, b);
+				list::fail(r, &self.message, &err);
+				Err(err)
+			}
+			None => Ok(()),
+		}
+	}
+
+	/// Select the options with the [initial values](MultiSelect::initial_values()),
+	/// or the ones in the cached answer if there is one.
+	fn preselect(&self, rows: &mut [Row<Opt<T, O>>]) {
+		if let Some(cached) = self.cached.as_ref() {
+			for opt in rows.iter_mut().filter_map(Row::opt_mut) {
+				opt.active |= cached.matches(&opt.value);
+			}
+			return;
+		}
+
+		let Some(initial) = self.initial.as_ref() else {
+			return;
+		};
+
+		for opt in rows.iter_mut().filter_map(Row::opt_mut) {
+			opt.active |= initial
+				.values
+				.iter()
+				.any(|value| (initial.eq)(value, &opt.value));
+		}
+	}
+
+	/// Run the loader, or copy the options added to the builder.
+	fn state(&self, r: &mut Renderer) -> Result<State<T, O>, ClackError> {
+		let mut rows = match self.loader.as_deref() {
+			Some(loader) => {
+				let opts = list::load(r, &self.message, loader)?;
+				opts.into_iter().map(Row::Opt).collect()
+			}
+			None => self.options.clone(),
+		};
+		self.preselect(&mut rows);
 
-		let max = self.options.len();
-		let is_less = self.mk_less();
+		Ok(State {
+			rows,
+			loading: None,
+		})
+	}
 
-		let mut idx = 0;
-		let mut less_idx: u16 = 0;
+	/// Wait for the first options of the [`Feed`], if there are no options yet.
+	fn wait_feed(&self, r: &mut Renderer, s: &mut State<T, O>) -> Result<(), ClackError> {
+		let Some(incoming) = self.incoming.as_ref() else {
+			return Ok(());
+		};
 
-		if let Some(less) = is_less {
-			self.w_init_less(less);
+		let started = Instant::now();
+		let (opts, done) = if list::first(&s.rows).is_some() {
+			list::receive(incoming)
+		} else {
+			list::wait(r, &self.message, incoming, started)?
+		};
+
+		let mut rows = opts.into_iter().map(Row::Opt).collect::<Vec<_>>();
+		self.preselect(&mut rows);
+		s.rows.extend(rows);
+		s.loading = (!done).then_some(started);
+		Ok(())
+	}
+
+	/// Add the options that arrived through the [`Feed`], and stop loading once all were sent.
+	///
+	/// Returns the new row index of the focused row `idx`.
+	fn receive(
+		&self,
+		r: &mut Renderer,
+		s: &mut State<T, O>,
+		idx: usize,
+	) -> Result<usize, ClackError> {
+		let Some(incoming) = self.incoming.as_ref() else {
+			return Ok(idx);
+		};
+
+		let (opts, done) = list::receive(incoming);
+		if done {
+			s.loading = None;
+		}
+
+		if opts.is_empty() {
+			return Ok(idx);
+		}
+
+		let mut rows = opts.into_iter().map(Row::Opt).collect::<Vec<_>>();
+		self.preselect(&mut rows);
+		s.rows.extend(rows);
+		self.check_duplicates(r, &s.rows)?;
+
+		match self.sort.as_deref() {
+			Some(sort) => Ok(list::sort(&mut s.rows, sort)[idx]),
+			None => Ok(idx),
+		}
+	}
+
+	fn mk_less(&self, r: &Renderer, s: &State<T, O>) -> Option<u16> {
+		if !self.less {
+			return None;
+		}
+
+		if let Some(less) = self.less_amt {
+			let is_less = s.rows.len() > less as usize;
+			is_less.then_some(less)
+		} else if let Some(rows) = r.height() {
+			let len = s.rows.len();
+			let loading = u16::from(s.loading.is_some());
+			let message = render::message_height(&self.message, self.extra.as_deref());
+			// the bar, the page line and the end line, or the error line in its place
+			let space = rows.saturating_sub(3 + message + loading + r.footer_height());
+			let rows = match self.less_fraction {
+				Some(fraction) => u16::min(space, ((rows as f32 * fraction) as u16).max(1)),
+				None => space,
+			};
+			let rows = self.less_max.map_or(rows, |max| u16::min(rows, max));
+
+			let is_less = rows > 0 && len > rows as usize;
+			is_less.then_some(rows)
 		} else {
-			self.w_init();
+			None
+		}
+	}
+
+	/// List the key bindings in the footer.
+	fn keys(&self, r: &mut Renderer, is_less: Option<u16>) {
+		let mut keys = vec![(*chars::ARROWS_UP_DOWN, "move")];
+		if is_less.is_some() {
+			keys.extend([("pgup/pgdn", "page"), (":", "jump")]);
+		}
+
+		keys.extend([("space", "toggle"), ("a", "all"), ("i", "invert")]);
+
+		let refresh = self.refresh_key.to_string();
+		if self.refresh.is_some() {
+			keys.push((&refresh, "refresh"));
+		}
+
+		keys.extend([("enter", "submit"), ("?", "hide keys")]);
+		r.keys(&keys);
+	}
+
+	/// Wait for the user to submit the selected options.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("select")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .option_hint("val 3", "value 3", "hint")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn interact(&self) -> Result<Vec<T>, ClackError> {
+		let selections = match (self.interact_indexed(), self.cancel_return.as_deref()) {
+			(Err(ClackError::Cancelled), Some(cancel_return)) => return Ok(cancel_return()),
+			(selections, _) => selections?,
+		};
+
+		Ok(selections.into_iter().map(|sel| sel.value).collect())
+	}
+
+	/// Draw the prompt as if the options with the `values` were submitted, without waiting for the user.
+	///
+	/// Useful to show the answers of an earlier run as static lines before the first live prompt.
+	/// The [`on_submit`](MultiSelect::on_submit()) hook isn't called,
+	/// and the answer isn't added to the [transcript](crate::transcript) or the [`json_mode()`](crate::json_mode) summary.
+	///
+	/// The options are drawn in the order of the `values`, values that none of the options have are left out,
+	/// options that are still [loading](MultiSelect::loading()) aren't considered.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// multi_select("features")
+	///     .option("ts", "typescript")
+	///     .option("lint", "eslint")
+	///     .option("fmt", "prettier")
+	///     .render_submitted(&["ts", "fmt"]);
+	/// let answer = multi_select("targets")
+	///     .option("web", "web")
+	///     .option("node", "node")
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn render_submitted(&self, values: &[T])
+	where
+		T: PartialEq,
+	{
+		let opts = opts(&self.options).collect::<Vec<_>>();
+		let selected = values
+			.iter()
+			.filter_map(|value| opts.iter().find(|opt| opt.value == *value).copied())
+			.collect::<Vec<_>>();
+
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_submitted(&mut r, &selected, true);
+	}
+
+	/// Like [`MultiSelect::interact()`], but also returns the indices of the selected options.
+	///
+	/// The indices count only options, not separators or headers.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let selections = multi_select("select")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .interact_indexed()?;
+	/// for selection in selections {
+	///     println!("index {} value {:?}", selection.index, selection.value);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn interact_indexed(&self) -> Result<Vec<Selection<T>>, ClackError> {
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		r.hide_cursor();
+
+		let mut s = self.state(&mut r)?;
+		self.wait_feed(&mut r, &mut s)?;
+		self.check_duplicates(&mut r, &s.rows)?;
+		if let Some(sort) = self.sort.as_deref() {
+			list::sort(&mut s.rows, sort);
 		}
 
+		let (Some(mut first), Some(mut last)) = (list::first(&s.rows), list::last(&s.rows)) else {
+			return Err(ClackError::NoOptions);
+		};
+
+		let mut is_less = self.mk_less(&r, &s);
+		self.keys(&mut r, is_less);
+
+		let mut idx = first;
+		let mut less_idx = is_less.map_or(0, |less| list::less_place(&s.rows, idx, less, 0));
+		let mut jump: Option<String> = None;
+		let mut error: Option<String> = None;
+		let mut confirming = false;
+		let mut stamp = 0;
+
+		self.draw(&mut r, &s, is_less, idx, less_idx, None, None, None);
+
 		terminal::enable_raw_mode()?;
 
 		loop {
-			if let Event::Key(key) = event::read()? {
+			frame::flush()?;
+
+			let tick = s.loading.map(|_| render::TICK);
+			let Some(event) = r.read_event(tick)? else {
+				idx = match self.receive(&mut r, &mut s, idx) {
+					Ok(idx) => idx,
+					Err(err) => {
+						terminal::disable_raw_mode()?;
+						return Err(err);
+					}
+				};
+				first = list::first(&s.rows).expect("received options are not empty");
+				last = list::last(&s.rows).expect("received options are not empty");
+
+				is_less = self.mk_less(&r, &s);
+				self.keys(&mut r, is_less);
+				less_idx = is_less.map_or(0, |less| list::less_place(&s.rows, idx, less, less_idx));
+
+				let (jump, error) = (jump.as_deref(), error.as_deref());
+				let hint = confirming.then_some(CONFIRM_EMPTY);
+				self.draw(&mut r, &s, is_less, idx, less_idx, jump, error, hint);
+				continue;
+			};
+			if let Event::Resize(width, height) = event {
+				r.resize(width, height);
+				jump = None;
+				error = None;
+				confirming = false;
+
+				is_less = self.mk_less(&r, &s);
+				self.keys(&mut r, is_less);
+				less_idx = self.move_to(&mut r, &s, is_less, idx, less_idx);
+			}
+
+			if let Event::Key(key) = event {
 				if key.kind == KeyEventKind::Press {
-					match (key.code, key.modifiers) {
-						(KeyCode::Up | KeyCode::Left, _) => {
-							if let Some(less) = is_less {
-								let prev_less = less_idx;
+					let confirmed = std::mem::take(&mut confirming);
+					stamp += 1;
+					if error.take().is_some() || (confirmed && key.code != KeyCode::Enter) {
+						self.draw(&mut r, &s, is_less, idx, less_idx, None, None, None);
+					}
 
-								if idx > 0 {
-									idx -= 1;
-									less_idx = less_idx.saturating_sub(1);
-								} else {
-									idx = max - 1;
-									less_idx = less - 1;
+					match (key.code, key.modifiers) {
+						(KeyCode::Char(':'), _) if jump.is_none() && is_less.is_some() => {
+							jump = Some(String::new());
+							let jump = jump.as_deref();
+							self.draw(&mut r, &s, is_less, idx, less_idx, jump, None, None);
+						}
+						(code, modifiers)
+							if jump.is_some() && !modifiers.contains(KeyModifiers::CONTROL) =>
+						{
+							let less = is_less.expect("jump is only possible in less mode");
+							let buf = jump.as_mut().expect("jump is some");
+
+							match code {
+								KeyCode::Char(c @ '0'..='9') => buf.push(c),
+								KeyCode::Char('p') if buf.is_empty() => buf.push('p'),
+								KeyCode::Backspace => {
+									buf.pop();
 								}
-
-								self.draw_less(&options, less, idx, less_idx, prev_less);
-							} else {
-								self.draw_unfocus(&options, idx);
-								let mut stdout = stdout();
-
-								if idx > 0 {
-									idx -= 1;
-									let _ = execute!(stdout, cursor::MoveUp(1));
-								} else if max > 1 {
-									idx = max - 1;
-									let _ = execute!(stdout, cursor::MoveDown(max as u16 - 1));
+								KeyCode::Enter => {
+									if let Some((i, l)) =
+										list::less_jump(buf, &s.rows, less, less_idx)
+									{
+										idx = i;
+										less_idx = l;
+									}
+									jump = None;
 								}
-
-								self.draw_focus(&options, idx);
+								KeyCode::Esc => jump = None,
+								_ => {}
 							}
+
+							let jump = jump.as_deref();
+							self.draw(&mut r, &s, is_less, idx, less_idx, jump, None, None);
 						}
-						(KeyCode::Down | KeyCode::Right, _) => {
-							if let Some(less) = is_less {
-								let prev_less = less_idx;
+						(KeyCode::Char('?'), _) if r.has_footer() => {
+							r.toggle_footer();
+							self.draw(&mut r, &s, is_less, idx, less_idx, None, None, None);
+						}
+						(KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT)
+							if c == self.refresh_key && self.refresh.is_some() =>
+						{
+							let refresh = self.refresh.as_ref().expect("refresh is some");
+							terminal::disable_raw_mode()?;
 
-								if idx < max - 1 {
-									idx += 1;
-									if less_idx < less - 1 {
-										less_idx += 1;
-									}
-								} else {
-									idx = 0;
-									less_idx = 0;
-								}
+							idx = self.refresh(&mut r, refresh, &mut s, idx)?;
+							first = list::first(&s.rows).expect("refreshed options are not empty");
+							last = list::last(&s.rows).expect("refreshed options are not empty");
 
-								self.draw_less(&options, less, idx, less_idx, prev_less);
-							} else {
-								self.draw_unfocus(&options, idx);
-								let mut stdout = stdout();
-
-								if idx < max - 1 {
-									idx += 1;
-									let _ = execute!(stdout, cursor::MoveDown(1));
-								} else if idx > 0 {
-									idx = 0;
-									let _ = execute!(stdout, cursor::MoveUp(max as u16 - 1));
-								}
+							is_less = self.mk_less(&r, &s);
+							self.keys(&mut r, is_less);
+							less_idx = self.move_to(&mut r, &s, is_less, idx, less_idx);
 
-								self.draw_focus(&options, idx);
-							}
+							terminal::enable_raw_mode()?;
 						}
-						(KeyCode::PageDown, _) => {
-							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								if idx + less as usize >= max - 1 {
-									less_idx = less - 1;
-									idx = max - 1;
-								} else {
-									idx += less as usize;
+						(KeyCode::Up | KeyCode::Left, _) => {
+							let (new, new_less) = match list::prev(&s.rows, idx) {
+								Some(new) => (new, less_idx.saturating_sub((idx - new) as u16)),
+								None => (last, u16::MAX),
+							};
 
-									if max - idx < (less - less_idx) as usize {
-										less_idx = less - (max - idx) as u16;
-									}
-								}
+							less_idx = self.move_to(&mut r, &s, is_less, new, new_less);
+							idx = new;
+						}
+						(KeyCode::Down | KeyCode::Right, _) => {
+							let (new, new_less) = match list::next(&s.rows, idx) {
+								Some(new) => (new, less_idx.saturating_add((new - idx) as u16)),
+								None => (first, 0),
+							};
 
-								self.draw_less(&options, less, idx, less_idx, prev_less);
-							}
+							less_idx = self.move_to(&mut r, &s, is_less, new, new_less);
+							idx = new;
 						}
-						(KeyCode::PageUp, _) if idx != 0 => {
+						(KeyCode::PageDown, _) if idx != last => {
 							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								if idx <= less as usize {
-									less_idx = 0;
-									idx = 0;
+								let target = idx + less as usize;
+								let (new, new_less) = if target >= last {
+									(last, u16::MAX)
 								} else {
-									idx -= less as usize;
-									less_idx = prev_less.min(idx as u16);
-								}
+									let new = list::snap(&s.rows, target).unwrap_or(last);
+									(new, less_idx)
+								};
 
-								self.draw_less(&options, less, idx, less_idx, prev_less);
+								less_idx = self.move_to(&mut r, &s, is_less, new, new_less);
+								idx = new;
 							}
 						}
-						(KeyCode::Home, _) if idx != 0 => {
+						(KeyCode::PageUp, _) if idx != first => {
 							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								idx = 0;
-								less_idx = 0;
-
-								self.draw_less(&options, less, idx, less_idx, prev_less);
-							} else {
-								self.draw_unfocus(&options, idx);
-
-								let mut stdout = stdout();
-								let _ = execute!(stdout, cursor::MoveUp(idx as u16));
+								let target = idx.saturating_sub(less as usize);
+								let (new, new_less) = if target <= first {
+									(first, 0)
+								} else {
+									let new = list::snap(&s.rows, target).unwrap_or(first);
+									(new, less_idx)
+								};
 
-								idx = 0;
-								self.draw_focus(&options, 0);
+								less_idx = self.move_to(&mut r, &s, is_less, new, new_less);
+								idx = new;
 							}
 						}
-						(KeyCode::End, _) if idx != max - 1 => {
-							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								idx = max - 1;
-								less_idx = less - 1;
-
-								self.draw_less(&options, less, idx, less_idx, prev_less);
+						(KeyCode::Home, _) if idx != first => {
+							less_idx = self.move_to(&mut r, &s, is_less, first, 0);
+							idx = first;
+						}
+						(KeyCode::End, _) if idx != last => {
+							less_idx = self.move_to(&mut r, &s, is_less, last, u16::MAX);
+							idx = last;
+						}
+						(KeyCode::Char(' '), _) => {
+							let amt = count(&s.rows);
+							let opt = s
+								.rows
+								.get_mut(idx)
+								.and_then(Row::opt_mut)
+								.expect("idx should always be an option");
+							if !opt.active && amt >= self.max as usize {
+								error = Some(format!("maximum {}", self.max));
 							} else {
-								self.draw_unfocus(&options, idx);
-
-								let mut stdout = stdout();
-								let diff = max - idx - 1;
-								let _ = execute!(stdout, cursor::MoveDown(diff as u16));
-
-								idx = max - 1;
+								opt.toggle(stamp);
+							}
 
-								self.draw_focus(&options, idx);
+							let error = error.as_deref();
+							self.draw(&mut r, &s, is_less, idx, less_idx, None, error, None);
+						}
+						(KeyCode::Char('a'), _) => {
+							let active = !opts(&s.rows).all(|opt| opt.active);
+							if active && list::count(&s.rows) > self.max as usize {
+								error = Some(format!("maximum {}", self.max));
+							} else {
+								let opts = s.rows.iter_mut().filter_map(Row::opt_mut);
+								opts.for_each(|opt| opt.set(active, stamp));
 							}
+
+							let error = error.as_deref();
+							self.draw(&mut r, &s, is_less, idx, less_idx, None, error, None);
 						}
-						(KeyCode::Char(' '), _) => {
-							let opt = options.get_mut(idx).expect("idx should always be in bound");
-							opt.toggle();
-							self.draw_focus(&options, idx);
+						(KeyCode::Char('i'), _) => {
+							if list::count(&s.rows) - count(&s.rows) > self.max as usize {
+								error = Some(format!("maximum {}", self.max));
+							} else {
+								let opts = s.rows.iter_mut().filter_map(Row::opt_mut);
+								opts.for_each(|opt| opt.toggle(stamp));
+							}
+
+							let error = error.as_deref();
+							self.draw(&mut r, &s, is_less, idx, less_idx, None, error, None);
 						}
 						(KeyCode::Enter, _) => {
-							terminal::disable_raw_mode()?;
+							if let Err(text) = self.do_validate(count(&s.rows)) {
+								let err = Some(text.as_str());
+								self.draw(&mut r, &s, is_less, idx, less_idx, None, err, None);
+								error = Some(text);
+								continue;
+							}
 
-							let selected_opts =
-								options.iter().filter(|opt| opt.active).collect::<Vec<_>>();
+							if self.confirm_empty && count(&s.rows) == 0 && !confirmed {
+								let hint = Some(CONFIRM_EMPTY);
+								self.draw(&mut r, &s, is_less, idx, less_idx, None, None, hint);
+								confirming = true;
+								continue;
+							}
 
-							if let Some(less) = is_less {
-								self.w_out_less(less, less_idx, &selected_opts);
-							} else {
-								self.w_out(idx, &selected_opts);
+							terminal::disable_raw_mode()?;
+
+							let mut selected = s
+								.rows
+								.into_iter()
+								.filter_map(|row| match row {
+									Row::Opt(opt) => Some(opt),
+									_ => None,
+								})
+								.enumerate()
+								.filter(|(_, opt)| opt.active)
+								.collect::<Vec<_>>();
+							if self.ordered_by_selection {
+								selected.sort_by_key(|(_, opt)| opt.stamp);
 							}
 
-							let all = options
+							let selected_opts =
+								selected.iter().map(|(_, opt)| opt).collect::<Vec<_>>();
+							self.w_out(&mut r, &selected_opts);
+
+							let all = selected
 								.into_iter()
-								.filter(|opt| opt.active)
-								.map(|opt| opt.value)
+								.map(|(index, opt)| Selection {
+									index,
+									value: opt.value,
+								})
 								.collect();
 
 							return Ok(all);
 						}
 						(KeyCode::Char('c' | 'd'), KeyModifiers::CONTROL) => {
 							terminal::disable_raw_mode()?;
-
-							if let Some(less) = is_less {
-								self.w_cancel_less(less, idx, less_idx);
-							} else {
-								self.w_cancel(idx);
-							}
+							self.w_cancel(&mut r, &s.rows, idx);
 
 							if let Some(cancel) = self.cancel.as_deref() {
-								cancel();
+								let selected = opts(&s.rows)
+									.enumerate()
+									.filter(|(_, opt)| opt.active)
+									.map(|(index, _)| index)
+									.collect::<Vec<_>>();
+								cancel(&selected);
 							}
 
-							panic!();
+							return Err(ClackError::Cancelled);
 						}
 						_ => {}
 					}
@@ -614,211 +1935,314 @@ impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
 }
 
 impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
-	fn draw_focus(&self, options: &[Opt<T, O>], idx: usize) {
-		let opt = options.get(idx).expect("idx should always be in bound");
-		let line = opt.focus();
-		self.draw(&line);
-	}
+	/// Re-fetch the options, carrying over the selection and returning the new focused row index.
+	fn refresh(
+		&self,
+		r: &mut Renderer,
+		refresh: &Refresh<T, Opt<T, O>>,
+		s: &mut State<T, O>,
+		idx: usize,
+	) -> Result<usize, ClackError> {
+		let fetched = list::load(r, &self.message, &*refresh.fetch)?;
+		let mut rows = fetched.into_iter().map(Row::Opt).collect::<Vec<_>>();
+		self.check_duplicates(r, &rows)?;
+		if let Some(sort) = self.sort.as_deref() {
+			list::sort(&mut rows, sort);
+		}
 
-	fn draw_unfocus(&self, options: &[Opt<T, O>], idx: usize) {
-		let opt = options.get(idx).expect("idx should always be in bound");
-		let line = opt.unfocus();
-		self.draw(&line);
-	}
+		let prev = std::mem::replace(&mut s.rows, rows);
 
-	fn draw(&self, line: &str) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToColumn(0));
+		let focus = prev[idx].opt().map(|opt| &opt.value);
+		let mut new_idx = None;
 
-		print!("{}", ansi::CLEAR_LINE);
-		print!("{}  {}", (*chars::BAR).cyan(), line);
-		let _ = stdout.flush();
-	}
+		for (i, row) in s.rows.iter_mut().enumerate() {
+			let Some(opt) = row.opt_mut() else {
+				continue;
+			};
 
-	fn draw_less(&self, opts: &[Opt<T, O>], less: u16, idx: usize, less_idx: u16, prev_less: u16) {
-		let mut stdout = stdout();
-		if prev_less > 0 {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(prev_less));
-		} else {
-			let _ = execute!(stdout, cursor::MoveToColumn(0));
+			if let Some(old) = opts(&prev).find(|old| (refresh.eq)(&old.value, &opt.value)) {
+				opt.active = old.active;
+				opt.stamp = old.stamp;
+			}
+
+			if new_idx.is_none() && focus.is_some_and(|focus| (refresh.eq)(focus, &opt.value)) {
+				new_idx = Some(i);
+			}
 		}
 
-		for i in 0..less.into() {
-			let i_idx = idx + i - less_idx as usize;
-			let opt = opts.get(i_idx).expect("i_idx should always be in bound");
-			let line = opt.unfocus();
+		let new_idx = new_idx.or_else(|| list::snap(&s.rows, idx));
+		new_idx.ok_or_else(|| {
+			list::fail(r, &self.message, &ClackError::NoOptions);
+			ClackError::NoOptions
+		})
+	}
 
-			print!("{}", ansi::CLEAR_LINE);
-			println!("{}  {}\r", (*chars::BAR).cyan(), line);
+	/// Move the focus to `idx`, returning the new `less_idx`.
+	fn move_to(
+		&self,
+		r: &mut Renderer,
+		s: &State<T, O>,
+		is_less: Option<u16>,
+		idx: usize,
+		less_idx: u16,
+	) -> u16 {
+		let less_idx = is_less.map_or(0, |less| list::less_place(&s.rows, idx, less, less_idx));
+		self.draw(r, s, is_less, idx, less_idx, None, None, None);
+		less_idx
+	}
 
-			let _ = execute!(stdout, cursor::MoveToColumn(0));
-		}
+	/// Draw the prompt, either in the error or the active style.
+	#[allow(clippy::too_many_arguments)]
+	fn draw(
+		&self,
+		r: &mut Renderer,
+		s: &State<T, O>,
+		is_less: Option<u16>,
+		idx: usize,
+		less_idx: u16,
+		jump: Option<&str>,
+		error: Option<&str>,
+		hint: Option<&str>,
+	) {
+		let opts = &s.rows;
+		let (start, end) = match is_less {
+			Some(less) => {
+				let start = idx - less_idx as usize;
+				(start, start + less as usize)
+			}
+			None => (0, opts.len()),
+		};
 
-		let max = self.options.len();
-		let amt = max.to_string().len();
-		print!("{}", ansi::CLEAR_LINE);
-		println!(
-			"{}  ......... ({:#0amt$}/{})",
-			(*chars::BAR).cyan(),
-			idx + 1,
-			max,
-			amt = amt
-		);
+		let extra = self.extra.as_deref();
+		let mut message = if error.is_some() {
+			let (step, bar) = ((*chars::STEP_ERROR).yellow(), (*chars::BAR).yellow());
+			r.message(step, bar, &self.message, extra)
+		} else {
+			let (step, bar) = ((*chars::STEP_ACTIVE).cyan(), (*chars::BAR).cyan());
+			r.message(step, bar, &self.message, extra)
+		};
 
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(less + 1));
-		if less_idx > 0 {
-			let _ = execute!(stdout, cursor::MoveToNextLine(less_idx));
+		if self.show_count {
+			let count = opts.iter().filter_map(Row::opt).filter(|opt| opt.active);
+			let count = match self.max {
+				u16::MAX => format!("({} selected)", count.count()),
+				max => format!("({}/{} selected)", count.count(), max),
+			};
+			message[0] = format!("{} {}", message[0], count.dimmed());
 		}
 
-		self.draw_focus(opts, idx);
-	}
-}
+		let column = list::icon_column(
+			opts.iter()
+				.filter_map(Row::opt)
+				.map(|opt| opt.icon.as_ref()),
+		);
 
-impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
-	fn w_init(&self) {
-		let mut stdout = stdout();
+		let mut lines = vec![chars::BAR.to_string()];
+		lines.extend(message);
+		let head = lines.len();
 
-		println!("{}", *chars::BAR);
-		println!("{}  {}", (*chars::STEP_ACTIVE).cyan(), self.message);
+		for (i, row) in opts.iter().enumerate().take(end).skip(start) {
+			let line = match row {
+				Row::Opt(opt) if i == idx => opt.focus(r.width(), column),
+				row => fmt_unfocus(r.width(), column, row),
+			};
+			lines.push(format!("{}  {}", (*chars::BAR).cyan(), line));
+		}
 
-		for opt in &self.options {
-			let line = opt.unfocus();
-			println!("{}  {}", (*chars::BAR).cyan(), line);
+		if let Some(less) = is_less {
+			lines.push(list::fmt_less(opts, less, idx, jump));
 		}
 
-		print!("{}", (*chars::BAR_END).cyan());
+		if let Some(started) = s.loading {
+			lines.push(spinner::fmt_row("loading", started));
+		}
 
-		let len = self.options.len() as u16;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(len));
+		if let Some(error) = error {
+			lines.push(format!(
+				"{}  {}",
+				(*chars::BAR_END).yellow(),
+				error.yellow()
+			));
+		} else {
+			let help = hint.or(self.help.as_deref()).unwrap_or(HELP);
+			lines.push(render::end(Some(help)));
+		}
 
-		self.draw_focus(&self.options, 0);
+		r.draw(lines, head + idx - start);
 	}
+}
 
-	fn w_init_less(&self, less: u16) {
-		println!("{}", *chars::BAR);
-		println!("{}  {}", (*chars::STEP_ACTIVE).cyan(), self.message);
-
-		self.draw_less(&self.options, less, 0, 0, 0);
-
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToNextLine(less));
+impl<M: Display, T: Clone, O: Display + Clone> MultiSelect<M, T, O> {
+	fn w_cancel(&self, r: &mut Renderer, opts: &[Row<Opt<T, O>>], idx: usize) {
+		transcript::prompt(&self.message, None);
 
-		println!();
-		print!("{}", (*chars::BAR_END).cyan());
+		let label = &opts
+			.get(idx)
+			.and_then(Row::opt)
+			.expect("idx should always be an option")
+			.label;
 
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(less + 1));
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_CANCEL).red();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!(
+			"{}  {}",
+			*chars::BAR,
+			label.strikethrough().dimmed()
+		));
 
-		self.draw_focus(&self.options, 0);
+		r.finish(lines);
 	}
 
-	fn w_cancel(&self, idx: usize) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(idx as u16 + 1));
+	fn w_out(&self, r: &mut Renderer, selected: &[&Opt<T, O>]) {
+		self.w_submitted(r, selected, false);
 
-		println!("{}  {}", (*chars::STEP_CANCEL).red(), self.message);
-
-		for _ in &self.options {
-			println!("{}", ansi::CLEAR_LINE);
+		if let Some(on_submit) = self.on_submit.as_deref() {
+			let values = selected
+				.iter()
+				.map(|opt| opt.value.clone())
+				.collect::<Vec<_>>();
+			on_submit(&values);
 		}
-		print!("{}", ansi::CLEAR_LINE);
-
-		let len = self.options.len() as u16;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(len));
-
-		let label = &self
-			.options
-			.get(idx)
-			.expect("idx should always be in bound")
-			.label;
-		println!("{}  {}", *chars::BAR, label.strikethrough().dimmed());
 	}
 
-	fn w_cancel_less(&self, less: u16, idx: usize, less_idx: u16) {
-		let mut stdout = stdout();
-		if less_idx > 0 {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(less_idx + 1));
+	fn w_submitted(&self, r: &mut Renderer, selected: &[&Opt<T, O>], preview: bool) {
+		let answer = self.fmt_submitted(None, selected);
+		if preview {
+			transcript::preview(&self.message, &answer);
 		} else {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(1));
+			transcript::prompt(&self.message, Some(&answer));
 		}
 
-		println!("{}  {}", (*chars::STEP_CANCEL).red(), self.message);
-
-		for _ in 0..less.into() {
-			println!("{}", ansi::CLEAR_LINE);
-		}
+		let submitted = self.fmt_submitted(r.width(), selected);
 
-		println!("{}", ansi::CLEAR_LINE);
-		println!("{}", ansi::CLEAR_LINE);
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_SUBMIT).green();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!("{}  {}", *chars::BAR, submitted));
 
-		let mv = less + 2;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(mv));
+		r.finish(lines);
+	}
 
-		let label = &self
-			.options
-			.get(idx)
-			.expect("idx should always be in bound")
-			.label;
-		println!("{}  {}", *chars::BAR, label.strikethrough().dimmed());
+	fn fmt_submitted(&self, width: Option<usize>, selected: &[&Opt<T, O>]) -> String {
+		if let Some(format) = self.format.as_deref() {
+			let values = selected
+				.iter()
+				.map(|opt| opt.value.clone())
+				.collect::<Vec<_>>();
+			format(&values).dimmed().to_string()
+		} else if selected.is_empty() {
+			"none".dimmed().italic().to_string()
+		} else {
+			let vals = selected.iter().map(|&opt| &opt.label).collect::<Vec<_>>();
+			self.join(width, &vals).dimmed().to_string()
+		}
 	}
 
-	fn w_out(&self, idx: usize, selected: &[&Opt<T, O>]) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(idx as u16 + 1));
+	/// Join the labels, cutting off the ones that don't fit the terminal width.
+	fn join(&self, width: Option<usize>, v: &[&O]) -> String {
+		let labels = v.iter().map(|val| val.to_string()).collect::<Vec<_>>();
+		let joined = labels.join(", ");
 
-		println!("{}  {}", (*chars::STEP_SUBMIT).green(), self.message);
+		let Some(width) = width else {
+			return joined;
+		};
+		let width = width.saturating_sub(3);
 
-		for _ in &self.options {
-			println!("{}", ansi::CLEAR_LINE);
+		let fits = |line: &str| text::width(line) <= width;
+		if fits(&joined) {
+			return joined;
 		}
-		println!("{}", ansi::CLEAR_LINE);
 
-		let mv = self.options.len() as u16 + 1;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(mv));
+		let more = |n: usize| match self.more.as_deref() {
+			Some(more) => more(n),
+			None => format!("{} and {} more", *chars::ELLIPSIS, n),
+		};
 
-		let vals = selected.iter().map(|&opt| &opt.label).collect::<Vec<_>>();
+		let mut line = String::new();
+		let mut shown = 0;
+		for label in &labels {
+			let next = if line.is_empty() {
+				label.clone()
+			} else {
+				format!("{}, {}", line, label)
+			};
+
+			if !fits(&format!("{} {}", next, more(labels.len() - shown - 1))) {
+				break;
+			}
 
-		if vals.is_empty() {
-			println!("{}  {}", *chars::BAR, "none".dimmed().italic());
-		} else {
-			let vals = self.join(&vals);
-			println!("{}  {}", *chars::BAR, vals.dimmed());
-		};
-	}
+			line = next;
+			shown += 1;
+		}
 
-	fn w_out_less(&self, less: u16, less_idx: u16, selected: &[&Opt<T, O>]) {
-		let mut stdout = stdout();
-		if less_idx > 0 {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(less_idx + 1));
+		if shown == 0 {
+			text::truncate(&more(labels.len()), width).into_owned()
 		} else {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(1));
+			format!("{} {}", line, more(labels.len() - shown))
 		}
+	}
+}
 
-		println!("{}  {}", (*chars::STEP_SUBMIT).green(), self.message);
-
-		for _ in 0..less.into() {
-			println!("{}", ansi::CLEAR_LINE);
-		}
-		println!("{}", ansi::CLEAR_LINE);
-		println!("{}", ansi::CLEAR_LINE);
+fn opts<T: Clone, O: Display + Clone>(
+	options: &[Row<Opt<T, O>>],
+) -> impl Iterator<Item = &Opt<T, O>> {
+	options.iter().filter_map(Row::opt)
+}
 
-		let mv = less + 2;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(mv));
+/// Amount of selected options.
+fn count<T: Clone, O: Display + Clone>(options: &[Row<Opt<T, O>>]) -> usize {
+	opts(options).filter(|opt| opt.active).count()
+}
 
-		let vals = selected.iter().map(|&opt| &opt.label).collect::<Vec<_>>();
+fn fmt_unfocus<T: Clone, O: Display + Clone>(
+	width: Option<usize>,
+	column: usize,
+	row: &Row<Opt<T, O>>,
+) -> String {
+	match row {
+		Row::Opt(opt) => opt.unfocus(width, column),
+		row => row.fmt_separator(width),
+	}
+}
 
-		if vals.is_empty() {
-			println!("{}  {}", *chars::BAR, "none".dimmed().italic());
-		} else {
-			let vals = self.join(&vals);
-			println!("{}  {}", *chars::BAR, vals.dimmed());
-		};
+impl<M: Display, T: Clone + Display> MultiSelect<M, T, T> {
+	/// Add an option, using the value as the label.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message")
+	///     .option_auto("val1")
+	///     .option_auto("val2")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn option_auto(&mut self, value: T) -> &mut Self {
+		self.options.push(Row::Opt(Opt::auto(value)));
+		self
 	}
 
-	fn join(&self, v: &[&O]) -> String {
-		v.iter()
-			.map(|val| val.to_string())
-			.collect::<Vec<_>>()
-			.join(", ")
+	/// Add multiple options, using the values as the labels.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::multi_select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = multi_select("message").options_auto(1..=10).interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn options_auto<I: IntoIterator<Item = T>>(&mut self, values: I) -> &mut Self {
+		self.extend_options(values.into_iter().map(Opt::auto))
 	}
 }
 
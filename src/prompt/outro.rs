@@ -0,0 +1,98 @@
+//! Outro
+
+use super::{frame, summary, transcript};
+use crate::style::{chars, BadgeStyle};
+use std::fmt::Display;
+
+/// `Outro` struct.
+///
+/// Builder for the message that ends a prompt session, see also [`outro!`](crate::outro!).
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::{outro::Outro, style::BadgeStyle};
+///
+/// Outro::new("done").badge_style(BadgeStyle::Bold).print();
+/// ```
+pub struct Outro<M: Display> {
+	message: M,
+	badge_style: BadgeStyle,
+	blank_line: bool,
+}
+
+impl<M: Display> Outro<M> {
+	/// Creates a new `Outro` struct.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::outro::Outro;
+	///
+	/// Outro::new("done").print();
+	/// ```
+	pub fn new(message: M) -> Outro<M> {
+		Outro {
+			message,
+			badge_style: BadgeStyle::Plain,
+			blank_line: true,
+		}
+	}
+
+	/// Change the style of the message.
+	///
+	/// Default: [`BadgeStyle::Plain`]
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::{outro::Outro, style::BadgeStyle};
+	///
+	/// Outro::new("done").badge_style(BadgeStyle::Reversed).print();
+	/// ```
+	pub fn badge_style(&mut self, style: BadgeStyle) -> &mut Self {
+		self.badge_style = style;
+		self
+	}
+
+	/// Whether to write an empty line after the outro.
+	///
+	/// Default: `true`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::outro::Outro;
+	///
+	/// Outro::new("done").blank_line(false).print();
+	/// ```
+	pub fn blank_line(&mut self, blank_line: bool) -> &mut Self {
+		self.blank_line = blank_line;
+		self
+	}
+
+	/// Write the outro.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::outro::Outro;
+	///
+	/// Outro::new("done").print();
+	/// ```
+	pub fn print(&self) {
+		let message = self.badge_style.badge(&self.message);
+		if frame::is_quiet() {
+			frame::line(message);
+		} else {
+			frame::line(*chars::BAR);
+			frame::line(format!("{}  {}", *chars::BAR_END, message));
+			if self.blank_line {
+				frame::line("");
+			}
+		}
+
+		transcript::message("outro", &self.message.to_string());
+		summary::emit("done");
+	}
+}
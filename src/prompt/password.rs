@@ -0,0 +1,874 @@
+//! Password input
+
+use super::{
+	frame,
+	input::{CancelFn, CancelReturnFn, SubmitFn, ValidateFn},
+	render::{self, RenderFn, Renderer},
+	session::Context,
+	transcript,
+};
+use crate::{
+	error::ClackError,
+	style::chars,
+	validate::{Attempt, Validator},
+};
+use crossterm::{
+	event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+	terminal,
+};
+use owo_colors::{AnsiColors, OwoColorize};
+use std::{
+	borrow::Cow,
+	fmt::Display,
+	sync::{Arc, Mutex, PoisonError},
+};
+
+/// `Password` struct
+///
+/// Accepts a single line of text, drawn masked while it is typed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::password;
+/// # use std::borrow::Cow;
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let answer = password("password")
+///     .validate(|x| {
+///         if x.chars().count() < 8 {
+///             Err(Cow::Borrowed("use at least 8 characters"))
+///         } else {
+///             Ok(())
+///         }
+///     })
+///     .confirm("repeat password")
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Password<M: Display> {
+	message: M,
+	extra: Option<String>,
+	help: Option<String>,
+	confirm: Option<String>,
+	validate: Option<Mutex<Box<ValidateFn>>>,
+	max_attempts: Option<usize>,
+	strength: Option<Arc<StrengthFn>>,
+	mask: Mask,
+	submitted: Submitted,
+	cancel: Option<Arc<CancelFn>>,
+	cancel_return: Option<Arc<CancelReturnFn<String>>>,
+	on_render: Option<Arc<RenderFn>>,
+	context: Context,
+	on_submit: Option<Arc<SubmitFn<str>>>,
+}
+
+/// How the password is drawn while it is typed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mask {
+	/// One mask char for every typed char.
+	#[default]
+	PerChar,
+	/// The same amount of mask chars, no matter how long the password is.
+	Fixed(usize),
+	/// Nothing, not even the length of the password is shown.
+	Hidden,
+}
+
+/// What is drawn in place of the password after it is submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Submitted {
+	/// The mask, like it was drawn while the password was typed.
+	#[default]
+	Mask,
+	/// `<hidden>`
+	Hidden,
+	/// The last chars of the password after an ellipsis,
+	/// or only the ellipsis if the password isn't longer than that.
+	Last(usize),
+	/// Nothing, the answer line is left out.
+	Nothing,
+}
+
+type StrengthFn = dyn Fn(&str) -> Strength + Send + Sync;
+
+/// `Strength` struct.
+///
+/// Strength of a password, drawn as a meter below it by [`Password::strength()`].
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::password::Strength;
+///
+/// let strength = Strength {
+///     score: 2,
+///     rules: vec![("at least 12 characters".into(), false)],
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Strength {
+	/// Score from `0`, very weak, to `4`, strong.
+	pub score: u8,
+	/// Rules that are drawn as a checklist, with whether the password follows them.
+	pub rules: Vec<(String, bool)>,
+}
+
+impl Strength {
+	/// Score the password by its length and the classes of characters it uses.
+	///
+	/// The rules are at least 8 characters, lowercase and uppercase letters, digits and symbols,
+	/// and every rule that is followed adds to the score.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::password::Strength;
+	///
+	/// let strength = Strength::check("correct horse");
+	/// assert_eq!(strength.score, 1);
+	/// ```
+	pub fn check(password: &str) -> Strength {
+		let rules = [
+			("at least 8 characters", password.chars().count() >= 8),
+			("lowercase letter", password.chars().any(char::is_lowercase)),
+			("uppercase letter", password.chars().any(char::is_uppercase)),
+			("digit", password.chars().any(|c| c.is_ascii_digit())),
+			(
+				"symbol",
+				password
+					.chars()
+					.any(|c| !c.is_alphanumeric() && !c.is_whitespace()),
+			),
+		];
+
+		let passed = rules.iter().filter(|(_, passed)| *passed).count();
+		Strength {
+			score: passed.saturating_sub(1).min(4) as u8,
+			rules: rules
+				.into_iter()
+				.map(|(rule, passed)| (rule.to_owned(), passed))
+				.collect(),
+		}
+	}
+}
+
+/// Stage of the prompt.
+enum Stage {
+	/// Entering the password.
+	Enter,
+	/// Repeating the entered password.
+	Repeat(String),
+}
+
+impl<M: Display> Password<M> {
+	/// Creates a new `Password` struct.
+	///
+	/// Has a shorthand version in [`password()`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{password, password::Password};
+	///
+	/// // these two are equivalent
+	/// let question = Password::new("message");
+	/// let question = password("message");
+	/// ```
+	pub fn new(message: M) -> Self {
+		Password {
+			message,
+			extra: None,
+			help: None,
+			confirm: None,
+			validate: None,
+			max_attempts: None,
+			strength: None,
+			mask: Mask::default(),
+			submitted: Submitted::default(),
+			cancel: None,
+			cancel_return: None,
+			on_render: None,
+			context: Context::default(),
+			on_submit: None,
+		}
+	}
+
+	/// Ask for the password a second time with the `message`, and start over if they don't match.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password").confirm("repeat password").interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn confirm<S: ToString>(&mut self, message: S) -> &mut Self {
+		self.confirm = Some(message.to_string());
+		self
+	}
+
+	/// Specify a validation function.
+	///
+	/// On a successful validation, return `Ok(())` from the closure,
+	/// and on an unsuccessful validation return an `Err` with the error message.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	/// # use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password")
+	///     .validate(|x| {
+	///         if x.is_empty() {
+	///             Err(Cow::Borrowed("password is required"))
+	///         } else {
+	///             Ok(())
+	///         }
+	///     })
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validate<F>(&mut self, mut validate: F) -> &mut Self
+	where
+		F: FnMut(&str) -> Result<(), Cow<'static, str>> + Send + 'static,
+	{
+		let validate = Box::new(move |value: &str, _: &Attempt| validate(value));
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Like [`Password::validate()`], but the function can keep state between the submissions,
+	/// and gets the [`Attempt`] that is validated.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	/// use std::borrow::Cow;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password")
+	///     .validate_with(|value, attempt| {
+	///         if value.len() >= 8 {
+	///             Ok(())
+	///         } else if attempt.number >= 3 {
+	///             Err(Cow::Owned(format!("attempt {}: use at least 8 characters", attempt.number)))
+	///         } else {
+	///             Err(Cow::Borrowed("too short"))
+	///         }
+	///     })
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validate_with<F>(&mut self, validate: F) -> &mut Self
+	where
+		F: FnMut(&str, &Attempt) -> Result<(), Cow<'static, str>> + Send + 'static,
+	{
+		let validate = Box::new(validate);
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Specify a [`Validator`], like [`Password::validate()`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{
+	///     password,
+	///     validate::{self, Validator},
+	/// };
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password")
+	///     .validator(validate::min_len(12))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn validator<V: Validator + Send + 'static>(&mut self, validator: V) -> &mut Self {
+		let validate = Box::new(move |value: &str, _: &Attempt| validator.validate(value));
+		self.validate = Some(Mutex::new(validate));
+		self
+	}
+
+	/// Abort the prompt with [`ClackError::TooManyAttempts`] after `max` rejected submissions,
+	/// instead of asking again.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{error::ClackError, password};
+	///
+	/// let answer = password("password").max_attempts(3).interact();
+	/// if let Err(ClackError::TooManyAttempts { last_error }) = answer {
+	///     eprintln!("giving up: {}", last_error);
+	/// }
+	/// ```
+	pub fn max_attempts(&mut self, max: usize) -> &mut Self {
+		self.max_attempts = Some(max);
+		self
+	}
+
+	fn do_validate(&self, input: &str, attempt: &Attempt) -> Result<(), Cow<'static, str>> {
+		if let Some(validate) = &self.validate {
+			let mut validate = validate.lock().unwrap_or_else(PoisonError::into_inner);
+			validate(input, attempt)
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Draw a strength meter and a checklist of rules below the password while it is entered,
+	/// scored by the `scorer`.
+	///
+	/// [`Strength::check()`] scores the password by its length and classes of characters,
+	/// other scorers can be wired in the same way.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{password, password::Strength};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password").strength(Strength::check).interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn strength<F>(&mut self, scorer: F) -> &mut Self
+	where
+		F: Fn(&str) -> Strength + Send + Sync + 'static,
+	{
+		self.strength = Some(Arc::new(scorer));
+		self
+	}
+
+	/// Change how the password is drawn while it is typed.
+	///
+	/// Default: [`Mask::PerChar`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{password, password::Mask};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password").mask(Mask::Fixed(8)).interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn mask(&mut self, mask: Mask) -> &mut Self {
+		self.mask = mask;
+		self
+	}
+
+	/// Change what is drawn in place of the password after it is submitted.
+	///
+	/// Default: [`Submitted::Mask`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{password, password::Submitted};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("api key").submitted(Submitted::Last(4)).interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn submitted(&mut self, submitted: Submitted) -> &mut Self {
+		self.submitted = submitted;
+		self
+	}
+
+	/// Add secondary lines below the message, drawn dimmed while the prompt is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password")
+	///     .message_extra("used to encrypt the backup")
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn message_extra<S: ToString>(&mut self, extra: S) -> &mut Self {
+		self.extra = Some(extra.to_string());
+		self
+	}
+
+	/// Show a dimmed help line at the end of the prompt while it is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password").help("ctrl+u to clear").interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn help<S: ToString>(&mut self, help: S) -> &mut Self {
+		self.help = Some(help.to_string());
+		self
+	}
+
+	/// Call `on_render` with the lines of every frame that is drawn, e.g. to record the prompt in tests.
+	///
+	/// The line that is typed is drawn by the line editor, so it isn't part of the frames.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	/// use std::sync::{Arc, Mutex};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let frames = Arc::new(Mutex::new(vec![]));
+	/// let recorded = Arc::clone(&frames);
+	/// let answer = password("password")
+	///     .on_render(move |lines| recorded.lock().unwrap().push(lines.to_vec()))
+	///     .interact()?;
+	/// println!("{} frames", frames.lock().unwrap().len());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_render<F>(&mut self, on_render: F) -> &mut Self
+	where
+		F: Fn(&[String]) + Send + Sync + 'static,
+	{
+		self.on_render = Some(Arc::new(on_render));
+		self
+	}
+
+	/// Draw the prompt as part of a [`Session`](crate::session::Session),
+	/// with the [`Context`] the session passes to [`Session::prompt()`](crate::session::Session::prompt()).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{password, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.steps(1);
+	///
+	/// let answer = session.prompt("password", |cx| password("password").context(cx).interact())?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn context(&mut self, cx: &Context) -> &mut Self {
+		self.context = cx.clone();
+		self
+	}
+
+	/// Call `on_submit` with the answer when the prompt is submitted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password")
+	///     .on_submit(|_| println!("password entered"))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_submit<F>(&mut self, on_submit: F) -> &mut Self
+	where
+		F: Fn(&str) + Send + Sync + 'static,
+	{
+		self.on_submit = Some(Arc::new(on_submit));
+		self
+	}
+
+	/// Specify function to call on cancel.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{cancel, password};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password").cancel(do_cancel).interact()?;
+	/// # Ok(())
+	/// # }
+	///
+	/// fn do_cancel() {
+	///     cancel!("operation cancelled");
+	///     std::process::exit(1);
+	/// }
+	/// ```
+	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn() + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(move |_: &str| cancel());
+		self.cancel = Some(cancel);
+		self
+	}
+
+	/// Like [`Password::cancel()`], but the function gets the password that was typed so far.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password")
+	///     .cancel_with(|typed| eprintln!("cancelled after {} characters", typed.chars().count()))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cancel_with<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn(&str) + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(cancel);
+		self.cancel = Some(cancel);
+		self
+	}
+
+	/// Return the password of the `cancel_return` function instead of an `Err` when the prompt is cancelled.
+	///
+	/// The password isn't validated or confirmed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// // ctrl+c skips the passphrase
+	/// let passphrase = password("passphrase")
+	///     .on_cancel_return(String::new)
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_cancel_return<F>(&mut self, cancel_return: F) -> &mut Self
+	where
+		F: Fn() -> String + Send + Sync + 'static,
+	{
+		self.cancel_return = Some(Arc::new(cancel_return));
+		self
+	}
+
+	/// Wait for the user to submit a password.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = password("password").interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn interact(&self) -> Result<String, ClackError> {
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		r.hide_cursor();
+		r.keys(&[("enter", "submit"), ("ctrl+u", "clear")]);
+
+		let mut value = String::new();
+		let mut stage = Stage::Enter;
+		let mut error: Option<Cow<'static, str>> = None;
+		let mut attempt = Attempt::default();
+		self.draw(&mut r, &stage, &value, error.as_deref());
+
+		terminal::enable_raw_mode()?;
+
+		loop {
+			frame::flush()?;
+
+			let Some(event) = r.read_event(None)? else {
+				continue;
+			};
+			if let Event::Resize(width, height) = event {
+				r.resize(width, height);
+				self.draw(&mut r, &stage, &value, error.as_deref());
+			}
+
+			if let Event::Key(key) = event {
+				if key.kind == KeyEventKind::Press {
+					match (key.code, key.modifiers) {
+						(KeyCode::Char('c' | 'd'), KeyModifiers::CONTROL) => {
+							terminal::disable_raw_mode()?;
+							self.w_cancel(&mut r);
+							if let Some(cancel) = self.cancel.as_deref() {
+								cancel(&value);
+							}
+
+							if let Some(cancel_return) = self.cancel_return.as_deref() {
+								return Ok(cancel_return());
+							}
+
+							return Err(ClackError::Cancelled);
+						}
+						(KeyCode::Char('u'), KeyModifiers::CONTROL) => value.clear(),
+						(KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+							value.push(c);
+						}
+						(KeyCode::Backspace, _) => {
+							value.pop();
+						}
+						(KeyCode::Enter, _) => match stage {
+							Stage::Enter => {
+								attempt.next();
+								if let Err(err) = self.do_validate(&value, &attempt) {
+									attempt.reject(&err);
+									if attempt.is_last(self.max_attempts) {
+										terminal::disable_raw_mode()?;
+										self.w_abort(&mut r, &err);
+										let last_error = err.into_owned();
+										return Err(ClackError::TooManyAttempts { last_error });
+									}
+
+									error = Some(err);
+								} else if self.confirm.is_some() {
+									stage = Stage::Repeat(std::mem::take(&mut value));
+									error = None;
+								} else {
+									terminal::disable_raw_mode()?;
+									self.w_out(&mut r, &value);
+									return Ok(value);
+								}
+							}
+							Stage::Repeat(ref first) => {
+								if *first == value {
+									terminal::disable_raw_mode()?;
+									self.w_out(&mut r, &value);
+									return Ok(value);
+								}
+
+								let err = "passwords don't match";
+								attempt.reject(err);
+								if attempt.is_last(self.max_attempts) {
+									terminal::disable_raw_mode()?;
+									self.w_abort(&mut r, err);
+									let last_error = err.to_owned();
+									return Err(ClackError::TooManyAttempts { last_error });
+								}
+
+								stage = Stage::Enter;
+								value.clear();
+								error = Some(Cow::Borrowed(err));
+							}
+						},
+						_ => {}
+					}
+
+					self.draw(&mut r, &stage, &value, error.as_deref());
+				}
+			}
+		}
+	}
+
+	/// Draw the prompt as if the `value` was submitted, without waiting for the user.
+	///
+	/// Useful to show the answers of an earlier run as static lines before the first live prompt.
+	/// The [`on_submit`](Password::on_submit()) hook isn't called,
+	/// and the answer isn't added to the [transcript](crate::transcript) or the [`json_mode()`](crate::json_mode) summary.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::password;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// password("token").render_submitted("hunter2");
+	/// let answer = password("password").interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn render_submitted(&self, value: &str) {
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		self.w_submitted(&mut r, value, true);
+	}
+}
+
+impl<M: Display> Password<M> {
+	/// The masked value.
+	fn fmt_mask(&self, value: &str) -> String {
+		match self.mask {
+			Mask::PerChar => chars::MASK.repeat(value.chars().count()),
+			Mask::Fixed(len) if !value.is_empty() => chars::MASK.repeat(len),
+			Mask::Fixed(_) | Mask::Hidden => String::new(),
+		}
+	}
+
+	/// What is drawn in place of the submitted value, [`None`] if there is no answer line.
+	fn fmt_submitted(&self, value: &str) -> Option<String> {
+		match self.submitted {
+			Submitted::Mask => Some(self.fmt_mask(value)),
+			Submitted::Hidden => Some("<hidden>".into()),
+			Submitted::Last(last) => {
+				let len = value.chars().count();
+				let skip = if len > last { len - last } else { len };
+				let last = value.chars().skip(skip).collect::<String>();
+				Some(format!("{}{}", *chars::ELLIPSIS, last))
+			}
+			Submitted::Nothing => None,
+		}
+	}
+
+	/// Draw the prompt, in yellow if there is an `error`.
+	fn draw(&self, r: &mut Renderer, stage: &Stage, value: &str, error: Option<&str>) {
+		let line = format!("{}{}", self.fmt_mask(value), " ".reversed());
+
+		let mut lines = vec![chars::BAR.to_string()];
+		let bar = if error.is_some() {
+			lines.extend(r.message(
+				(*chars::STEP_ERROR).yellow(),
+				(*chars::BAR).yellow(),
+				&self.message,
+				self.extra.as_deref(),
+			));
+			(*chars::BAR).yellow().to_string()
+		} else {
+			let step = (*chars::STEP_ACTIVE).cyan();
+			let bar = (*chars::BAR).cyan();
+			let message = match (stage, self.confirm.as_deref()) {
+				(Stage::Repeat(_), Some(confirm)) => r.message(step, bar, &confirm, None),
+				_ => r.message(step, bar, &self.message, self.extra.as_deref()),
+			};
+
+			lines.extend(message);
+			(*chars::BAR).cyan().to_string()
+		};
+
+		let row = lines.len();
+		lines.push(format!("{}  {}", bar, line));
+
+		if let (Stage::Enter, Some(strength)) = (stage, self.strength.as_deref()) {
+			let strength = strength(value);
+			let meter = fmt_strength(&strength);
+			lines.extend(meter.iter().map(|line| format!("{}  {}", bar, line)));
+		}
+
+		match error {
+			Some(error) => lines.push(format!(
+				"{}  {}",
+				(*chars::BAR_END).yellow(),
+				error.yellow()
+			)),
+			None => lines.push(render::end(self.help.as_deref())),
+		}
+
+		r.draw(lines, row);
+	}
+
+	fn w_out(&self, r: &mut Renderer, value: &str) {
+		self.w_submitted(r, value, false);
+
+		if let Some(on_submit) = self.on_submit.as_deref() {
+			on_submit(value);
+		}
+	}
+
+	fn w_submitted(&self, r: &mut Renderer, value: &str, preview: bool) {
+		let submitted = self.fmt_submitted(value);
+		let answer = submitted.as_deref().unwrap_or_default();
+		if preview {
+			transcript::preview(&self.message, answer);
+		} else {
+			transcript::prompt(&self.message, Some(answer));
+		}
+
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_SUBMIT).green();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		if let Some(submitted) = submitted {
+			lines.push(format!("{}  {}", *chars::BAR, submitted.dimmed()));
+		}
+
+		r.finish(lines);
+	}
+
+	/// Draw the prompt as aborted after too many attempts, with the last `error`.
+	fn w_abort(&self, r: &mut Renderer, error: &str) {
+		transcript::prompt(&self.message, None);
+
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_CANCEL).red();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!("{}  {}", *chars::BAR, error.red()));
+
+		r.finish(lines);
+	}
+
+	fn w_cancel(&self, r: &mut Renderer) {
+		transcript::prompt(&self.message, None);
+
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_CANCEL).red();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!(
+			"{}  {}",
+			*chars::BAR,
+			"cancelled".strikethrough().dimmed()
+		));
+
+		r.finish(lines);
+	}
+}
+
+/// Lines of the strength meter, followed by the checklist of rules.
+fn fmt_strength(strength: &Strength) -> Vec<String> {
+	let score = strength.score.min(4);
+	let (label, color) = match score {
+		0 => ("very weak", AnsiColors::Red),
+		1 => ("weak", AnsiColors::Red),
+		2 => ("fair", AnsiColors::Yellow),
+		3 => ("good", AnsiColors::Green),
+		_ => ("strong", AnsiColors::Green),
+	};
+
+	let filled = chars::METER.repeat(score as usize * 2 + 2);
+	let empty = chars::METER.repeat(8 - score as usize * 2);
+	let meter = format!(
+		"{}{}  {}",
+		filled.color(color),
+		empty.dimmed(),
+		label.color(color)
+	);
+
+	let mut lines = vec![meter];
+	lines.extend(strength.rules.iter().map(|(rule, passed)| {
+		if *passed {
+			format!("{} {}", (*chars::CHECK).green(), rule.dimmed())
+		} else {
+			format!("{} {}", (*chars::CROSS).dimmed(), rule.dimmed())
+		}
+	}));
+
+	lines
+}
+
+/// Shorthand for [`Password::new()`]
+pub fn password<M: Display>(message: M) -> Password<M> {
+	Password::new(message)
+}
@@ -0,0 +1,497 @@
+//! Shared renderer for the prompt blocks
+
+use super::{
+	debug,
+	frame::{self, out, Out},
+	markup,
+	session::Context,
+	summary, text,
+	token::CancelToken,
+};
+use crate::style::chars;
+use crossterm::{
+	cursor,
+	event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+	queue, terminal,
+};
+use owo_colors::OwoColorize;
+use std::{
+	fmt::Display,
+	io,
+	sync::{Arc, Mutex, PoisonError},
+	time::{Duration, Instant},
+};
+
+pub(super) type RenderFn = dyn Fn(&[String]) + Send + Sync;
+
+/// How often the event loops of the prompts tick to redraw their animations, like a countdown.
+pub(super) const TICK: Duration = Duration::from_millis(100);
+
+/// Keeps the terminal in raw mode until it is dropped,
+/// so it is left again when the prompt returns early with an error.
+pub(super) struct RawMode(());
+
+impl RawMode {
+	/// Enter raw mode.
+	pub fn enable() -> io::Result<RawMode> {
+		terminal::enable_raw_mode()?;
+		Ok(RawMode(()))
+	}
+}
+
+impl Drop for RawMode {
+	fn drop(&mut self) {
+		let _ = terminal::disable_raw_mode();
+	}
+}
+
+/// The `message: answer` line of the last prompt, drawn instead of the prompt once it is finished in quiet mode.
+static ANSWER: Mutex<Option<String>> = Mutex::new(None);
+
+/// Remember the `answer` to the prompt with the `message`, or that it was cancelled if it is [`None`],
+/// to draw it as a single line in quiet mode.
+pub(super) fn answered(message: &str, answer: Option<&str>) {
+	if frame::is_quiet() {
+		let message = message.lines().next().unwrap_or_default();
+		let line = format!("{}: {}", message, answer.unwrap_or("cancelled"));
+		*ANSWER.lock().unwrap_or_else(PoisonError::into_inner) = Some(line);
+	}
+}
+
+/// Take the `message: answer` line of the last prompt.
+pub(super) fn take_answer() -> Option<String> {
+	ANSWER.lock().unwrap_or_else(PoisonError::into_inner).take()
+}
+
+/// Lines logged while a prompt is active, or [`None`] if no prompt is active.
+static QUEUE: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Write a message with the `step` in front of it between the prompts, like the [`info!`](crate::info!) message.
+///
+/// While a prompt is active the message is queued and written after it is finished,
+/// so it doesn't end up in the middle of the prompt.
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub(super) fn log<S: Display>(step: S, message: &str) {
+	super::transcript::message("log", message);
+
+	let mut message = message.lines();
+
+	let mut lines = vec![];
+	if !frame::is_quiet() {
+		lines.push(chars::BAR.to_string());
+	}
+	lines.push(format!("{}  {}", step, message.next().unwrap_or_default()));
+	lines.extend(message.map(|line| format!("{}  {}", *chars::BAR, line)));
+
+	let mut queue = QUEUE.lock().unwrap_or_else(PoisonError::into_inner);
+	match queue.as_mut() {
+		Some(queue) => queue.extend(lines),
+		None => {
+			for line in lines {
+				frame::line(line);
+			}
+		}
+	}
+}
+
+/// Draws a prompt block line by line, rewriting only the lines that changed since the last frame.
+///
+/// The block starts at the line the cursor is on when the renderer is created.
+pub(super) struct Renderer {
+	/// Lines of the last frame.
+	lines: Vec<String>,
+	/// Whether the next frame is drawn in full, because the terminal may have reflowed the last one.
+	reflowed: bool,
+	/// Amount of lines the block takes up on the screen.
+	height: usize,
+	/// Row of the cursor, relative to the first line of the block.
+	row: usize,
+	/// Whether the cursor is hidden while rendering.
+	hidden: bool,
+	/// Cached terminal size, updated from resize events.
+	size: Option<(u16, u16)>,
+	/// Whether the prompt lists its key bindings in a footer.
+	show_footer: bool,
+	/// Key bindings listed below the block, if the footer is enabled.
+	footer: Option<Vec<(String, String)>>,
+	/// Whether the footer was hidden with `?`.
+	footer_hidden: bool,
+	/// Whether lines are logged to the queue while the block is drawn.
+	active: bool,
+	/// Called with the lines of every frame.
+	on_render: Option<Arc<RenderFn>>,
+	/// Current step and total amount of steps, if the prompt is numbered.
+	step: Option<(usize, usize)>,
+	/// Token the prompt is cancelled with.
+	token: Option<CancelToken>,
+}
+
+impl Renderer {
+	pub fn new() -> Self {
+		Renderer {
+			lines: vec![],
+			reflowed: false,
+			height: 1,
+			row: 0,
+			hidden: false,
+			size: terminal::size()
+				.ok()
+				.filter(|&(width, height)| width > 0 && height > 0),
+			show_footer: false,
+			footer: None,
+			footer_hidden: false,
+			active: false,
+			on_render: None,
+			step: None,
+			token: None,
+		}
+	}
+
+	/// Take the step counter, whether to show the footer and the cancel token from the [`Context`]
+	/// of the session the prompt is asked in.
+	pub fn context(&mut self, cx: &Context) {
+		self.step = cx.step();
+		self.show_footer = cx.key_footer();
+		self.token = cx.token().cloned();
+	}
+
+	/// Wait for the next terminal event, or return [`None`] when the `tick` passes without one,
+	/// so the prompt can redraw its animations.
+	///
+	/// Without a `tick` it waits until there is an event.
+	///
+	/// Once the token of the prompt is cancelled, it returns ctrl+c,
+	/// so the prompt is cancelled like the user cancelled it.
+	pub fn read_event(&self, tick: Option<Duration>) -> io::Result<Option<Event>> {
+		let Some(token) = self.token.as_ref() else {
+			return match tick {
+				Some(tick) if !event::poll(tick)? => Ok(None),
+				_ => event::read().map(Some),
+			};
+		};
+
+		let started = Instant::now();
+		loop {
+			if token.is_cancelled() {
+				let key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+				return Ok(Some(Event::Key(key)));
+			}
+
+			let left = tick.map(|tick| tick.saturating_sub(started.elapsed()));
+			if left.is_some_and(|left| left.is_zero()) {
+				return Ok(None);
+			}
+
+			if event::poll(left.map_or(TICK, |left| left.min(TICK)))? {
+				return event::read().map(Some);
+			}
+		}
+	}
+
+	/// Lines of the prompt message, with the step counter in front of it if the prompt is numbered,
+	/// see [`message()`].
+	pub fn message<S: Display, B: Display, M: Display>(
+		&self,
+		step: S,
+		bar: B,
+		message: &M,
+		extra: Option<&str>,
+	) -> Vec<String> {
+		numbered(step, bar, message, extra, self.step)
+	}
+
+	/// Call `on_render` with the lines of every frame, until the renderer is dropped.
+	pub fn on_render(&mut self, on_render: Option<&Arc<RenderFn>>) {
+		self.on_render = on_render.cloned();
+	}
+
+	/// Set the key bindings of the prompt, listed in the footer below the block.
+	pub fn keys(&mut self, keys: &[(&str, &str)]) {
+		if !self.show_footer {
+			return;
+		}
+
+		let keys = keys
+			.iter()
+			.map(|(key, action)| (key.to_string(), action.to_string()))
+			.collect();
+		self.footer = Some(keys);
+	}
+
+	/// Amount of lines the footer takes up when it is enabled.
+	pub fn footer_height(&self) -> u16 {
+		self.show_footer.into()
+	}
+
+	/// Whether there is a footer that can be toggled.
+	pub fn has_footer(&self) -> bool {
+		self.footer.is_some()
+	}
+
+	/// Hide or show the footer again, which is drawn with the next frame.
+	pub fn toggle_footer(&mut self) {
+		self.footer_hidden = !self.footer_hidden;
+	}
+
+	/// Width of the terminal, if known.
+	pub fn width(&self) -> Option<usize> {
+		self.size.map(|(width, _)| width as usize)
+	}
+
+	/// Height of the terminal, if known.
+	pub fn height(&self) -> Option<u16> {
+		self.size.map(|(_, height)| height)
+	}
+
+	/// Update the cached terminal size after a resize event.
+	///
+	/// The terminal may have reflowed the block, so the next frame is drawn in full,
+	/// and the lines of the last frame that are left over below it are cleared.
+	pub fn resize(&mut self, width: u16, height: u16) {
+		debug::log(|| format!("resize to {}x{}", width, height));
+		self.size = Some((width, height));
+		self.reflowed = true;
+	}
+
+	/// Hide the cursor until the block is finished.
+	pub fn hide_cursor(&mut self) {
+		if !self.hidden {
+			debug::log(|| "hide cursor".into());
+		}
+
+		let _ = queue!(Out, cursor::Hide);
+		self.hidden = true;
+	}
+
+	/// Draw a frame and move the cursor to the start of the line `row`.
+	pub fn draw(&mut self, mut lines: Vec<String>, row: usize) {
+		if let Some(footer) = self.fmt_footer() {
+			lines.push(footer);
+		}
+
+		self.draw_lines(lines, row);
+	}
+
+	/// Format the footer, leaving out the keys that don't fit on the line.
+	fn fmt_footer(&self) -> Option<String> {
+		let keys = self.footer.as_ref().filter(|_| !self.footer_hidden)?;
+		let width = self.width().unwrap_or(usize::MAX);
+
+		let mut line = String::new();
+		let mut used = 0;
+		for (key, action) in keys {
+			let len = 3 + text::width(key) + 1 + text::width(action);
+			if used + len > width {
+				break;
+			}
+
+			line = format!("{}   {} {}", line, key, action.dimmed());
+			used += len;
+		}
+
+		Some(line)
+	}
+
+	fn draw_lines(&mut self, lines: Vec<String>, row: usize) {
+		if let Some(on_render) = self.on_render.as_deref() {
+			on_render(&lines);
+		}
+
+		let lines = match self.width().filter(|_| frame::is_rtl()) {
+			Some(width) => lines.iter().map(|line| text::mirror(line, width)).collect(),
+			None => lines,
+		};
+
+		if !self.active {
+			*QUEUE.lock().unwrap_or_else(PoisonError::into_inner) = Some(vec![]);
+			self.active = true;
+		}
+
+		debug::log(|| {
+			let reflowed = if self.reflowed { ", in full" } else { "" };
+			format!(
+				"draw {} lines, cursor at row {}{}",
+				lines.len(),
+				self.row,
+				reflowed
+			)
+		});
+
+		let len = usize::max(self.lines.len(), lines.len());
+		for i in 0..len {
+			let line = lines.get(i);
+			if !self.reflowed && self.lines.get(i) == line {
+				continue;
+			}
+
+			self.goto(i);
+			frame::clear_line();
+			match line {
+				Some(line) => {
+					debug::log(|| format!("write row {}: {:?}", i, line));
+					out!("{}", line);
+				}
+				None => debug::log(|| format!("clear row {}", i)),
+			}
+		}
+
+		self.lines = lines;
+		self.reflowed = false;
+		self.goto(row);
+	}
+
+	/// Draw the last frame, move the cursor below it and write it to stdout.
+	///
+	/// In [`json_mode()`](super::json_mode) the block is cleared instead, the answer ends up in the summary.
+	/// In [`quiet_mode()`](super::quiet_mode) the block of a prompt is replaced with its `message: answer` line.
+	pub fn finish(&mut self, lines: Vec<String>) {
+		debug::log(|| "finish".into());
+
+		let answer = take_answer();
+		if summary::is_enabled() {
+			self.draw_lines(vec![], 0);
+		} else if let Some(answer) = answer {
+			self.draw_lines(vec![answer], 1);
+		} else {
+			let row = lines.len();
+			self.draw_lines(lines, row);
+		}
+
+		self.show_cursor();
+		let _ = frame::flush();
+		self.release();
+
+		let (on_render, step, show_footer) = (self.on_render.take(), self.step, self.show_footer);
+		let token = self.token.take();
+		*self = Renderer::new();
+		self.on_render = on_render;
+		self.step = step;
+		self.show_footer = show_footer;
+		self.token = token;
+	}
+
+	/// Tell the renderer that the cursor was moved to the start of the next line from elsewhere,
+	/// e.g. by `rustyline` after submitting a line.
+	pub fn moved_down(&mut self) {
+		debug::log(|| format!("moved down to row {} from elsewhere", self.row + 1));
+		self.row += 1;
+		self.height = self.height.max(self.row + 1);
+	}
+
+	/// Write the lines that were logged while the block was drawn.
+	fn release(&mut self) {
+		if self.active {
+			let mut queue = QUEUE.lock().unwrap_or_else(PoisonError::into_inner);
+			for line in queue.take().into_iter().flatten() {
+				frame::line(line);
+			}
+
+			self.active = false;
+		}
+	}
+
+	fn show_cursor(&mut self) {
+		if self.hidden {
+			debug::log(|| "show cursor".into());
+			let _ = queue!(Out, cursor::Show);
+			self.hidden = false;
+		}
+	}
+
+	fn goto(&mut self, row: usize) {
+		let last = self.height - 1;
+		if row != self.row {
+			debug::log(|| format!("move to row {}", row));
+		}
+
+		if row < self.row {
+			let _ = queue!(Out, cursor::MoveUp((self.row - row) as u16));
+		} else if row > self.row {
+			let down = row.min(last).saturating_sub(self.row);
+			if down > 0 {
+				let _ = queue!(Out, cursor::MoveDown(down as u16));
+			}
+
+			for _ in last..row {
+				debug::log(|| "write newline".into());
+				out!("\r\n");
+			}
+			self.height = self.height.max(row + 1);
+		}
+
+		let _ = queue!(Out, cursor::MoveToColumn(0));
+		self.row = row;
+	}
+}
+
+impl Drop for Renderer {
+	fn drop(&mut self) {
+		self.show_cursor();
+		let _ = frame::flush();
+		self.release();
+	}
+}
+
+/// Lines of a prompt message, with the `step` in front of the first line
+/// and the `bar` in front of every other line.
+///
+/// The `extra` lines are drawn dimmed below the message.
+pub(super) fn message<S: Display, B: Display, M: Display>(
+	step: S,
+	bar: B,
+	message: &M,
+	extra: Option<&str>,
+) -> Vec<String> {
+	numbered(step, bar, message, extra, None)
+}
+
+/// Like [`message()`], with the `counter` of a numbered prompt like `3/7` in front of the message.
+pub(super) fn numbered<S: Display, B: Display, M: Display>(
+	step: S,
+	bar: B,
+	message: &M,
+	extra: Option<&str>,
+	counter: Option<(usize, usize)>,
+) -> Vec<String> {
+	let message = message.to_string();
+	let mut lines = message.lines();
+
+	let first = markup::render(lines.next().unwrap_or_default(), false);
+	let first = text::isolate(first.into()).into_owned();
+	let first = match counter {
+		Some((step, total)) => format!("{} {}", format!("{}/{}", step, total).dimmed(), first),
+		None => first,
+	};
+
+	let mut message = vec![format!("{}  {}", step, first)];
+	message.extend(lines.map(|line| {
+		let line = markup::render(line, false);
+		format!("{}  {}", bar, text::isolate(line.into()))
+	}));
+
+	if let Some(extra) = extra {
+		let extra = extra
+			.lines()
+			.map(|line| format!("{}  {}", bar, markup::render(line, true)));
+		message.extend(extra);
+	}
+
+	message
+}
+
+/// Amount of lines a prompt message takes up, with the `extra` lines below it, see [`message()`].
+pub(super) fn message_height<M: Display>(message: &M, extra: Option<&str>) -> u16 {
+	self::message("", "", message, extra).len() as u16
+}
+
+/// End line of an active prompt, with the `help` drawn dimmed next to it.
+pub(super) fn end(help: Option<&str>) -> String {
+	match help {
+		Some(help) => format!(
+			"{}  {}",
+			(*chars::BAR_END).cyan(),
+			markup::render(help, true)
+		),
+		None => (*chars::BAR_END).cyan().to_string(),
+	}
+}
@@ -0,0 +1,408 @@
+//! Runner for prompts that are put together at runtime
+
+use crate::error::ClackError;
+use std::any::Any;
+
+/// Converts an answer to JSON, if it is of the right type.
+#[cfg(feature = "serde")]
+type SerializeFn = fn(&dyn Any) -> Option<serde_json::Result<serde_json::Value>>;
+
+#[cfg(feature = "serde")]
+fn to_value<T: serde::Serialize + 'static>(
+	answer: &dyn Any,
+) -> Option<serde_json::Result<serde_json::Value>> {
+	answer.downcast_ref::<T>().map(serde_json::to_value)
+}
+
+/// Answer types that can be serialized without calling [`Answers::serializable()`].
+#[cfg(feature = "serde")]
+const SERIALIZABLE: &[SerializeFn] = &[
+	to_value::<String>,
+	to_value::<&'static str>,
+	to_value::<bool>,
+	to_value::<char>,
+	to_value::<i32>,
+	to_value::<i64>,
+	to_value::<u32>,
+	to_value::<u64>,
+	to_value::<usize>,
+	to_value::<f32>,
+	to_value::<f64>,
+	to_value::<Option<String>>,
+	to_value::<Vec<String>>,
+	to_value::<Vec<&'static str>>,
+	to_value::<Vec<i32>>,
+	to_value::<Vec<i64>>,
+	to_value::<Vec<u32>>,
+	to_value::<Vec<u64>>,
+	to_value::<Vec<usize>>,
+	to_value::<Vec<f64>>,
+];
+
+/// `AnyPrompt` trait.
+///
+/// A prompt whose answer is recorded under a key, with the type of the answer erased,
+/// so prompts with different answer types can be put in one `Vec<Box<dyn AnyPrompt>>` and [`run()`].
+///
+/// It is implemented for the [`Step`] returned by [`step()`].
+pub trait AnyPrompt {
+	/// Key the answer is recorded under.
+	fn key(&self) -> &str;
+
+	/// Ask the prompt.
+	///
+	/// # Errors
+	///
+	/// Returns the error of the prompt, like [`ClackError::Cancelled`].
+	fn interact_any(&mut self) -> Result<Box<dyn Any>, ClackError>;
+
+	/// Whether the prompt is asked, given the `answers` of the prompts before it.
+	///
+	/// Default: `true`
+	fn is_asked(&self, answers: &Answers) -> bool {
+		let _ = answers;
+		true
+	}
+}
+
+impl dyn AnyPrompt {
+	/// Only ask the prompt if `when` returns `true` for the answers of the prompts before it.
+	///
+	/// A skipped prompt isn't drawn, and has no answer.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, password, runner};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answers = runner::run(vec![
+	///     runner::step("publish", || confirm("publish to the registry?").interact()),
+	///     runner::step("token", || password("registry token").interact())
+	///         .when(|answers| answers.get::<bool>("publish") == Some(&true)),
+	/// ])?;
+	///
+	/// let token = answers.get::<String>("token");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn when<F>(self: Box<Self>, when: F) -> Box<dyn AnyPrompt>
+	where
+		F: Fn(&Answers) -> bool + 'static,
+	{
+		Box::new(When {
+			prompt: self,
+			when: Box::new(when),
+		})
+	}
+}
+
+/// A prompt that is only asked if the condition is met, see [`<dyn AnyPrompt>::when()`](AnyPrompt#method.when).
+struct When {
+	prompt: Box<dyn AnyPrompt>,
+	when: Box<dyn Fn(&Answers) -> bool>,
+}
+
+impl AnyPrompt for When {
+	fn key(&self) -> &str {
+		self.prompt.key()
+	}
+
+	fn interact_any(&mut self) -> Result<Box<dyn Any>, ClackError> {
+		self.prompt.interact_any()
+	}
+
+	fn is_asked(&self, answers: &Answers) -> bool {
+		(self.when)(answers) && self.prompt.is_asked(answers)
+	}
+}
+
+/// `Step` struct.
+///
+/// A prompt for the [`run()`] function, created with [`step()`].
+pub struct Step<F> {
+	key: String,
+	prompt: F,
+}
+
+impl<T, F> AnyPrompt for Step<F>
+where
+	T: 'static,
+	F: FnMut() -> Result<T, ClackError>,
+{
+	fn key(&self) -> &str {
+		&self.key
+	}
+
+	fn interact_any(&mut self) -> Result<Box<dyn Any>, ClackError> {
+		let answer = (self.prompt)()?;
+		Ok(Box::new(answer))
+	}
+}
+
+/// Create a boxed [`Step`] that runs the `prompt` and records its answer under the `key`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{confirm, input, runner};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let prompts = vec![
+///     runner::step("name", || input("name").required()),
+///     runner::step("git", || confirm("initialize git?").interact()),
+/// ];
+/// let answers = runner::run(prompts)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn step<T, F>(key: &str, prompt: F) -> Box<dyn AnyPrompt>
+where
+	T: 'static,
+	F: FnMut() -> Result<T, ClackError> + 'static,
+{
+	Box::new(Step {
+		key: key.into(),
+		prompt,
+	})
+}
+
+/// `Answers` struct.
+///
+/// The answers of the prompts that were [`run()`], or asked in a [`Session`](crate::session::Session), by their keys.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{confirm, input, runner};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let answers = runner::run(vec![
+///     runner::step("name", || input("name").required()),
+///     runner::step("git", || confirm("initialize git?").interact()),
+/// ])?;
+///
+/// for (key, answer) in &answers {
+///     if let Some(text) = answer.downcast_ref::<String>() {
+///         println!("{}: {}", key, text);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[cfg_attr(not(feature = "serde"), derive(Default))]
+pub struct Answers {
+	answers: Vec<(String, Box<dyn Any>)>,
+	#[cfg(feature = "serde")]
+	serializable: Vec<SerializeFn>,
+}
+
+#[cfg(feature = "serde")]
+impl Default for Answers {
+	fn default() -> Self {
+		Answers {
+			answers: vec![],
+			#[cfg(feature = "serde")]
+			serializable: SERIALIZABLE.to_vec(),
+		}
+	}
+}
+
+impl Answers {
+	/// Get the answer recorded under the `key`.
+	///
+	/// Returns [`None`] if there is no answer under the `key` or it isn't a `T`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, runner};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answers = runner::run(vec![runner::step("git", || {
+	///     confirm("initialize git?").interact()
+	/// })])?;
+	///
+	/// let git = answers.get::<bool>("git");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn get<T: 'static>(&self, key: &str) -> Option<&T> {
+		self.answers
+			.iter()
+			.find(|(k, _)| k == key)
+			.and_then(|(_, answer)| answer.downcast_ref())
+	}
+
+	/// Whether there is an answer under the `key`.
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.answers.iter().any(|(k, _)| k == key)
+	}
+
+	/// Keys of the answers, in the order the prompts were answered.
+	pub fn keys(&self) -> impl Iterator<Item = &str> {
+		self.answers.iter().map(|(key, _)| key.as_str())
+	}
+
+	/// Keys and answers, in the order the prompts were answered.
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &dyn Any)> {
+		self.answers
+			.iter()
+			.map(|(key, answer)| (key.as_str(), answer.as_ref()))
+	}
+
+	/// Amount of answers.
+	pub fn len(&self) -> usize {
+		self.answers.len()
+	}
+
+	/// Whether there are no answers.
+	pub fn is_empty(&self) -> bool {
+		self.answers.is_empty()
+	}
+
+	/// Record the `answer` under the `key`, replacing an earlier answer with the same key.
+	pub(super) fn insert(&mut self, key: &str, answer: Box<dyn Any>) {
+		self.answers.retain(|(k, _)| k != key);
+		self.answers.push((key.into(), answer));
+	}
+}
+
+impl<'a> IntoIterator for &'a Answers {
+	type Item = (&'a str, &'a dyn Any);
+	type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		Box::new(self.iter())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Answers {
+	/// Allow answers of the type `T` to be serialized.
+	///
+	/// Strings, numbers, bools and vectors of them can be serialized without it.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{runner, select};
+	/// use serde::Serialize;
+	///
+	/// #[derive(Debug, Clone, Serialize)]
+	/// enum Region {
+	///     Europe,
+	///     America,
+	/// }
+	///
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let mut answers = runner::run(vec![runner::step("region", || {
+	///     select("region")
+	///         .option(Region::Europe, "Europe")
+	///         .option(Region::America, "America")
+	///         .interact()
+	/// })])?;
+	///
+	/// answers.serializable::<Region>();
+	/// println!("{}", answers.to_json()?);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn serializable<T: serde::Serialize + 'static>(&mut self) -> &mut Self {
+		self.serializable.push(to_value::<T>);
+		self
+	}
+
+	/// The answers as a JSON object, with their keys.
+	///
+	/// # Errors
+	///
+	/// Returns an error if an answer can't be serialized,
+	/// because its type wasn't made serializable with [`Answers::serializable()`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, input, runner};
+	///
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let answers = runner::run(vec![
+	///     runner::step("name", || input("name").required()),
+	///     runner::step("git", || confirm("initialize git?").interact()),
+	/// ])?;
+	///
+	/// std::fs::write("app.json", answers.to_json()?.to_string())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+		let mut json = serde_json::Map::new();
+		for (key, answer) in &self.answers {
+			let value = self
+				.serializable
+				.iter()
+				.find_map(|to_value| to_value(answer.as_ref()))
+				.unwrap_or_else(|| {
+					let err = format!("the answer to {:?} is not serializable", key);
+					Err(serde::ser::Error::custom(err))
+				})?;
+
+			json.insert(key.clone(), value);
+		}
+
+		Ok(serde_json::Value::Object(json))
+	}
+}
+
+/// Serializes the answers as a map, like [`Answers::to_json()`].
+#[cfg(feature = "serde")]
+impl serde::Serialize for Answers {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let json = self.to_json().map_err(serde::ser::Error::custom)?;
+		json.serialize(serializer)
+	}
+}
+
+/// Ask the `prompts` in order and collect their answers.
+///
+/// Prompts that are skipped with [`when()`](AnyPrompt#method.when) have no answer.
+///
+/// # Errors
+///
+/// Stops at the first prompt that is cancelled or fails, and returns its error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{input, runner};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// // e.g. read from a config file
+/// let fields = ["name", "email", "website"];
+///
+/// let prompts = fields
+///     .iter()
+///     .map(|&field| runner::step(field, move || input(field).interact()))
+///     .collect();
+///
+/// let answers = runner::run(prompts)?;
+/// for field in fields {
+///     println!("{}: {:?}", field, answers.get::<Option<String>>(field));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn run(prompts: Vec<Box<dyn AnyPrompt>>) -> Result<Answers, ClackError> {
+	let mut answers = Answers::default();
+	for mut prompt in prompts {
+		if !prompt.is_asked(&answers) {
+			continue;
+		}
+
+		let answer = prompt.interact_any()?;
+		answers.insert(prompt.key(), answer);
+	}
+
+	Ok(answers)
+}
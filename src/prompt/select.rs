@@ -1,20 +1,31 @@
 //! Select option
 
-use crate::{
-	error::ClackError,
-	style::{ansi, chars},
+use super::{
+	cache::Prefill,
+	frame,
+	input::{CancelReturnFn, FormatFn, SubmitFn},
+	list::{self, CmpFn, EqFn, Icon, Incoming, Loader, Pending, Refresh, Row, Slot},
+	markup,
+	render::{self, RenderFn, Renderer},
+	session::Context,
+	spinner, text, transcript,
 };
+use crate::{error::ClackError, style::chars};
 use crossterm::{
-	cursor,
-	event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
-	execute, terminal,
+	event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+	terminal,
 };
-use owo_colors::OwoColorize;
+use owo_colors::{OwoColorize, Style};
 use std::{
+	cmp::Ordering,
+	error::Error,
 	fmt::Display,
-	io::{stdout, Write},
+	ops::Deref,
+	sync::{Arc, Mutex, PoisonError},
+	time::Instant,
 };
-use unicode_truncate::UnicodeTruncateStr;
+
+const DEFAULT: &str = "(default)";
 
 /// `Select` `Opt` struct
 #[derive(Debug)]
@@ -22,6 +33,8 @@ pub struct Opt<T: Clone, O: Display> {
 	value: T,
 	label: O,
 	hint: Option<String>,
+	style: Option<Style>,
+	icon: Option<Icon>,
 }
 
 impl<T: Clone, O: Display> Opt<T, O> {
@@ -39,6 +52,8 @@ impl<T: Clone, O: Display> Opt<T, O> {
 			value,
 			label,
 			hint: hint.map(|hint| hint.to_string()),
+			style: None,
+			icon: None,
 		}
 	}
 
@@ -68,36 +83,196 @@ impl<T: Clone, O: Display> Opt<T, O> {
 		Opt::new(value, label, Some(hint))
 	}
 
-	fn trunc(&self, hint: usize) -> String {
-		let size = crossterm::terminal::size();
+	/// Style the label, e.g. to draw a destructive option in red.
+	///
+	/// The style is kept when the option isn't focused, with the label dimmed on top of it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::select::Opt;
+	/// use owo_colors::Style;
+	///
+	/// let option = Opt::simple("rm", "delete everything").style(Style::new().red().bold());
+	/// ```
+	pub fn style(mut self, style: Style) -> Self {
+		self.style = Some(style);
+		self
+	}
+
+	/// Draw an `icon` in front of the label, like an emoji.
+	///
+	/// The `ascii` fallback is drawn instead in terminals without unicode support,
+	/// the labels of all options are lined up after the widest icon.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::select::Opt;
+	///
+	/// let option = Opt::simple("rust", "Rust").icon("🦀", "rs");
+	/// ```
+	pub fn icon<I: ToString, A: ToString>(mut self, icon: I, ascii: A) -> Self {
+		self.icon = Some(Icon::new(icon.to_string(), ascii.to_string()));
+		self
+	}
+
+	fn trunc(&self, width: Option<usize>, hint: usize) -> String {
 		let label = format!("{}", self.label);
+		text::fit(&label, width, 5 + hint).into_owned()
+	}
 
-		match size {
-			Ok((width, _height)) => label
-				.unicode_truncate(width as usize - 5 - hint)
-				.0
-				.to_owned(),
-			Err(_) => label,
+	/// Format the icon and the label, leaving `extra` columns for the rest of the line.
+	fn fmt_label(&self, width: Option<usize>, column: usize, extra: usize, dimmed: bool) -> String {
+		let icon = list::fmt_icon(self.icon.as_ref(), column);
+		let label = self.paint(self.trunc(width, extra + text::width(&icon)), dimmed);
+
+		if dimmed {
+			format!("{}{}", icon.dimmed(), label)
+		} else {
+			format!("{}{}", icon, label)
+		}
+	}
+
+	/// Apply the style of the option to the `label`, dimmed on top of it if `dimmed` is `true`.
+	fn paint(&self, label: String, dimmed: bool) -> String {
+		match self.style {
+			Some(style) if dimmed => label.style(style.dimmed()).to_string(),
+			Some(style) => label.style(style).to_string(),
+			None if dimmed => label.dimmed().to_string(),
+			None => label,
 		}
 	}
 
-	fn focus(&self) -> String {
-		let hint_len = self.hint.as_deref().map_or(0, |hint| hint.len() + 3);
-		let label = self.trunc(hint_len);
+	fn focus(&self, width: Option<usize>, column: usize, is_default: bool) -> String {
+		let default_len = if is_default {
+			text::width(DEFAULT) + 1
+		} else {
+			0
+		};
+		let hint = self.hint.as_deref().map(|hint| markup::render(hint, true));
+		let hint = hint
+			.as_deref()
+			.map(|hint| text::fit(hint, width, 8 + default_len));
+		let hint_len = hint.as_deref().map_or(0, |hint| text::width(hint) + 3);
+		let label = self.fmt_label(width, column, hint_len + default_len, false);
+
+		let mut fmt = format!("{} {}", (*chars::RADIO_ACTIVE).green(), label);
+
+		if is_default {
+			fmt = format!("{} {}", fmt, DEFAULT.dimmed());
+		}
+
+		if let Some(hint) = hint {
+			format!("{} {}{}{}", fmt, "(".dimmed(), hint, ")".dimmed())
+		} else {
+			fmt
+		}
+	}
 
-		let fmt = format!("{} {}", (*chars::RADIO_ACTIVE).green(), label);
+	fn unfocus(&self, width: Option<usize>, column: usize, is_default: bool) -> String {
+		let default_len = if is_default {
+			text::width(DEFAULT) + 1
+		} else {
+			0
+		};
+		let label = self.fmt_label(width, column, default_len, true);
+		let fmt = format!("{} {}", (*chars::RADIO_INACTIVE).dimmed(), label);
 
-		if let Some(hint) = &self.hint {
-			let hint = format!("({})", hint);
-			format!("{} {}", fmt, hint.dimmed())
+		if is_default {
+			format!("{} {}", fmt, DEFAULT.dimmed())
 		} else {
 			fmt
 		}
 	}
+}
+
+impl<T: Clone + Display> Opt<T, T> {
+	/// Creates a new `Opt` struct, using the value as the label.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::select::Opt;
+	///
+	/// let option = Opt::auto("value");
+	/// ```
+	pub fn auto(value: T) -> Self {
+		Opt::simple(value.clone(), value)
+	}
+}
+
+impl<T: Clone, O: Display> From<(T, O)> for Opt<T, O> {
+	fn from((value, label): (T, O)) -> Self {
+		Opt::simple(value, label)
+	}
+}
+
+impl<T: Clone, O: Display, S: ToString> From<(T, O, S)> for Opt<T, O> {
+	fn from((value, label, hint): (T, O, S)) -> Self {
+		Opt::hint(value, label, hint)
+	}
+}
+
+/// A submitted option together with its index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection<T> {
+	/// Index of the option, not counting separators or headers
+	pub index: usize,
+	/// Value of the option
+	pub value: T,
+}
+
+/// `Feed` struct
+///
+/// Sends options to a prompt that is loading, returned by [`Select::loading()`]
+/// and [`MultiSelect::loading()`](crate::multi_select::MultiSelect::loading()).
+///
+/// The prompt stops loading once the feed is [done](Feed::done()) or dropped,
+/// so it can be moved to the thread that fetches the options.
+pub struct Feed<P> {
+	incoming: Incoming<P>,
+}
+
+impl<P> Feed<P> {
+	pub(super) fn new() -> (Feed<P>, Incoming<P>) {
+		let pending = Pending {
+			opts: vec![],
+			done: false,
+		};
+		let incoming = Arc::new(Mutex::new(pending));
+
+		let feed = Feed {
+			incoming: Arc::clone(&incoming),
+		};
+		(feed, incoming)
+	}
+
+	/// Send an option, like a `(value, label)` tuple.
+	pub fn push<Q: Into<P>>(&self, opt: Q) {
+		self.extend([opt]);
+	}
 
-	fn unfocus(&self) -> String {
-		let label = self.trunc(0);
-		format!("{} {}", (*chars::RADIO_INACTIVE).dimmed(), label.dimmed())
+	/// Send several options at once.
+	pub fn extend<I, Q>(&self, opts: I)
+	where
+		I: IntoIterator<Item = Q>,
+		Q: Into<P>,
+	{
+		let mut pending = self.incoming.lock().unwrap_or_else(PoisonError::into_inner);
+		pending.opts.extend(opts.into_iter().map(Into::into));
+	}
+
+	/// Tell the prompt that all options were sent.
+	pub fn done(self) {}
+}
+
+impl<P> Drop for Feed<P> {
+	fn drop(&mut self) {
+		self.incoming
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.done = true;
 	}
 }
 
@@ -121,13 +296,86 @@ impl<T: Clone, O: Display> Opt<T, O> {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Interacting doesn't change the builder, so it can be shared between threads and asked again.
+///
+/// ```no_run
+/// use may_clack::select;
+///
+/// fn assert_send_sync<P: Send + Sync>(_: &P) {}
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let mut prompt = select("message");
+/// prompt.option("val1", "value 1").option("val2", "value 2");
+/// assert_send_sync(&prompt);
+///
+/// let first = prompt.interact()?;
+/// let again = prompt.interact()?;
+/// println!("answers {:?} {:?}", first, again);
+/// # Ok(())
+/// # }
+/// ```
 pub struct Select<M: Display, T: Clone, O: Display> {
 	message: M,
+	extra: Option<String>,
+	help: Option<String>,
 	less: bool,
 	less_amt: Option<u16>,
 	less_max: Option<u16>,
-	cancel: Option<Box<dyn Fn()>>,
-	options: Vec<Opt<T, O>>,
+	less_fraction: Option<f32>,
+	cancel: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+	cancel_return: Option<Arc<CancelReturnFn<T>>>,
+	format: Option<Arc<FormatFn<T>>>,
+	on_render: Option<Arc<RenderFn>>,
+	context: Context,
+	on_submit: Option<Arc<SubmitFn<T>>>,
+	options: Vec<Row<Opt<T, O>>>,
+	loader: Loader<Opt<T, O>>,
+	incoming: Option<Incoming<Opt<T, O>>>,
+	refresh: Option<Refresh<T, Opt<T, O>>>,
+	refresh_key: char,
+	dedup: Option<Arc<EqFn<T>>>,
+	sort: Option<Arc<CmpFn<Opt<T, O>>>>,
+	initial: Option<Pick<T>>,
+	cached: Option<Prefill<T>>,
+	default: Option<Pick<T>>,
+	show_default: bool,
+}
+
+/// An option picked by its index or its value, looked up when the prompt starts.
+enum Pick<T> {
+	Index(usize),
+	Value(T, fn(&T, &T) -> bool),
+}
+
+impl<T: Clone> Pick<T> {
+	/// Row index of the picked option, if it is one of the `rows`.
+	fn row<O: Display, S: Deref<Target = Opt<T, O>>>(&self, rows: &[Row<S>]) -> Option<usize> {
+		match self {
+			Pick::Index(idx) => list::nth(rows, *idx),
+			Pick::Value(value, eq) => rows
+				.iter()
+				.position(|row| row.opt().is_some_and(|opt| eq(&opt.value, value))),
+		}
+	}
+}
+
+/// Options of the prompt while it is active, so interacting with it doesn't change the builder.
+struct State<'a, T: Clone, O: Display> {
+	rows: Vec<Row<Slot<'a, Opt<T, O>>>>,
+	/// Row index of the default option.
+	default: Option<usize>,
+	/// When the prompt started loading, until all options arrived through the feed.
+	loading: Option<Instant>,
+}
+
+impl<T: Clone, O: Display> State<'_, T, O> {
+	fn opt(&self, idx: usize) -> &Opt<T, O> {
+		self.rows
+			.get(idx)
+			.and_then(Row::opt)
+			.expect("idx should always be an option")
+	}
 }
 
 impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
@@ -150,11 +398,29 @@ impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
 	pub fn new(message: M) -> Self {
 		Select {
 			message,
+			extra: None,
+			help: None,
 			less: false,
 			less_amt: None,
 			less_max: None,
+			less_fraction: None,
 			cancel: None,
+			cancel_return: None,
+			format: None,
+			on_render: None,
+			context: Context::default(),
+			on_submit: None,
 			options: vec![],
+			loader: None,
+			incoming: None,
+			refresh: None,
+			refresh_key: 'r',
+			dedup: None,
+			sort: None,
+			initial: None,
+			cached: None,
+			default: None,
+			show_default: false,
 		}
 	}
 
@@ -176,7 +442,7 @@ impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
 	/// ```
 	pub fn option(&mut self, value: T, label: O) -> &mut Self {
 		let opt = Opt::new(value, label, None::<String>);
-		self.options.push(opt);
+		self.options.push(Row::Opt(opt));
 		self
 	}
 
@@ -199,10 +465,70 @@ impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
 	/// ```
 	pub fn option_hint<S: ToString>(&mut self, value: T, label: O, hint: S) -> &mut Self {
 		let opt = Opt::new(value, label, Some(hint));
-		self.options.push(opt);
+		self.options.push(Row::Opt(opt));
+		self
+	}
+
+	/// Add an option with an `icon` in front of the label, like an emoji.
+	///
+	/// The icon is left out in terminals without unicode support,
+	/// use [`Opt::icon()`] to draw an ascii fallback instead.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("language")
+	///     .option_icon("🦀", "rust", "Rust")
+	///     .option_icon("🐹", "go", "Go")
+	///     .option_icon("🐍", "python", "Python")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn option_icon<I: ToString>(&mut self, icon: I, value: T, label: O) -> &mut Self {
+		let opt = Opt::simple(value, label).icon(icon, "");
+		self.options.push(Row::Opt(opt));
 		self
 	}
 
+	/// Open the prompt in a loading state, and get a [`Feed`] to send the options through.
+	///
+	/// A spinner row is drawn below the message until the first options arrive,
+	/// and below the options until the feed is done.
+	/// The options can be navigated as soon as they arrive.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	/// use std::thread;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut prompt = select("container");
+	/// let feed = prompt.loading();
+	///
+	/// thread::spawn(move || {
+	///     for name in ["web", "db", "cache"] {
+	///         // slow api call
+	///         feed.push((name, name));
+	///     }
+	/// });
+	///
+	/// let answer = prompt.interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn loading(&mut self) -> Feed<Opt<T, O>> {
+		let (feed, incoming) = Feed::new();
+		self.incoming = Some(incoming);
+		feed
+	}
+
 	/// Add multiple options.
 	///
 	/// # Examples
@@ -219,45 +545,76 @@ impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
 	///
 	/// let answer = select("message").options(opts).interact()?;
 	/// println!("answer {:?}", answer);
+	///
+	/// let opts = [("val1", "label 1"), ("val2", "label 2")];
+	/// let answer = select("message").options(opts).interact()?;
+	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn options(&mut self, options: Vec<Opt<T, O>>) -> &mut Self {
-		self.options = options;
-		self
+	pub fn options<I, P>(&mut self, options: I) -> &mut Self
+	where
+		I: IntoIterator<Item = P>,
+		P: Into<Opt<T, O>>,
+	{
+		self.options.clear();
+		self.extend_options(options)
 	}
 
-	/// Enable paging with the amount of terminal rows.
+	/// Add multiple options, keeping the existing ones.
+	///
+	/// Accepts anything that converts into an [`Opt`], like `(value, label)` or `(value, label, hint)` tuples.
 	///
 	/// # Examples
 	///
 	/// ```no_run
 	/// use may_clack::select;
+	/// use std::collections::BTreeMap;
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let map = BTreeMap::from([("val2", "label 2"), ("val3", "label 3")]);
+	///
 	/// let answer = select("message")
-	///     .option("val 1", "value 1")
-	///     .option("val 2", "value 2")
-	///     .option_hint("val 3", "value 3", "hint")
-	///     .option("val 4", "value 4")
-	///     .option("val 5", "value 5")
-	///     .less()
+	///     .option("val1", "label 1")
+	///     .extend_options(map)
 	///     .interact()?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn less(&mut self) -> &mut Self {
-		self.less = true;
+	pub fn extend_options<I, P>(&mut self, options: I) -> &mut Self
+	where
+		I: IntoIterator<Item = P>,
+		P: Into<Opt<T, O>>,
+	{
+		let options = options.into_iter().map(|opt| Row::Opt(opt.into()));
+		self.options.extend(options);
 		self
 	}
 
-	/// Enable paging with the amount of terminal rows, additionally setting a maximum amount.
+	/// Add a non-selectable separator line.
 	///
-	/// # Panics
+	/// # Examples
 	///
-	/// Panics when the given value is 0.  
-	/// Panics when called after [`Select::less_amt`] has already been called.
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option("val1", "label 1")
+	///     .separator()
+	///     .option("val2", "label 2")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn separator(&mut self) -> &mut Self {
+		self.options.push(Row::Separator);
+		self
+	}
+
+	/// Add a non-selectable header label.
 	///
 	/// # Examples
 	///
@@ -266,34 +623,25 @@ impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
 	/// let answer = select("message")
-	///     .option("val 1", "value 1")
-	///     .option("val 2", "value 2")
-	///     .option_hint("val 3", "value 3", "hint")
-	///     .option("val 4", "value 4")
-	///     .option("val 5", "value 5")
-	///     .less_max(3)
+	///     .header("fruits")
+	///     .option("mango", "Mango")
+	///     .option("peach", "Peach")
+	///     .header("vegetables")
+	///     .option("carrot", "Carrot")
 	///     .interact()?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn less_max(&mut self, max: u16) -> &mut Self {
-		assert!(max > 0, "less max value has to be greater than zero");
-		assert!(
-			self.less_amt.is_none(),
-			"cannot set both less_amt and less_max"
-		);
-		self.less = true;
-		self.less_max = Some(max);
+	pub fn header<S: ToString>(&mut self, label: S) -> &mut Self {
+		self.options.push(Row::Header(label.to_string()));
 		self
 	}
 
-	/// Enable paging with the specified amount of lines.
+	/// Specify the index of the initially focused option.
 	///
-	/// # Panics
-	///
-	/// Panics when the given value is 0.  
-	/// Panics when called after [`Select::less_max`] has already been called.
+	/// Takes precedence over the [default](Select::default_index()) option.
+	/// Out of bound indices are ignored, and the first option is focused instead.
 	///
 	/// # Examples
 	///
@@ -302,81 +650,77 @@ impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
 	/// let answer = select("message")
-	///     .option("val 1", "value 1")
-	///     .option("val 2", "value 2")
-	///     .option_hint("val 3", "value 3", "hint")
-	///     .option("val 4", "value 4")
-	///     .option("val 5", "value 5")
-	///     .less_amt(3)
+	///     .option("val1", "label 1")
+	///     .option("val2", "label 2")
+	///     .initial_index(1)
 	///     .interact()?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn less_amt(&mut self, less: u16) -> &mut Self {
-		assert!(less > 0, "less value has to be greater than zero");
-		assert!(
-			self.less_max.is_none(),
-			"cannot set both less_amt and less_max"
-		);
-		self.less = true;
-		self.less_amt = Some(less);
+	pub fn initial_index(&mut self, idx: usize) -> &mut Self {
+		self.initial = Some(Pick::Index(idx));
 		self
 	}
 
-	/// Specify function to call on cancel.
+	/// Focus the option with the given value initially.
+	///
+	/// The value is looked up when the prompt starts,
+	/// so options added or loaded after calling this are considered too.
 	///
 	/// # Examples
 	///
 	/// ```no_run
-	/// use may_clack::{select, cancel};
+	/// use may_clack::select;
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
-	/// let answer = select("select")
-	///     .option("val1", "value 1")
-	///     .option("val2", "value 2")
-	///     .option_hint("val 3", "value 3", "hint")
-	///     .cancel(do_cancel)
+	/// let answer = select("message")
+	///     .option("eu", "Europe")
+	///     .option("us", "North America")
+	///     .initial_value(&"us")
 	///     .interact()?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
-	///
-	/// fn do_cancel() {
-	///     cancel!("operation cancelled");
-	///     panic!("operation cancelled");
-	/// }
-	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
+	/// ```
+	pub fn initial_value(&mut self, value: &T) -> &mut Self
 	where
-		F: Fn() + 'static,
+		T: PartialEq,
 	{
-		let cancel = Box::new(cancel);
-		self.cancel = Some(cancel);
-
+		self.initial = Some(Pick::Value(value.clone(), T::eq));
 		self
 	}
 
-	fn mk_less(&self) -> Option<u16> {
-		if !self.less {
-			return None;
-		}
-
-		if let Some(less) = self.less_amt {
-			let is_less = self.options.len() > less as usize;
-			is_less.then_some(less)
-		} else if let Ok((_, rows)) = crossterm::terminal::size() {
-			let len = self.options.len();
-			let rows = rows.saturating_sub(4);
-			let rows = self.less_max.map_or(rows, |max| u16::min(rows, max));
-
-			let is_less = rows > 0 && len > rows as usize;
-			is_less.then_some(rows)
-		} else {
-			None
-		}
+	/// Mark the option at the given index as the default.
+	///
+	/// The default option is focused initially, so pressing enter right away accepts it,
+	/// unless an [initial](Select::initial_index()) option is specified.
+	/// Out of bound indices are ignored.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option("val1", "label 1")
+	///     .option("val2", "label 2")
+	///     .default_index(1)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn default_index(&mut self, idx: usize) -> &mut Self {
+		self.default = Some(Pick::Index(idx));
+		self
 	}
 
-	/// Wait for the user to submit an option.
+	/// Mark the option with the given value as the default.
+	///
+	/// The value is looked up when the prompt starts,
+	/// so options added or loaded after calling this are considered too.
 	///
 	/// # Examples
 	///
@@ -384,194 +728,1070 @@ impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
 	/// use may_clack::select;
 	///
 	/// # fn main() -> Result<(), may_clack::error::ClackError> {
-	/// let answer = select("select")
-	///     .option("val1", "value 1")
-	///     .option("val2", "value 2")
-	///     .option_hint("val 3", "value 3", "hint")
+	/// let answer = select("message")
+	///     .option("eu", "Europe")
+	///     .option("us", "North America")
+	///     .default_value(&"us")
 	///     .interact()?;
 	/// println!("answer {:?}", answer);
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub fn interact(&self) -> Result<T, ClackError> {
-		if self.options.is_empty() {
-			return Err(ClackError::NoOptions);
+	pub fn default_value(&mut self, value: &T) -> &mut Self
+	where
+		T: PartialEq,
+	{
+		self.default = Some(Pick::Value(value.clone(), T::eq));
+		self
+	}
+
+	/// Show the label of the default option next to the message, like `(default: label)`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option("eu", "Europe")
+	///     .option("us", "North America")
+	///     .default_value(&"us")
+	///     .show_default(true)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn show_default(&mut self, show: bool) -> &mut Self {
+		self.show_default = show;
+		self
+	}
+
+	/// Enable paging with the amount of terminal rows.
+	///
+	/// While paging, press `:` and type an item number (or `p` and a page number)
+	/// followed by enter to jump directly to it.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option("val 1", "value 1")
+	///     .option("val 2", "value 2")
+	///     .option_hint("val 3", "value 3", "hint")
+	///     .option("val 4", "value 4")
+	///     .option("val 5", "value 5")
+	///     .less()
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn less(&mut self) -> &mut Self {
+		self.less = true;
+		self
+	}
+
+	/// Enable paging with the amount of terminal rows, additionally setting a maximum amount.
+	///
+	/// # Panics
+	///
+	/// Panics when the given value is 0.  
+	/// Panics when called after [`Select::less_amt`] has already been called.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option("val 1", "value 1")
+	///     .option("val 2", "value 2")
+	///     .option_hint("val 3", "value 3", "hint")
+	///     .option("val 4", "value 4")
+	///     .option("val 5", "value 5")
+	///     .less_max(3)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn less_max(&mut self, max: u16) -> &mut Self {
+		assert!(max > 0, "less max value has to be greater than zero");
+		assert!(
+			self.less_amt.is_none(),
+			"cannot set both less_amt and less_max"
+		);
+		self.less = true;
+		self.less_max = Some(max);
+		self
+	}
+
+	/// Enable paging with the specified amount of lines.
+	///
+	/// # Panics
+	///
+	/// Panics when the given value is 0.  
+	/// Panics when called after [`Select::less_max`] or [`Select::less_fraction`] has already been called.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option("val 1", "value 1")
+	///     .option("val 2", "value 2")
+	///     .option_hint("val 3", "value 3", "hint")
+	///     .option("val 4", "value 4")
+	///     .option("val 5", "value 5")
+	///     .less_amt(3)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn less_amt(&mut self, less: u16) -> &mut Self {
+		assert!(less > 0, "less value has to be greater than zero");
+		assert!(
+			self.less_max.is_none() && self.less_fraction.is_none(),
+			"cannot set less_amt together with less_max or less_fraction"
+		);
+		self.less = true;
+		self.less_amt = Some(less);
+		self
+	}
+
+	/// Enable paging with a `fraction` of the terminal rows, like `0.5` for half of them.
+	///
+	/// The page is resized with the terminal, and can be combined with [`Select::less_max`].
+	///
+	/// # Panics
+	///
+	/// Panics when the given value is not greater than 0 and at most 1.  
+	/// Panics when called after [`Select::less_amt`] has already been called.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .options((1..=100).map(|i| (i, format!("value {}", i))))
+	///     .less_fraction(0.5)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn less_fraction(&mut self, fraction: f32) -> &mut Self {
+		assert!(
+			fraction > 0.0 && fraction <= 1.0,
+			"less fraction has to be greater than zero and at most one"
+		);
+		assert!(
+			self.less_amt.is_none(),
+			"cannot set both less_amt and less_fraction"
+		);
+		self.less = true;
+		self.less_fraction = Some(fraction);
+		self
+	}
+
+	/// Add secondary lines below the message, drawn dimmed while the prompt is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("select")
+	///     .message_extra("can be changed later")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn message_extra<S: ToString>(&mut self, extra: S) -> &mut Self {
+		self.extra = Some(extra.to_string());
+		self
+	}
+
+	/// Show a dimmed help line at the end of the prompt while it is active.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("select")
+	///     .help("arrow keys to move, enter to submit")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn help<S: ToString>(&mut self, help: S) -> &mut Self {
+		self.help = Some(help.to_string());
+		self
+	}
+
+	/// Change what is printed after submitting, instead of the label of the submitted option.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option("/usr/bin", "binaries")
+	///     .option("/usr/lib", "libraries")
+	///     .format_submitted(|path| path.to_string())
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn format_submitted<F>(&mut self, format: F) -> &mut Self
+	where
+		F: Fn(&T) -> String + Send + Sync + 'static,
+	{
+		self.format = Some(Arc::new(format));
+		self
+	}
+
+	/// Call `on_render` with the lines of every frame that is drawn, e.g. to record the prompt in tests.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	/// use std::sync::{Arc, Mutex};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let frames = Arc::new(Mutex::new(vec![]));
+	/// let recorded = Arc::clone(&frames);
+	/// let answer = select("fruit")
+	///     .option("mango", "Mango")
+	///     .option("peach", "Peach")
+	///     .on_render(move |lines| recorded.lock().unwrap().push(lines.to_vec()))
+	///     .interact()?;
+	/// println!("{} frames", frames.lock().unwrap().len());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_render<F>(&mut self, on_render: F) -> &mut Self
+	where
+		F: Fn(&[String]) + Send + Sync + 'static,
+	{
+		self.on_render = Some(Arc::new(on_render));
+		self
+	}
+
+	/// Draw the prompt as part of a [`Session`](crate::session::Session),
+	/// with the [`Context`] the session passes to [`Session::prompt()`](crate::session::Session::prompt()).
+	///
+	/// The option that is its [cached](crate::session::Session::cache()) answer is focused,
+	/// taking precedence over the [initial](Select::initial_index()) and the default option.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{select, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.steps(1);
+	///
+	/// let answer = session.prompt("region", |cx| {
+	///     select("region")
+	///         .option("eu", "Europe")
+	///         .option("us", "America")
+	///         .context(cx)
+	///         .interact()
+	/// })?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn context(&mut self, cx: &Context) -> &mut Self
+	where
+		T: 'static,
+	{
+		self.context = cx.clone();
+		self.cached = cx.cached().map(Prefill::answer);
+		self
+	}
+
+	/// Call `on_submit` with the answer when the prompt is submitted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("fruit")
+	///     .option("mango", "Mango")
+	///     .option("peach", "Peach")
+	///     .on_submit(|fruit| println!("picked {}", fruit))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_submit<F>(&mut self, on_submit: F) -> &mut Self
+	where
+		F: Fn(&T) + Send + Sync + 'static,
+	{
+		self.on_submit = Some(Arc::new(on_submit));
+		self
+	}
+
+	/// Specify function to call on cancel.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{select, cancel};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("select")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .option_hint("val 3", "value 3", "hint")
+	///     .cancel(do_cancel)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	///
+	/// fn do_cancel() {
+	///     cancel!("operation cancelled");
+	///     panic!("operation cancelled");
+	/// }
+	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn() + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(move |_| cancel());
+		self.cancel = Some(cancel);
+
+		self
+	}
+
+	/// Like [`Select::cancel()`], but the function gets the index of the focused option, not counting separators or headers.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("select")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .cancel_with(|index| eprintln!("cancelled on option {}", index))
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cancel_with<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn(usize) + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(cancel);
+		self.cancel = Some(cancel);
+		self
+	}
+
+	/// Return the value of the `cancel_return` function instead of an `Err` when the prompt is cancelled.
+	///
+	/// Only [`Select::interact()`] returns it, since the value doesn't have to be one of the options.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// // ctrl+c keeps the default region
+	/// let region = select("region")
+	///     .option("eu", "Europe")
+	///     .option("us", "America")
+	///     .on_cancel_return(|| "eu")
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_cancel_return<F>(&mut self, cancel_return: F) -> &mut Self
+	where
+		F: Fn() -> T + Send + Sync + 'static,
+	{
+		self.cancel_return = Some(Arc::new(cancel_return));
+		self
+	}
+
+	/// Load the options with a closure when the prompt starts, instead of specifying them upfront.
+	///
+	/// A spinner is shown below the message while the closure runs.
+	/// The loaded options replace the ones added with the other methods,
+	/// the closure runs again every time the prompt is interacted with.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{select, select::Opt};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .loader(|| {
+	///         let entries = std::fs::read_dir(".")?;
+	///         let opts = entries
+	///             .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+	///             .map(|name| name.map(Opt::auto))
+	///             .collect::<Result<Vec<_>, _>>();
+	///         opts
+	///     })
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn loader<F, E>(&mut self, loader: F) -> &mut Self
+	where
+		F: Fn() -> Result<Vec<Opt<T, O>>, E> + Send + Sync + 'static,
+		E: Into<Box<dyn Error + Send + Sync>>,
+	{
+		self.loader = Some(Arc::new(move || loader().map_err(Into::into)));
+		self
+	}
+
+	/// Re-fetch the options with a closure when the refresh key (`r` by default) is pressed.
+	///
+	/// The focus stays on the same value if it is still in the new options.
+	/// If the closure fails, the prompt is aborted with [`ClackError::Load`],
+	/// or with [`ClackError::NoOptions`] and [`ClackError::DuplicateOptions`] if the new options can't be shown.
+	/// The error is drawn below the message.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{select, select::Opt};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// fn containers() -> std::io::Result<Vec<Opt<String, String>>> {
+	///     // ...
+	/// #   Ok(vec![])
+	/// }
+	///
+	/// let answer = select("pick a container")
+	///     .loader(containers)
+	///     .on_refresh(containers)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_refresh<F, E>(&mut self, refresh: F) -> &mut Self
+	where
+		T: PartialEq,
+		F: Fn() -> Result<Vec<Opt<T, O>>, E> + Send + Sync + 'static,
+		E: Into<Box<dyn Error + Send + Sync>>,
+	{
+		self.refresh = Some(Refresh {
+			fetch: Arc::new(move || refresh().map_err(Into::into)),
+			eq: T::eq,
+		});
+		self
+	}
+
+	/// Re-fetch the options, returning the row index of the focused value in the new options.
+	///
+	/// The default stays on the same value, or is cleared if the value is not in the new options.
+	fn refresh(
+		&self,
+		r: &mut Renderer,
+		refresh: &Refresh<T, Opt<T, O>>,
+		s: &mut State<'_, T, O>,
+		idx: usize,
+	) -> Result<usize, ClackError> {
+		let focus = s.opt(idx).value.clone();
+		let default = s.default.map(|row| s.opt(row).value.clone());
+		let opts = list::load(r, &self.message, &*refresh.fetch)?;
+		let mut rows = list::loaded(opts);
+		self.check_duplicates(r, &rows)?;
+		if let Some(sort) = self.sort.as_deref() {
+			list::sort(&mut rows, |a: &Slot<_>, b: &Slot<_>| sort(a, b));
+		}
+
+		let position = |value: &T| {
+			rows.iter()
+				.position(|row| row.opt().is_some_and(|opt| (refresh.eq)(&opt.value, value)))
+		};
+
+		let new_idx = position(&focus).or_else(|| list::snap(&rows, idx));
+		s.default = default.as_ref().and_then(position);
+
+		s.rows = rows;
+		new_idx.ok_or_else(|| {
+			list::fail(r, &self.message, &ClackError::NoOptions);
+			ClackError::NoOptions
+		})
+	}
+
+	/// Change the key that re-fetches the options, see [`Select::on_refresh()`].
+	///
+	/// Defaults to `r`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .on_refresh(|| Ok::<_, std::io::Error>(vec![("val1", "value 1").into()]))
+	///     .refresh_key('u')
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn refresh_key(&mut self, key: char) -> &mut Self {
+		self.refresh_key = key;
+		self
+	}
+
+	/// Return [`ClackError::DuplicateOptions`] from `interact` if two options have the same value.
+	///
+	/// Duplicate values make it ambiguous which option was submitted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{select, error::ClackError};
+	///
+	/// let answer = select("message")
+	///     .option("val1", "value 1")
+	///     .option("val1", "value 2")
+	///     .deny_duplicates()
+	///     .interact();
+	/// assert!(matches!(answer, Err(ClackError::DuplicateOptions(0, 1))));
+	/// ```
+	pub fn deny_duplicates(&mut self) -> &mut Self
+	where
+		T: PartialEq + 'static,
+	{
+		self.dedup = Some(Arc::new(T::eq));
+		self
+	}
+
+	/// Like [`Select::deny_duplicates()`], but compares the keys extracted from the values.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option(("a", 1), "value 1")
+	///     .option(("b", 2), "value 2")
+	///     .deny_duplicates_by(|&(name, _)| name)
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn deny_duplicates_by<F, K>(&mut self, key: F) -> &mut Self
+	where
+		F: Fn(&T) -> K + Send + Sync + 'static,
+		K: PartialEq,
+	{
+		self.dedup = Some(Arc::new(move |a, b| key(a) == key(b)));
+		self
+	}
+
+	/// Sort the options by their labels before they are drawn.
+	///
+	/// Only options between the same separators or headers are sorted,
+	/// the default and the initially focused option are kept.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("branch")
+	///     .options(["main", "feature", "dev"].map(|branch| (branch, branch)))
+	///     .sort_by_label()
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn sort_by_label(&mut self) -> &mut Self {
+		self.sort = Some(Arc::new(|a, b| {
+			text::strip(&a.label.to_string()).cmp(&text::strip(&b.label.to_string()))
+		}));
+		self
+	}
+
+	/// Like [`Select::sort_by_label()`], but sorts the options by their values with a closure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("size")
+	///     .option(500, "500 MB")
+	///     .option(20, "20 MB")
+	///     .option(1000, "1 GB")
+	///     .sort_by(|a, b| b.cmp(a))
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn sort_by<F>(&mut self, cmp: F) -> &mut Self
+	where
+		F: Fn(&T, &T) -> Ordering + Send + Sync + 'static,
+	{
+		self.sort = Some(Arc::new(move |a, b| cmp(&a.value, &b.value)));
+		self
+	}
+
+	/// Look up the default and the initially focused option, and sort the options keeping both.
+	///
+	/// Returns the row index of the initially focused option,
+	/// which is the cached answer, the initial or the default option.
+	fn sort(&self, s: &mut State<'_, T, O>) -> Option<usize> {
+		s.default = self.default.as_ref().and_then(|pick| pick.row(&s.rows));
+		let cached = self.cached.as_ref().and_then(|cached| {
+			s.rows
+				.iter()
+				.position(|row| row.opt().is_some_and(|opt| cached.matches(&opt.value)))
+		});
+		let initial = self.initial.as_ref().and_then(|pick| pick.row(&s.rows));
+		let initial = cached.or(initial).or(s.default);
+		let Some(moved) = self.sort_rows(s) else {
+			return initial;
+		};
+
+		initial.map(|row| moved[row])
+	}
+
+	/// Sort the options, if they are sorted, keeping the default.
+	///
+	/// Returns the new row index of every row.
+	fn sort_rows(&self, s: &mut State<'_, T, O>) -> Option<Vec<usize>> {
+		let sort = self.sort.as_deref()?;
+		let moved = list::sort(&mut s.rows, |a: &Slot<_>, b: &Slot<_>| sort(a, b));
+		s.default = s.default.map(|row| moved[row]);
+		Some(moved)
+	}
+
+	/// Finish the prompt with an error if two options have the same value.
+	fn check_duplicates(
+		&self,
+		r: &mut Renderer,
+		rows: &[Row<Slot<'_, Opt<T, O>>>],
+	) -> Result<(), ClackError> {
+		let Some(eq) = self.dedup.as_deref() else {
+			return Ok(());
+		};
+
+		let values = rows.iter().filter_map(Row::opt).map(|opt| &opt.value);
+		match list::duplicate(values, eq) {
+			Some((a, b)) => {
+				let err = ClackError::DuplicateOptions(a, b);
+				list::fail(r, &self.message, &err);
+				Err(err)
+			}
+			None => Ok(()),
 		}
+	}
+
+	/// Run the loader, or borrow the options added to the builder.
+	fn state(&self, r: &mut Renderer) -> Result<State<'_, T, O>, ClackError> {
+		let rows = match self.loader.as_deref() {
+			Some(loader) => list::loaded(list::load(r, &self.message, loader)?),
+			None => self.options.iter().map(Row::borrowed).collect(),
+		};
+
+		Ok(State {
+			rows,
+			default: None,
+			loading: None,
+		})
+	}
 
-		let max = self.options.len();
-		let is_less = self.mk_less();
+	/// Wait for the first options of the [`Feed`], if there are no options yet.
+	fn wait_feed(&self, r: &mut Renderer, s: &mut State<'_, T, O>) -> Result<(), ClackError> {
+		let Some(incoming) = self.incoming.as_ref() else {
+			return Ok(());
+		};
 
-		let mut idx = 0;
-		let mut less_idx: u16 = 0;
+		let started = Instant::now();
+		let (opts, done) = if list::first(&s.rows).is_some() {
+			list::receive(incoming)
+		} else {
+			list::wait(r, &self.message, incoming, started)?
+		};
 
-		if let Some(less) = is_less {
-			self.w_init_less(less);
+		s.rows.extend(list::loaded(opts));
+		s.loading = (!done).then_some(started);
+		Ok(())
+	}
+
+	/// Add the options that arrived through the [`Feed`], and stop loading once all were sent.
+	///
+	/// Returns the new row index of the focused row `idx`.
+	fn receive(
+		&self,
+		r: &mut Renderer,
+		s: &mut State<'_, T, O>,
+		idx: usize,
+	) -> Result<usize, ClackError> {
+		let Some(incoming) = self.incoming.as_ref() else {
+			return Ok(idx);
+		};
+
+		let (opts, done) = list::receive(incoming);
+		if done {
+			s.loading = None;
+		}
+
+		if opts.is_empty() {
+			return Ok(idx);
+		}
+
+		s.rows.extend(list::loaded(opts));
+		self.check_duplicates(r, &s.rows)?;
+		Ok(self.sort_rows(s).map_or(idx, |moved| moved[idx]))
+	}
+
+	fn mk_less(&self, r: &Renderer, s: &State<'_, T, O>) -> Option<u16> {
+		if !self.less {
+			return None;
+		}
+
+		if let Some(less) = self.less_amt {
+			let is_less = s.rows.len() > less as usize;
+			is_less.then_some(less)
+		} else if let Some(rows) = r.height() {
+			let len = s.rows.len();
+			let loading = u16::from(s.loading.is_some());
+			let message = render::message_height(&self.message, self.extra.as_deref());
+			// the bar, the page line and the end line, or the error line in its place
+			let space = rows.saturating_sub(3 + message + loading + r.footer_height());
+			let rows = match self.less_fraction {
+				Some(fraction) => u16::min(space, ((rows as f32 * fraction) as u16).max(1)),
+				None => space,
+			};
+			let rows = self.less_max.map_or(rows, |max| u16::min(rows, max));
+
+			let is_less = rows > 0 && len > rows as usize;
+			is_less.then_some(rows)
 		} else {
-			self.w_init();
+			None
+		}
+	}
+
+	/// List the key bindings in the footer.
+	fn keys(&self, r: &mut Renderer, is_less: Option<u16>) {
+		let mut keys = vec![(*chars::ARROWS_UP_DOWN, "move")];
+		if is_less.is_some() {
+			keys.extend([("pgup/pgdn", "page"), (":", "jump")]);
+		}
+
+		let refresh = self.refresh_key.to_string();
+		if self.refresh.is_some() {
+			keys.push((&refresh, "refresh"));
+		}
+
+		keys.extend([("enter", "submit"), ("?", "hide keys")]);
+		r.keys(&keys);
+	}
+
+	/// Wait for the user to submit an option.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("select")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .option_hint("val 3", "value 3", "hint")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn interact(&self) -> Result<T, ClackError> {
+		match (self.interact_indexed(), self.cancel_return.as_deref()) {
+			(Err(ClackError::Cancelled), Some(cancel_return)) => Ok(cancel_return()),
+			(selection, _) => selection.map(|selection| selection.value),
+		}
+	}
+
+	/// Draw the prompt as if the option with the `value` was submitted, without waiting for the user.
+	///
+	/// Useful to show the answers of an earlier run as static lines before the first live prompt.
+	/// The [`on_submit`](Select::on_submit()) hook isn't called,
+	/// and the answer isn't added to the [transcript](crate::transcript) or the [`json_mode()`](crate::json_mode) summary.
+	///
+	/// Nothing is drawn if none of the options has the `value`,
+	/// options that are still [loading](Select::loading()) aren't considered.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// select("region")
+	///     .option("eu", "Europe")
+	///     .option("us", "North America")
+	///     .render_submitted(&"us");
+	/// let answer = select("size")
+	///     .option("s", "small")
+	///     .option("l", "large")
+	///     .interact()?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn render_submitted(&self, value: &T)
+	where
+		T: PartialEq,
+	{
+		let mut opts = self.options.iter().filter_map(Row::opt);
+		if let Some(opt) = opts.find(|opt| opt.value == *value) {
+			let mut r = Renderer::new();
+			r.on_render(self.on_render.as_ref());
+			r.context(&self.context);
+			self.w_submitted(&mut r, opt, true);
 		}
+	}
+
+	/// Like [`Select::interact()`], but also returns the index of the submitted option.
+	///
+	/// The index counts only options, not separators or headers.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let selection = select("select")
+	///     .option("val1", "value 1")
+	///     .option("val2", "value 2")
+	///     .interact_indexed()?;
+	/// println!("index {} value {:?}", selection.index, selection.value);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn interact_indexed(&self) -> Result<Selection<T>, ClackError> {
+		let mut r = Renderer::new();
+		r.on_render(self.on_render.as_ref());
+		r.context(&self.context);
+		r.hide_cursor();
+
+		let mut s = self.state(&mut r)?;
+		self.wait_feed(&mut r, &mut s)?;
+		self.check_duplicates(&mut r, &s.rows)?;
+		let initial = self.sort(&mut s);
+
+		let (Some(mut first), Some(mut last)) = (list::first(&s.rows), list::last(&s.rows)) else {
+			return Err(ClackError::NoOptions);
+		};
+
+		let mut is_less = self.mk_less(&r, &s);
+		self.keys(&mut r, is_less);
+
+		let mut idx = initial.unwrap_or(first);
+		let mut less_idx = is_less.map_or(0, |less| list::less_place(&s.rows, idx, less, 0));
+		let mut jump: Option<String> = None;
+
+		self.draw(&mut r, &s, is_less, idx, less_idx, None);
 
 		terminal::enable_raw_mode()?;
 
 		loop {
-			if let Event::Key(key) = event::read()? {
+			frame::flush()?;
+
+			let tick = s.loading.map(|_| render::TICK);
+			let Some(event) = r.read_event(tick)? else {
+				idx = match self.receive(&mut r, &mut s, idx) {
+					Ok(idx) => idx,
+					Err(err) => {
+						terminal::disable_raw_mode()?;
+						return Err(err);
+					}
+				};
+				first = list::first(&s.rows).expect("received options are not empty");
+				last = list::last(&s.rows).expect("received options are not empty");
+
+				is_less = self.mk_less(&r, &s);
+				self.keys(&mut r, is_less);
+				less_idx = is_less.map_or(0, |less| list::less_place(&s.rows, idx, less, less_idx));
+				self.draw(&mut r, &s, is_less, idx, less_idx, jump.as_deref());
+				continue;
+			};
+			if let Event::Resize(width, height) = event {
+				r.resize(width, height);
+				jump = None;
+
+				is_less = self.mk_less(&r, &s);
+				self.keys(&mut r, is_less);
+				less_idx = self.move_to(&mut r, &s, is_less, idx, less_idx);
+			}
+
+			if let Event::Key(key) = event {
 				if key.kind == KeyEventKind::Press {
 					match (key.code, key.modifiers) {
-						(KeyCode::Up | KeyCode::Left, _) => {
-							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								if idx > 0 {
-									idx -= 1;
-									less_idx = less_idx.saturating_sub(1);
-								} else {
-									idx = max - 1;
-									less_idx = less - 1;
+						(KeyCode::Char(':'), _) if jump.is_none() && is_less.is_some() => {
+							jump = Some(String::new());
+							self.draw(&mut r, &s, is_less, idx, less_idx, jump.as_deref());
+						}
+						(code, modifiers)
+							if jump.is_some() && !modifiers.contains(KeyModifiers::CONTROL) =>
+						{
+							let less = is_less.expect("jump is only possible in less mode");
+							let buf = jump.as_mut().expect("jump is some");
+
+							match code {
+								KeyCode::Char(c @ '0'..='9') => buf.push(c),
+								KeyCode::Char('p') if buf.is_empty() => buf.push('p'),
+								KeyCode::Backspace => {
+									buf.pop();
 								}
-
-								self.draw_less(less, idx, less_idx, prev_less);
-							} else {
-								self.draw_unfocus(idx);
-								let mut stdout = stdout();
-
-								if idx > 0 {
-									idx -= 1;
-									let _ = execute!(stdout, cursor::MoveUp(1));
-								} else if max > 1 {
-									idx = max - 1;
-									let _ = execute!(stdout, cursor::MoveDown(max as u16 - 1));
+								KeyCode::Enter => {
+									if let Some((i, l)) =
+										list::less_jump(buf, &s.rows, less, less_idx)
+									{
+										idx = i;
+										less_idx = l;
+									}
+									jump = None;
 								}
-
-								self.draw_focus(idx);
+								KeyCode::Esc => jump = None,
+								_ => {}
 							}
+
+							self.draw(&mut r, &s, is_less, idx, less_idx, jump.as_deref());
 						}
-						(KeyCode::Down | KeyCode::Right, _) => {
-							if let Some(less) = is_less {
-								let prev_less = less_idx;
+						(KeyCode::Char('?'), _) if r.has_footer() => {
+							r.toggle_footer();
+							self.draw(&mut r, &s, is_less, idx, less_idx, None);
+						}
+						(KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT)
+							if c == self.refresh_key && self.refresh.is_some() =>
+						{
+							let refresh = self.refresh.as_ref().expect("refresh is some");
+							terminal::disable_raw_mode()?;
 
-								if idx < max - 1 {
-									idx += 1;
-									if less_idx < less - 1 {
-										less_idx += 1;
-									}
-								} else {
-									idx = 0;
-									less_idx = 0;
-								}
+							idx = self.refresh(&mut r, refresh, &mut s, idx)?;
+							first = list::first(&s.rows).expect("refreshed options are not empty");
+							last = list::last(&s.rows).expect("refreshed options are not empty");
 
-								self.draw_less(less, idx, less_idx, prev_less);
-							} else {
-								self.draw_unfocus(idx);
-								let mut stdout = stdout();
-
-								if idx < max - 1 {
-									idx += 1;
-									let _ = execute!(stdout, cursor::MoveDown(1));
-								} else if idx > 0 {
-									idx = 0;
-									let _ = execute!(stdout, cursor::MoveUp(max as u16 - 1));
-								}
+							is_less = self.mk_less(&r, &s);
+							self.keys(&mut r, is_less);
+							less_idx = self.move_to(&mut r, &s, is_less, idx, less_idx);
 
-								self.draw_focus(idx);
-							}
+							terminal::enable_raw_mode()?;
 						}
-						(KeyCode::PageDown, _) => {
-							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								if idx + less as usize >= max - 1 {
-									less_idx = less - 1;
-									idx = max - 1;
-								} else {
-									idx += less as usize;
+						(KeyCode::Up | KeyCode::Left, _) => {
+							let (new, new_less) = match list::prev(&s.rows, idx) {
+								Some(new) => (new, less_idx.saturating_sub((idx - new) as u16)),
+								None => (last, u16::MAX),
+							};
 
-									if max - idx < (less - less_idx) as usize {
-										less_idx = less - (max - idx) as u16;
-									}
-								}
+							less_idx = self.move_to(&mut r, &s, is_less, new, new_less);
+							idx = new;
+						}
+						(KeyCode::Down | KeyCode::Right, _) => {
+							let (new, new_less) = match list::next(&s.rows, idx) {
+								Some(new) => (new, less_idx.saturating_add((new - idx) as u16)),
+								None => (first, 0),
+							};
 
-								self.draw_less(less, idx, less_idx, prev_less);
-							}
+							less_idx = self.move_to(&mut r, &s, is_less, new, new_less);
+							idx = new;
 						}
-						(KeyCode::PageUp, _) if idx != 0 => {
+						(KeyCode::PageDown, _) if idx != last => {
 							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								if idx <= less as usize {
-									less_idx = 0;
-									idx = 0;
+								let target = idx + less as usize;
+								let (new, new_less) = if target >= last {
+									(last, u16::MAX)
 								} else {
-									idx -= less as usize;
-									less_idx = prev_less.min(idx as u16);
-								}
+									let new = list::snap(&s.rows, target).unwrap_or(last);
+									(new, less_idx)
+								};
 
-								self.draw_less(less, idx, less_idx, prev_less);
+								less_idx = self.move_to(&mut r, &s, is_less, new, new_less);
+								idx = new;
 							}
 						}
-						(KeyCode::Home, _) if idx != 0 => {
+						(KeyCode::PageUp, _) if idx != first => {
 							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								idx = 0;
-								less_idx = 0;
-
-								self.draw_less(less, idx, less_idx, prev_less);
-							} else {
-								self.draw_unfocus(idx);
-
-								let mut stdout = stdout();
-								let _ = execute!(stdout, cursor::MoveUp(idx as u16));
+								let target = idx.saturating_sub(less as usize);
+								let (new, new_less) = if target <= first {
+									(first, 0)
+								} else {
+									let new = list::snap(&s.rows, target).unwrap_or(first);
+									(new, less_idx)
+								};
 
-								idx = 0;
-								self.draw_focus(0);
+								less_idx = self.move_to(&mut r, &s, is_less, new, new_less);
+								idx = new;
 							}
 						}
-						(KeyCode::End, _) if idx != max - 1 => {
-							if let Some(less) = is_less {
-								let prev_less = less_idx;
-
-								idx = max - 1;
-								less_idx = less - 1;
-
-								self.draw_less(less, idx, less_idx, prev_less);
-							} else {
-								self.draw_unfocus(idx);
-
-								let mut stdout = stdout();
-								let diff = max - idx - 1;
-								let _ = execute!(stdout, cursor::MoveDown(diff as u16));
-
-								idx = max - 1;
-
-								self.draw_focus(idx);
-							}
+						(KeyCode::Home, _) if idx != first => {
+							less_idx = self.move_to(&mut r, &s, is_less, first, 0);
+							idx = first;
+						}
+						(KeyCode::End, _) if idx != last => {
+							less_idx = self.move_to(&mut r, &s, is_less, last, u16::MAX);
+							idx = last;
 						}
 						(KeyCode::Enter, _) => {
 							terminal::disable_raw_mode()?;
+							self.w_out(&mut r, s.opt(idx));
 
-							if let Some(less) = is_less {
-								self.w_out_less(less, idx, less_idx);
-							} else {
-								self.w_out(idx);
-							}
-
-							let opt = self
-								.options
-								.get(idx)
-								.expect("idx should always be in bound");
-							let value = opt.value.clone();
-							return Ok(value);
+							let selection = Selection {
+								index: list::position(&s.rows, idx) - 1,
+								value: s.opt(idx).value.clone(),
+							};
+							return Ok(selection);
 						}
 						(KeyCode::Char('c' | 'd'), KeyModifiers::CONTROL) => {
 							terminal::disable_raw_mode()?;
-
-							if let Some(less) = is_less {
-								self.w_cancel_less(less, idx, less_idx);
-							} else {
-								self.w_cancel(idx);
-							}
+							self.w_cancel(&mut r, s.opt(idx));
 
 							if let Some(cancel) = self.cancel.as_deref() {
-								cancel();
+								cancel(list::position(&s.rows, idx) - 1);
 							}
 
 							return Err(ClackError::Cancelled);
@@ -585,210 +1805,192 @@ impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
 }
 
 impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
-	fn draw_focus(&self, idx: usize) {
-		let opt = self
-			.options
-			.get(idx)
-			.expect("idx should always be in bound");
-		let line = opt.focus();
-		self.draw(&line);
-	}
-
-	fn draw_unfocus(&self, idx: usize) {
-		let opt = self
-			.options
-			.get(idx)
-			.expect("idx should always be in bound");
-		let line = opt.unfocus();
-		self.draw(&line);
-	}
-
-	fn draw(&self, line: &str) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToColumn(0));
-
-		print!("{}", ansi::CLEAR_LINE);
-		print!("{}  {}", (*chars::BAR).cyan(), line);
-		let _ = stdout.flush();
-	}
-
-	fn draw_less(&self, less: u16, idx: usize, less_idx: u16, prev_less: u16) {
-		let mut stdout = stdout();
-		if prev_less > 0 {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(prev_less));
-		} else {
-			let _ = execute!(stdout, cursor::MoveToColumn(0));
-		}
-
-		for i in 0..less.into() {
-			let i_idx = idx + i - less_idx as usize;
-			let opt = self
-				.options
-				.get(i_idx)
-				.expect("i_idx should always be in bound");
-			let line = opt.unfocus();
-
-			print!("{}", ansi::CLEAR_LINE);
-			println!("{}  {}\r", (*chars::BAR).cyan(), line);
-
-			let _ = execute!(stdout, cursor::MoveToColumn(0));
+	fn fmt_unfocus(
+		&self,
+		s: &State<'_, T, O>,
+		width: Option<usize>,
+		column: usize,
+		idx: usize,
+	) -> String {
+		match &s.rows[idx] {
+			Row::Opt(opt) => opt.unfocus(width, column, s.default == Some(idx)),
+			row => row.fmt_separator(width),
 		}
+	}
 
-		let max = self.options.len();
-		let amt = max.to_string().len();
-		print!("{}", ansi::CLEAR_LINE);
-		println!(
-			"{}  ......... ({:#0amt$}/{})",
+	fn fmt_message(&self, r: &Renderer, s: &State<'_, T, O>) -> Vec<String> {
+		let mut lines = r.message(
+			(*chars::STEP_ACTIVE).cyan(),
 			(*chars::BAR).cyan(),
-			idx + 1,
-			max,
-			amt = amt
+			&self.message,
+			self.extra.as_deref(),
 		);
 
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(less + 1));
-		if less_idx > 0 {
-			let _ = execute!(stdout, cursor::MoveToNextLine(less_idx));
-		}
-
-		self.draw_focus(idx);
-	}
-}
+		let default = s
+			.default
+			.filter(|_| self.show_default)
+			.map(|idx| s.opt(idx));
 
-impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
-	fn w_init(&self) {
-		let mut stdout = stdout();
-
-		println!("{}", *chars::BAR);
-		println!("{}  {}", (*chars::STEP_ACTIVE).cyan(), self.message);
+		if let Some(opt) = default {
+			let label = opt.label.to_string();
+			let label = text::fit(&label, r.width(), text::width(&lines[0]) + 12);
 
-		for opt in &self.options {
-			let line = opt.unfocus();
-			println!("{}  {}", (*chars::BAR).cyan(), line);
+			let default = format!("(default: {})", label);
+			lines[0] = format!("{} {}", lines[0], default.dimmed());
 		}
 
-		print!("{}", (*chars::BAR_END).cyan());
-
-		let len = self.options.len() as u16;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(len));
-
-		self.draw_focus(0);
+		lines
 	}
 
-	fn w_init_less(&self, less: u16) {
-		println!("{}", *chars::BAR);
-		println!("{}  {}", (*chars::STEP_ACTIVE).cyan(), self.message);
-
-		self.draw_less(less, 0, 0, 0);
-
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToNextLine(less));
-
-		println!();
-		print!("{}", (*chars::BAR_END).cyan());
-
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(less + 1));
+	fn fmt_submitted(&self, width: Option<usize>, opt: &Opt<T, O>) -> String {
+		match self.format.as_deref() {
+			Some(format) => format(&opt.value),
+			None => text::fit(&opt.label.to_string(), width, 3).into_owned(),
+		}
+	}
 
-		self.draw_focus(0);
+	/// Move the focus to `idx`, returning the new `less_idx`.
+	fn move_to(
+		&self,
+		r: &mut Renderer,
+		s: &State<'_, T, O>,
+		is_less: Option<u16>,
+		idx: usize,
+		less_idx: u16,
+	) -> u16 {
+		let less_idx = is_less.map_or(0, |less| list::less_place(&s.rows, idx, less, less_idx));
+		self.draw(r, s, is_less, idx, less_idx, None);
+		less_idx
 	}
 
-	fn w_cancel(&self, idx: usize) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(idx as u16 + 1));
+	fn draw(
+		&self,
+		r: &mut Renderer,
+		s: &State<'_, T, O>,
+		is_less: Option<u16>,
+		idx: usize,
+		less_idx: u16,
+		jump: Option<&str>,
+	) {
+		let rows = &s.rows;
+		let (start, end) = match is_less {
+			Some(less) => {
+				let start = idx - less_idx as usize;
+				(start, start + less as usize)
+			}
+			None => (0, rows.len()),
+		};
 
-		println!("{}  {}", (*chars::STEP_CANCEL).red(), self.message);
+		let column = list::icon_column(
+			rows.iter()
+				.filter_map(Row::opt)
+				.map(|opt| opt.icon.as_ref()),
+		);
 
-		for _ in &self.options {
-			println!("{}", ansi::CLEAR_LINE);
+		let mut lines = vec![chars::BAR.to_string()];
+		lines.extend(self.fmt_message(r, s));
+		let head = lines.len();
+
+		for i in start..end.min(rows.len()) {
+			let line = if i == idx {
+				s.opt(idx).focus(r.width(), column, s.default == Some(idx))
+			} else {
+				self.fmt_unfocus(s, r.width(), column, i)
+			};
+			lines.push(format!("{}  {}", (*chars::BAR).cyan(), line));
 		}
-		print!("{}", ansi::CLEAR_LINE);
 
-		let len = self.options.len() as u16;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(len));
-
-		let label = &self
-			.options
-			.get(idx)
-			.expect("idx should always be in bound")
-			.label;
-		println!("{}  {}", *chars::BAR, label.strikethrough().dimmed());
-	}
-
-	fn w_cancel_less(&self, less: u16, idx: usize, less_idx: u16) {
-		let mut stdout = stdout();
-		if less_idx > 0 {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(less_idx + 1));
-		} else {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(1));
+		if let Some(less) = is_less {
+			lines.push(list::fmt_less(rows, less, idx, jump));
 		}
-
-		println!("{}  {}", (*chars::STEP_CANCEL).red(), self.message);
-
-		for _ in 0..less.into() {
-			println!("{}", ansi::CLEAR_LINE);
+		if let Some(started) = s.loading {
+			lines.push(spinner::fmt_row("loading", started));
 		}
+		lines.push(render::end(self.help.as_deref()));
 
-		println!("{}", ansi::CLEAR_LINE);
-		println!("{}", ansi::CLEAR_LINE);
-
-		let mv = less + 2;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(mv));
-
-		let label = &self
-			.options
-			.get(idx)
-			.expect("idx should always be in bound")
-			.label;
-		println!("{}  {}", *chars::BAR, label.strikethrough().dimmed());
+		r.draw(lines, head + idx - start);
 	}
+}
 
-	fn w_out(&self, idx: usize) {
-		let mut stdout = stdout();
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(idx as u16 + 1));
+impl<M: Display, T: Clone, O: Display> Select<M, T, O> {
+	fn w_cancel(&self, r: &mut Renderer, opt: &Opt<T, O>) {
+		transcript::prompt(&self.message, None);
+
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_CANCEL).red();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!(
+			"{}  {}",
+			*chars::BAR,
+			opt.label.strikethrough().dimmed()
+		));
+
+		r.finish(lines);
+	}
 
-		println!("{}  {}", (*chars::STEP_SUBMIT).green(), self.message);
+	fn w_out(&self, r: &mut Renderer, opt: &Opt<T, O>) {
+		self.w_submitted(r, opt, false);
 
-		for _ in &self.options {
-			println!("{}", ansi::CLEAR_LINE);
+		if let Some(on_submit) = self.on_submit.as_deref() {
+			on_submit(&opt.value);
 		}
-		print!("{}", ansi::CLEAR_LINE);
-
-		let len = self.options.len() as u16;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(len));
-
-		let label = &self
-			.options
-			.get(idx)
-			.expect("idx should always be in bound")
-			.label;
-		println!("{}  {}", *chars::BAR, label.dimmed());
 	}
 
-	fn w_out_less(&self, less: u16, idx: usize, less_idx: u16) {
-		let mut stdout = stdout();
-		if less_idx > 0 {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(less_idx + 1));
+	fn w_submitted(&self, r: &mut Renderer, opt: &Opt<T, O>, preview: bool) {
+		let answer = self.fmt_submitted(None, opt);
+		if preview {
+			transcript::preview(&self.message, &answer);
 		} else {
-			let _ = execute!(stdout, cursor::MoveToPreviousLine(1));
+			transcript::prompt(&self.message, Some(&answer));
 		}
 
-		println!("{}  {}", (*chars::STEP_SUBMIT).green(), self.message);
-
-		for _ in 0..less.into() {
-			println!("{}", ansi::CLEAR_LINE);
-		}
+		let submitted = self.fmt_submitted(r.width(), opt);
+		let mut lines = vec![chars::BAR.to_string()];
+		let step = (*chars::STEP_SUBMIT).green();
+		lines.extend(r.message(step, *chars::BAR, &self.message, None));
+		lines.push(format!("{}  {}", *chars::BAR, submitted.dimmed()));
 
-		println!("{}", ansi::CLEAR_LINE);
-		println!("{}", ansi::CLEAR_LINE);
+		r.finish(lines);
+	}
+}
 
-		let mv = less + 2;
-		let _ = execute!(stdout, cursor::MoveToPreviousLine(mv));
+impl<M: Display, T: Clone + Display> Select<M, T, T> {
+	/// Add an option, using the value as the label.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message")
+	///     .option_auto("val1")
+	///     .option_auto("val2")
+	///     .interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn option_auto(&mut self, value: T) -> &mut Self {
+		self.options.push(Row::Opt(Opt::auto(value)));
+		self
+	}
 
-		let label = &self
-			.options
-			.get(idx)
-			.expect("idx should always be in bound")
-			.label;
-		println!("{}  {}", *chars::BAR, label.dimmed());
+	/// Add multiple options, using the values as the labels.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::select;
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let answer = select("message").options_auto(1..=10).interact()?;
+	/// println!("answer {:?}", answer);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn options_auto<I: IntoIterator<Item = T>>(&mut self, values: I) -> &mut Self {
+		self.extend_options(values.into_iter().map(Opt::auto))
 	}
 }
 
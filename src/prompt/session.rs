@@ -0,0 +1,821 @@
+//! Session
+
+use super::{
+	cache::{Cache, Cached},
+	frame,
+	misc::{outro_cancel, outro_error},
+	render,
+	runner::Answers,
+	token::CancelToken,
+	transcript,
+};
+use crate::{error::ClackError, outro, style::chars};
+use owo_colors::OwoColorize;
+use std::{fmt::Display, path::Path, sync::Arc};
+
+/// What a [`Session`] does after a prompt was cancelled and the session was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnCancel {
+	/// Return [`ClackError::Cancelled`] from [`Session::prompt()`].
+	#[default]
+	Return,
+	/// Exit the process with the exit code.
+	Exit(i32),
+}
+
+/// `Context` struct.
+///
+/// What a [`Session`] passes to the prompt it asks in [`Session::prompt()`],
+/// like the step counter of a numbered prompt or its [cached](Session::cache()) answer.
+///
+/// Give it to the prompt with its `context` method, like [`Input::context()`](crate::input::Input::context()).
+/// A prompt without a context is drawn like it is outside of a session.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+	step: Option<(usize, usize)>,
+	key_footer: bool,
+	cached: Option<Cached>,
+	token: Option<CancelToken>,
+}
+
+impl Context {
+	/// The current step and the total amount of steps, if the prompts are [numbered](Session::steps()).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.steps(2);
+	///
+	/// let name = session.prompt("name", |cx| {
+	///     assert_eq!(cx.step(), Some((1, 2)));
+	///     input("name").context(cx).required()
+	/// })?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn step(&self) -> Option<(usize, usize)> {
+		self.step
+	}
+
+	/// Whether the prompt lists its key bindings in a footer, see [`Session::key_footer()`].
+	pub fn key_footer(&self) -> bool {
+		self.key_footer
+	}
+
+	/// The answer to the prompt saved in the [`Session::cache()`] by an earlier run.
+	///
+	/// The prompt is pre-filled with it, taking precedence over its initial value.
+	pub fn cached(&self) -> Option<&Cached> {
+		self.cached.as_ref()
+	}
+
+	/// Cancel the prompt once the `token` is cancelled, see [`CancelToken`].
+	///
+	/// Prompts asked in a session get the token of [`Session::cancel_on()`],
+	/// so this is for prompts outside of a session.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session::Context, token::CancelToken};
+	///
+	/// let token = CancelToken::new();
+	/// let mut cx = Context::default();
+	/// cx.cancel_on(token.clone());
+	///
+	/// let name = input("name").context(&cx).required();
+	/// ```
+	pub fn cancel_on(&mut self, token: CancelToken) -> &mut Self {
+		self.token = Some(token);
+		self
+	}
+
+	/// The token the prompt is cancelled with, if there is one.
+	pub(super) fn token(&self) -> Option<&CancelToken> {
+		self.token.as_ref()
+	}
+
+	/// Whether the prompt was cancelled with its token.
+	pub(super) fn is_cancelled(&self) -> bool {
+		self.token.as_ref().is_some_and(CancelToken::is_cancelled)
+	}
+}
+
+/// State of the block around the prompts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+	Idle,
+	Open,
+	Closed,
+}
+
+/// `Session` struct.
+///
+/// Owns the intro and outro around a group of prompts, and collects their answers.
+///
+/// The session is opened with the intro before the first prompt
+/// and closed with an empty outro when it is dropped, so the bars are always properly opened and closed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{confirm, input, session};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let mut session = session("create-app");
+/// session.steps(2).start();
+///
+/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+/// let git = session.prompt("git", |cx| confirm("initialize git?").context(cx).interact())?;
+///
+/// session.outro(format!("created {}", name));
+/// println!("git {}", git);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Session<M: Display> {
+	intro: M,
+	steps: usize,
+	/// Amount of prompts asked so far.
+	asked: usize,
+	key_footer: bool,
+	state: State,
+	cancel_message: String,
+	cancel: Option<Arc<dyn Fn() + Send + Sync>>,
+	on_cancel: OnCancel,
+	token: Option<CancelToken>,
+	answers: Answers,
+	cache: Option<Cache>,
+	config: Option<Cache>,
+	env_prefix: Option<String>,
+	stderr: bool,
+	/// Whether the output went to stderr before the session was opened.
+	was_stderr: bool,
+	quiet: bool,
+	/// Whether the bars were left out before the session was opened.
+	was_quiet: bool,
+}
+
+impl<M: Display> Session<M> {
+	/// Creates a new `Session` struct.
+	///
+	/// Has a shorthand in [`session()`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{session, session::Session};
+	///
+	/// // these two are equivalent
+	/// let wizard = Session::new("intro");
+	/// let wizard = session("intro");
+	/// ```
+	pub fn new(intro: M) -> Session<M> {
+		Session {
+			intro,
+			steps: 0,
+			asked: 0,
+			key_footer: false,
+			state: State::Idle,
+			cancel_message: "operation cancelled".into(),
+			cancel: None,
+			on_cancel: OnCancel::default(),
+			token: None,
+			answers: Answers::default(),
+			cache: None,
+			config: None,
+			env_prefix: None,
+			stderr: false,
+			was_stderr: false,
+			quiet: false,
+			was_quiet: false,
+		}
+	}
+
+	/// Number the next `total` prompts of the session, drawing a step counter like `3/7` in front of their messages.
+	///
+	/// Every prompt asked with [`Session::prompt()`] counts as a step,
+	/// after the last one the prompts are no longer numbered.
+	/// The prompts get their step through the [`Context`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.steps(1);
+	///
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn steps(&mut self, total: usize) -> &mut Self {
+		self.steps = self.asked + total;
+		self
+	}
+
+	/// Show a footer below the prompts of the session, listing the key bindings of the prompt.
+	///
+	/// The footer can be hidden and shown again with `?`,
+	/// except in [`input`](crate::input::Input), [`password`](crate::password::Password)
+	/// and [`multi_input`](crate::multi_input::MultiInput) prompts.
+	/// The prompts get it through the [`Context`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{multi_select, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.key_footer(true);
+	///
+	/// let answer = session.prompt("select", |cx| {
+	///     multi_select("select")
+	///         .option("val1", "value 1")
+	///         .option("val2", "value 2")
+	///         .context(cx)
+	///         .interact()
+	/// })?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn key_footer(&mut self, show: bool) -> &mut Self {
+		self.key_footer = show;
+		self
+	}
+
+	/// Change the message of the outro when a prompt is cancelled.
+	///
+	/// Default: `operation cancelled`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.cancel_message("setup aborted");
+	///
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cancel_message<S: ToString>(&mut self, message: S) -> &mut Self {
+		self.cancel_message = message.to_string();
+		self
+	}
+
+	/// Specify function to call when a prompt of the session is cancelled.
+	///
+	/// It is called after the session is closed with the cancel message.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.cancel(|| std::process::exit(1));
+	///
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cancel<F>(&mut self, cancel: F) -> &mut Self
+	where
+		F: Fn() + Send + Sync + 'static,
+	{
+		let cancel = Arc::new(cancel);
+		self.cancel = Some(cancel);
+		self
+	}
+
+	/// Change what happens after a prompt of the session was cancelled,
+	/// so the prompts don't need their own [`cancel`](crate::input::Input::cancel()) functions.
+	///
+	/// Default: [`OnCancel::Return`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, input, session, session::OnCancel};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session
+	///     .cancel_message("setup aborted")
+	///     .on_cancel(OnCancel::Exit(130));
+	///
+	/// // exits the process if any of them is cancelled
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// let git = session.prompt("git", |cx| confirm("initialize git?").context(cx).interact())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_cancel(&mut self, on_cancel: OnCancel) -> &mut Self {
+		self.on_cancel = on_cancel;
+		self
+	}
+
+	/// Cancel the prompts of the session once the `token` is cancelled from another thread, see [`CancelToken`].
+	///
+	/// The active prompt is cancelled like the user cancelled it,
+	/// and so is every prompt that is asked after the token was cancelled.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session, token::CancelToken};
+	/// use std::{thread, time::Duration};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let token = CancelToken::new();
+	/// let mut session = session("intro");
+	/// session.cancel_on(token.clone());
+	///
+	/// thread::spawn(move || {
+	///     thread::sleep(Duration::from_secs(60));
+	///     token.cancel();
+	/// });
+	///
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cancel_on(&mut self, token: CancelToken) -> &mut Self {
+		self.token = Some(token);
+		self
+	}
+
+	/// Save the answers of the prompts in the file at the `path`, see [`Cache`],
+	/// so the prompts are pre-filled with them the next time.
+	///
+	/// The prompts get their cached answer through the [`Context`],
+	/// except [`password`](crate::password::Password) prompts, which are never pre-filled.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("create-app");
+	/// session.cache(".create-app-answers");
+	///
+	/// // pre-filled with the last name, or `my-app` on the first run
+	/// let name = session.prompt("name", |cx| input("name").initial_value("my-app").context(cx).required())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn cache<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+		self.cache = Some(Cache::open(path));
+		self
+	}
+
+	/// Get the answer saved under the `key` in the [`Session::cache()`] by an earlier run.
+	///
+	/// Returns [`None`] if there is no cache or no answer under the `key`.
+	pub fn cached(&self, key: &str) -> Option<&Cached> {
+		self.cache.as_ref()?.get(key)
+	}
+
+	/// Take the answers from the config file at the `path` instead of prompting,
+	/// for every prompt with an answer under its key.
+	///
+	/// The file has a `key = value` line for every answer, like the [`Cache`] file,
+	/// and answers from [`Session::env_prefix()`] take precedence over it.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// // with `git = false` in the file, only the name is asked
+	/// let mut session = session("create-app");
+	/// session.config_file("create-app.conf");
+	///
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// let git = session.prompt("git", |cx| confirm("initialize git?").context(cx).interact())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn config_file<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+		self.config = Some(Cache::open(path));
+		self
+	}
+
+	/// Take the answers from environment variables instead of prompting,
+	/// for every prompt with a variable named like the `prefix` and its key, e.g. `CREATE_APP_NAME` for `name`.
+	///
+	/// Variables are text, they are converted to bools like `true`, `yes` or `1`,
+	/// and to lists by splitting them at commas.
+	///
+	/// Answers that are taken from the environment or a config file are drawn as submitted, with where they are from,
+	/// so don't use them for secrets.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// // CREATE_APP_GIT=no create-app
+	/// let mut session = session("create-app");
+	/// session.env_prefix("CREATE_APP");
+	///
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// let git = session.prompt("git", |cx| confirm("initialize git?").context(cx).interact())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn env_prefix<S: ToString>(&mut self, prefix: S) -> &mut Self {
+		self.env_prefix = Some(prefix.to_string());
+		self
+	}
+
+	/// Write the session to stderr from the intro up to the outro, see [`stderr_mode()`](crate::stderr_mode),
+	/// so stdout only gets the results the program prints itself.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("create-app");
+	/// session.stderr(true);
+	///
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// session.outro("done");
+	///
+	/// println!("{}", name);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn stderr(&mut self, stderr: bool) -> &mut Self {
+		self.stderr = stderr;
+		self
+	}
+
+	/// Leave out the bars from the intro up to the outro, see [`quiet_mode()`](crate::quiet_mode),
+	/// so the finished prompts are only a `message: answer` line each.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{input, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("create-app");
+	/// session.quiet(true);
+	///
+	/// let name = session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// session.outro("done");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn quiet(&mut self, quiet: bool) -> &mut Self {
+		self.quiet = quiet;
+		self
+	}
+
+	/// Open the session with the intro.
+	///
+	/// Happens automatically before the first prompt, does nothing if the session was already opened.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::session;
+	///
+	/// let mut session = session("intro");
+	/// session.start();
+	/// ```
+	pub fn start(&mut self) -> &mut Self {
+		if self.state == State::Idle {
+			self.was_stderr = frame::is_stderr_mode();
+			if self.stderr {
+				frame::set_stderr(true);
+			}
+
+			self.was_quiet = frame::is_quiet();
+			if self.quiet {
+				frame::set_quiet(true);
+			}
+
+			let intro = self.intro.to_string();
+			if frame::is_quiet() {
+				frame::line(&intro);
+			} else {
+				frame::line(format!("{}  {}", *chars::BAR_START, intro));
+			}
+			transcript::message("intro", &intro);
+			self.state = State::Open;
+		}
+
+		self
+	}
+
+	/// Run a prompt and record its answer under the `key`.
+	///
+	/// The prompt is called with the [`Context`] of the session, to pass on to the prompt.
+	/// Opens the session if it isn't open yet.
+	/// If the session has a [`cache`](Session::cache()), the answer is saved in it.
+	/// If the prompt was cancelled or failed, the session is closed and the error is returned,
+	/// or the process is exited if it was cancelled with [`OnCancel::Exit`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{select, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// let region = session.prompt("region", |cx| {
+	///     select("region")
+	///         .option("eu", "Europe")
+	///         .option("us", "America")
+	///         .context(cx)
+	///         .interact()
+	/// })?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn prompt<T, F>(&mut self, key: &str, prompt: F) -> Result<T, ClackError>
+	where
+		T: Clone + 'static,
+		F: FnOnce(&Context) -> Result<T, ClackError>,
+	{
+		self.start();
+		let cx = self.context(key);
+		self.asked += 1;
+
+		if let Some((answer, text, source)) = self.resolve::<T>(key) {
+			transcript::prompt(&key, Some(&text));
+
+			if let Some(line) = render::take_answer() {
+				frame::line(format!("{} (from {})", line, source));
+			} else {
+				let step = (*chars::STEP_SUBMIT).green();
+				frame::line(*chars::BAR);
+				for line in render::numbered(step, *chars::BAR, &key, None, cx.step) {
+					frame::line(line);
+				}
+				let answer_line = format!("{} (from {})", text, source);
+				frame::line(format!("{}  {}", *chars::BAR, answer_line.dimmed()));
+			}
+
+			self.answers.insert(key, Box::new(answer.clone()));
+			return Ok(answer);
+		}
+
+		match prompt(&cx) {
+			Ok(answer) => {
+				self.answers.insert(key, Box::new(answer.clone()));
+				if let Some(cache) = self.cache.as_mut() {
+					cache.set(key, &answer);
+					let _ = cache.save();
+				}
+				Ok(answer)
+			}
+			Err(ClackError::Cancelled) => {
+				let message = self.cancel_message.clone();
+				self.close(|| outro_cancel(message));
+				if let Some(cancel) = self.cancel.as_deref() {
+					cancel();
+				}
+
+				if let OnCancel::Exit(code) = self.on_cancel {
+					std::process::exit(code);
+				}
+
+				Err(ClackError::Cancelled)
+			}
+			Err(err) => {
+				self.close(|| outro_error(&err));
+				Err(err)
+			}
+		}
+	}
+
+	/// Get the answer of the prompt recorded under the `key`.
+	///
+	/// Returns [`None`] if the prompt didn't run or its answer isn't a `T`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.prompt("git", |cx| confirm("initialize git?").context(cx).interact())?;
+	///
+	/// let git = session.answer::<bool>("git");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn answer<T: 'static>(&self, key: &str) -> Option<&T> {
+		self.answers.get(key)
+	}
+
+	/// Keys of the prompts that were answered, in order.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::session;
+	///
+	/// let session = session("intro");
+	/// for key in session.prompts() {
+	///     println!("{}", key);
+	/// }
+	/// ```
+	pub fn prompts(&self) -> impl Iterator<Item = &str> {
+		self.answers.keys()
+	}
+
+	/// The answers of the prompts that were answered.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, session};
+	///
+	/// # fn main() -> Result<(), may_clack::error::ClackError> {
+	/// let mut session = session("intro");
+	/// session.prompt("git", |cx| confirm("initialize git?").context(cx).interact())?;
+	///
+	/// let answers = session.answers();
+	/// println!("{} answers", answers.len());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn answers(&self) -> &Answers {
+		&self.answers
+	}
+
+	/// Close the session with the outro.
+	///
+	/// Does nothing if the session was already closed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::session;
+	///
+	/// let mut session = session("intro");
+	/// // do stuff
+	/// session.outro("done");
+	/// ```
+	pub fn outro<S: Display>(&mut self, message: S) {
+		self.close(|| {
+			outro!(message);
+		});
+	}
+
+	/// The context of the next prompt, asked under the `key`.
+	fn context(&self, key: &str) -> Context {
+		let step = self.asked + 1;
+		Context {
+			step: (step <= self.steps).then_some((step, self.steps)),
+			key_footer: self.key_footer,
+			cached: self.cached(key).cloned(),
+			token: self.token.clone(),
+		}
+	}
+
+	/// The answer to the prompt under the `key` from the environment or the config file,
+	/// with it as text and where it is from.
+	fn resolve<T: 'static>(&self, key: &str) -> Option<(T, String, String)> {
+		let env = self.env_prefix.as_ref().and_then(|prefix| {
+			let key = key
+				.to_uppercase()
+				.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+			let name = format!("{}_{}", prefix, key);
+			let value = std::env::var(&name).ok()?;
+			Some((Cached::Text(value), name))
+		});
+		let config = self
+			.config
+			.as_ref()
+			.and_then(|config| config.get(key))
+			.map(|value| (value.clone(), "config".to_owned()));
+
+		env.into_iter().chain(config).find_map(|(value, source)| {
+			let answer = value.to_answer::<T>()?;
+			Some((answer, value.to_text(), source))
+		})
+	}
+
+	/// Open the session if needed, and close it with the `end` message.
+	fn close<F: FnOnce()>(&mut self, end: F) {
+		if self.state == State::Closed {
+			return;
+		}
+
+		self.start();
+
+		end();
+		frame::set_stderr(self.was_stderr);
+		frame::set_quiet(self.was_quiet);
+		self.state = State::Closed;
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<M: Display> Session<M> {
+	/// Allow answers of the type `T` to be serialized.
+	///
+	/// Strings, numbers, bools and vectors of them can be serialized without it.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{select, session};
+	/// use serde::Serialize;
+	///
+	/// #[derive(Debug, Clone, Serialize)]
+	/// enum Region {
+	///     Europe,
+	///     America,
+	/// }
+	///
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let mut session = session("intro");
+	/// session.serializable::<Region>();
+	///
+	/// session.prompt("region", |cx| {
+	///     select("region")
+	///         .option(Region::Europe, "Europe")
+	///         .option(Region::America, "America")
+	///         .context(cx)
+	///         .interact()
+	/// })?;
+	/// println!("{}", session.to_json()?);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn serializable<T: serde::Serialize + 'static>(&mut self) -> &mut Self {
+		self.answers.serializable::<T>();
+		self
+	}
+
+	/// The answers as a JSON object, with the keys of the prompts.
+	///
+	/// # Errors
+	///
+	/// Returns an error if an answer can't be serialized,
+	/// because its type wasn't made serializable with [`Session::serializable()`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{confirm, input, session};
+	///
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let mut session = session("create-app");
+	/// session.prompt("name", |cx| input("name").context(cx).required())?;
+	/// session.prompt("git", |cx| confirm("initialize git?").context(cx).interact())?;
+	///
+	/// std::fs::write("app.json", session.to_json()?.to_string())?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+		self.answers.to_json()
+	}
+}
+
+/// Serializes the answers as a map, like [`Session::to_json()`].
+#[cfg(feature = "serde")]
+impl<M: Display> serde::Serialize for Session<M> {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.answers.serialize(serializer)
+	}
+}
+
+impl<M: Display> Drop for Session<M> {
+	fn drop(&mut self) {
+		if self.state == State::Open {
+			self.close(|| {
+				outro!();
+			});
+		}
+	}
+}
+
+/// Shorthand for [`Session::new()`]
+pub fn session<M: Display>(intro: M) -> Session<M> {
+	Session::new(intro)
+}
@@ -0,0 +1,1098 @@
+//! Spinner
+
+use super::{
+	debug,
+	frame::{self, out, Out},
+	render::Renderer,
+	transcript,
+};
+use crate::style::{chars, is_unicode};
+use crossterm::{cursor, queue};
+use owo_colors::OwoColorize;
+use std::{
+	fmt::Display,
+	future::Future,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex, PoisonError,
+	},
+	thread::{self, JoinHandle},
+	time::{Duration, Instant},
+};
+
+const FRAMES_UNICODE: [&str; 4] = ["◒", "◐", "◓", "◑"];
+const FRAMES_ASCII: [&str; 4] = ["•", "o", "O", "0"];
+const INTERVAL: Duration = Duration::from_millis(80);
+
+type StyleFn = dyn Fn(&str) -> String + Send + Sync;
+
+/// The `spin` frame with the `style`, or magenta.
+fn fmt_frame(style: Option<&StyleFn>, spin: &str) -> String {
+	match style {
+		Some(style) => style(spin),
+		None => spin.magenta().to_string(),
+	}
+}
+
+/// Time that passed, like `(1m 23s)`.
+pub(super) fn fmt_elapsed(elapsed: Duration) -> String {
+	let secs = elapsed.as_secs();
+	let elapsed = match secs {
+		0..60 => format!("{}s", secs),
+		60..3600 => format!("{}m {}s", secs / 60, secs % 60),
+		_ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+	};
+
+	format!("({})", elapsed).dimmed().to_string()
+}
+
+/// The default frames for the terminal.
+fn default_frames() -> Vec<String> {
+	let frames = if is_unicode() {
+		FRAMES_UNICODE
+	} else {
+		FRAMES_ASCII
+	};
+
+	frames.map(String::from).to_vec()
+}
+
+/// Spinner row of a prompt that is loading, with the frame for the time since it `started`.
+///
+/// Unlike a [`SpinRow`], it is drawn by the prompt itself with every tick.
+pub(super) fn fmt_row(text: &str, started: Instant) -> String {
+	let frames = default_frames();
+	let frame = started.elapsed().as_millis() / INTERVAL.as_millis();
+	let spin = &frames[frame as usize % frames.len()];
+	format!("{}  {} {}", (*chars::BAR).cyan(), spin.magenta(), text)
+}
+
+/// An animated spinner on the current line, drawn from a separate thread.
+pub(super) struct SpinRow {
+	stop: Arc<AtomicBool>,
+	handle: Option<JoinHandle<()>>,
+}
+
+impl SpinRow {
+	/// Start drawing the spinner with the text on the current line.
+	pub fn start(text: String) -> Self {
+		SpinRow::spin(default_frames(), INTERVAL, move |spin| {
+			format!("{}  {} {}", (*chars::BAR).cyan(), spin.magenta(), text)
+		})
+	}
+
+	/// Start drawing the `line` of every frame on the current line, switching frames every `interval`.
+	pub fn spin<F>(frames: Vec<String>, interval: Duration, line: F) -> Self
+	where
+		F: Fn(&str) -> String + Send + 'static,
+	{
+		let stop = Arc::new(AtomicBool::new(false));
+
+		let is_stop = Arc::clone(&stop);
+		let handle = thread::spawn(move || {
+			for spin in frames.iter().cycle() {
+				if is_stop.load(Ordering::Relaxed) {
+					break;
+				}
+
+				let line = frame::mirror(line(spin));
+				debug::log(|| format!("spin line: {:?}", line));
+
+				let _ = queue!(Out, cursor::MoveToColumn(0));
+				frame::clear_line();
+				out!("{}", line);
+				let _ = frame::flush();
+
+				thread::sleep(interval);
+			}
+
+			debug::log(|| "clear spin line".into());
+			let _ = queue!(Out, cursor::MoveToColumn(0));
+			frame::clear_line();
+			let _ = frame::flush();
+		});
+
+		SpinRow {
+			stop,
+			handle: Some(handle),
+		}
+	}
+
+	/// Stop the spinner and clear its line.
+	pub fn stop(mut self) {
+		self.join();
+	}
+
+	fn join(&mut self) {
+		self.stop.store(true, Ordering::Relaxed);
+		if let Some(handle) = self.handle.take() {
+			let _ = handle.join();
+		}
+	}
+}
+
+impl Drop for SpinRow {
+	fn drop(&mut self) {
+		self.join();
+	}
+}
+
+/// `Spinner` struct
+///
+/// Shows that something is happening, until it is stopped with a message.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::spinner;
+///
+/// let mut spinner = spinner();
+/// spinner.start("installing dependencies");
+/// // do stuff
+/// spinner.stop("installed dependencies");
+/// ```
+pub struct Spinner {
+	frames: Option<Vec<String>>,
+	ascii_frames: Option<Vec<String>>,
+	interval: Duration,
+	style: Option<Arc<StyleFn>>,
+	timer: bool,
+	started: Option<Instant>,
+	row: Option<SpinRow>,
+}
+
+impl Default for Spinner {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Spinner {
+	/// Creates a new `Spinner` struct.
+	///
+	/// Has a shorthand version in [`spinner()`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::{spinner, spinner::Spinner};
+	///
+	/// // these two are equivalent
+	/// let spin = Spinner::new();
+	/// let spin = spinner();
+	/// ```
+	pub fn new() -> Self {
+		Spinner {
+			frames: None,
+			ascii_frames: None,
+			interval: INTERVAL,
+			style: None,
+			timer: false,
+			started: None,
+			row: None,
+		}
+	}
+
+	/// Specify the frames of the animation.
+	///
+	/// They are only used if the terminal supports unicode, see [`Spinner::ascii_frames()`].
+	/// An empty list of frames is ignored.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let mut spinner = spinner();
+	/// spinner.frames(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"]);
+	/// spinner.start("building");
+	/// ```
+	pub fn frames(&mut self, frames: &[&str]) -> &mut Self {
+		self.frames = Some(frames.iter().map(|&frame| frame.into()).collect());
+		self
+	}
+
+	/// Specify the frames of the animation if the terminal doesn't support unicode.
+	///
+	/// Default: `•`, `o`, `O`, `0`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let mut spinner = spinner();
+	/// spinner
+	///     .frames(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"])
+	///     .ascii_frames(&["-", "\\", "|", "/"]);
+	/// spinner.start("building");
+	/// ```
+	pub fn ascii_frames(&mut self, frames: &[&str]) -> &mut Self {
+		self.ascii_frames = Some(frames.iter().map(|&frame| frame.into()).collect());
+		self
+	}
+
+	/// Specify how long every frame is shown.
+	///
+	/// Default: 80ms
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	/// use std::time::Duration;
+	///
+	/// let mut spinner = spinner();
+	/// spinner.interval(Duration::from_millis(120));
+	/// spinner.start("building");
+	/// ```
+	pub fn interval(&mut self, interval: Duration) -> &mut Self {
+		self.interval = interval;
+		self
+	}
+
+	/// Specify how the frames are styled.
+	///
+	/// Default: magenta
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let mut spinner = spinner();
+	/// spinner.style(|frame| {
+	///     use owo_colors::OwoColorize;
+	///     frame.bright_blue().bold().to_string()
+	/// });
+	/// spinner.start("building");
+	/// ```
+	pub fn style<F>(&mut self, style: F) -> &mut Self
+	where
+		F: Fn(&str) -> String + Send + Sync + 'static,
+	{
+		self.style = Some(Arc::new(style));
+		self
+	}
+
+	/// Show how long the spinner has been running next to the message, like `(1m 23s)`.
+	///
+	/// The time it took is also shown next to the message it is stopped with.
+	///
+	/// Default: `false`
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let mut spinner = spinner();
+	/// spinner.timer(true);
+	/// spinner.start("running migrations");
+	/// // do stuff
+	/// spinner.stop("ran migrations");
+	/// ```
+	pub fn timer(&mut self, timer: bool) -> &mut Self {
+		self.timer = timer;
+		self
+	}
+
+	/// Create a [`MultiSpinner`] with the animation of this spinner,
+	/// to draw a spinner for each of several tasks that run at the same time.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let multi = spinner()
+	///     .frames(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"])
+	///     .multi();
+	/// let task = multi.add("downloading");
+	/// // do stuff
+	/// task.stop("downloaded");
+	/// multi.wait();
+	/// ```
+	pub fn multi(&self) -> MultiSpinner {
+		MultiSpinner::start(
+			self.fmt_frames(),
+			self.interval,
+			self.style.clone(),
+			self.timer,
+		)
+	}
+
+	/// Start the spinner with the `message`.
+	///
+	/// If the spinner is already running, it is restarted with the new message, and the timer keeps running.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let mut spinner = spinner();
+	/// spinner.start("installing dependencies");
+	/// ```
+	pub fn start<M: Display>(&mut self, message: M) {
+		match self.row.take() {
+			Some(row) => row.stop(),
+			None => frame::bar(),
+		}
+
+		let started = *self.started.get_or_insert_with(Instant::now);
+		let timer = self.timer;
+
+		let style = self.style.clone();
+		let message = message.to_string();
+		let row = SpinRow::spin(self.fmt_frames(), self.interval, move |spin| {
+			let spin = fmt_frame(style.as_deref(), spin);
+			if timer {
+				format!("{}  {} {}", spin, message, fmt_elapsed(started.elapsed()))
+			} else {
+				format!("{}  {}", spin, message)
+			}
+		});
+
+		self.row = Some(row);
+	}
+
+	/// Frames of the animation for the terminal.
+	fn fmt_frames(&self) -> Vec<String> {
+		let frames = if is_unicode() {
+			self.frames.as_ref()
+		} else {
+			self.ascii_frames.as_ref()
+		};
+
+		frames
+			.filter(|frames| !frames.is_empty())
+			.cloned()
+			.unwrap_or_else(default_frames)
+	}
+
+	/// Stop the spinner, replacing it with the `message`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let mut spinner = spinner();
+	/// spinner.start("installing dependencies");
+	/// // do stuff
+	/// spinner.stop("installed dependencies");
+	/// ```
+	pub fn stop<M: Display>(&mut self, message: M) {
+		self.finish((*chars::STEP_SUBMIT).green(), message);
+	}
+
+	/// Stop the spinner, replacing it with the `message` drawn as an error.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let mut spinner = spinner();
+	/// spinner.start("installing dependencies");
+	/// // do stuff
+	/// spinner.stop_error("failed to install dependencies");
+	/// ```
+	pub fn stop_error<M: Display>(&mut self, message: M) {
+		self.finish((*chars::STEP_CANCEL).red(), message);
+	}
+
+	/// Start the spinner with the `message` while the `work` runs,
+	/// and stop it with the `message` if it succeeds or with the error if it fails.
+	///
+	/// # Errors
+	///
+	/// Returns the error of the `work`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// # fn main() -> std::io::Result<()> {
+	/// let config = spinner().run("reading config", || std::fs::read_to_string("config.toml"))?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn run<T, E, M, F>(&mut self, message: M, work: F) -> Result<T, E>
+	where
+		E: Display,
+		M: Display,
+		F: FnOnce() -> Result<T, E>,
+	{
+		self.start(&message);
+		let result = work();
+		self.resolve(message, &result);
+		result
+	}
+
+	/// Like [`Spinner::run()`], but awaits the `work`.
+	///
+	/// The spinner is drawn from its own thread, so it works with any async runtime.
+	///
+	/// # Errors
+	///
+	/// Returns the error of the `work`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// # async fn fetch_releases() -> Result<Vec<String>, std::io::Error> { Ok(vec![]) }
+	/// # async fn run() -> Result<(), std::io::Error> {
+	/// let releases = spinner()
+	///     .run_async("fetching releases", fetch_releases())
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn run_async<T, E, M, F>(&mut self, message: M, work: F) -> Result<T, E>
+	where
+		E: Display,
+		M: Display,
+		F: Future<Output = Result<T, E>>,
+	{
+		self.start(&message);
+		let result = work.await;
+		self.resolve(message, &result);
+		result
+	}
+
+	/// Stop the spinner with the `message`, or the error if the `result` is an error.
+	fn resolve<T, E: Display, M: Display>(&mut self, message: M, result: &Result<T, E>) {
+		match result {
+			Ok(_) => self.stop(message),
+			Err(err) => self.stop_error(err),
+		}
+	}
+
+	fn finish<S: Display, M: Display>(&mut self, step: S, message: M) {
+		let Some(row) = self.row.take() else {
+			return;
+		};
+		row.stop();
+
+		let message = message.to_string();
+		transcript::message("spinner", &message);
+
+		let started = self.started.take();
+		match started.filter(|_| self.timer) {
+			Some(started) => {
+				let elapsed = fmt_elapsed(started.elapsed());
+				frame::line(format!("{}  {} {}", step, message, elapsed));
+			}
+			None => frame::line(format!("{}  {}", step, message)),
+		}
+	}
+}
+
+/// State of a task of a [`MultiSpinner`] or a [`TaskGroup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TaskState {
+	Pending,
+	Running,
+	Done,
+	Failed,
+}
+
+struct Task {
+	message: String,
+	progress: Option<(u64, u64)>,
+	state: TaskState,
+	started: Instant,
+	/// How long the task took, once it is stopped.
+	elapsed: Option<Duration>,
+}
+
+impl Task {
+	fn is_stopped(&self) -> bool {
+		matches!(self.state, TaskState::Done | TaskState::Failed)
+	}
+
+	/// Stop the task with the `state`.
+	fn stop(&mut self, state: TaskState) {
+		self.state = state;
+		self.elapsed = Some(self.started.elapsed());
+	}
+}
+
+/// Tasks of a [`MultiSpinner`], shared with their handles.
+#[derive(Default)]
+struct Tasks {
+	tasks: Vec<Task>,
+	/// Whether the block is finished once all tasks are stopped.
+	closing: bool,
+	/// Whether the block is finished right away.
+	abort: bool,
+	/// Whether the time the tasks took is shown.
+	timer: bool,
+	/// Line drawn below the tasks, like the summary of a [`TaskGroup`].
+	summary: Option<String>,
+}
+
+impl Tasks {
+	fn is_finished(&self) -> bool {
+		self.abort || (self.closing && self.tasks.iter().all(Task::is_stopped))
+	}
+
+	/// Lines of the block, with the `spin` frame in front of the running tasks.
+	fn lines(&self, spin: &str, style: Option<&StyleFn>) -> Vec<String> {
+		let mut lines = vec![];
+		if !frame::is_quiet() {
+			lines.push(chars::BAR.to_string());
+		}
+
+		for task in &self.tasks {
+			let step = match task.state {
+				TaskState::Pending if self.abort => (*chars::STEP_CANCEL).red().to_string(),
+				TaskState::Pending => (*chars::STEP_PENDING).dimmed().to_string(),
+				TaskState::Running if self.abort => (*chars::STEP_CANCEL).red().to_string(),
+				TaskState::Running => fmt_frame(style, spin),
+				TaskState::Done => (*chars::STEP_SUBMIT).green().to_string(),
+				TaskState::Failed => (*chars::STEP_CANCEL).red().to_string(),
+			};
+
+			let mut line = match task.progress {
+				Some(progress) if task.state == TaskState::Running => {
+					format!("{}  {}  {}", step, task.message, fmt_progress(progress))
+				}
+				_ if task.state == TaskState::Pending => {
+					format!("{}  {}", step, task.message.dimmed())
+				}
+				_ => format!("{}  {}", step, task.message),
+			};
+
+			if self.timer && task.state != TaskState::Pending {
+				let elapsed = task.elapsed.unwrap_or_else(|| task.started.elapsed());
+				line = format!("{} {}", line, fmt_elapsed(elapsed));
+			}
+
+			lines.push(line);
+		}
+
+		match &self.summary {
+			Some(summary) if frame::is_quiet() => lines.push(summary.clone()),
+			Some(summary) => lines.push(format!("{}  {}", *chars::BAR, summary)),
+			None => {}
+		}
+
+		lines
+	}
+}
+
+/// A progress bar for `current` of `total`.
+fn fmt_progress((current, total): (u64, u64)) -> String {
+	const WIDTH: u64 = 20;
+
+	let current = current.min(total);
+	let filled = (current * WIDTH).checked_div(total).unwrap_or(WIDTH);
+	format!(
+		"{}{} {}",
+		chars::METER.repeat(filled as usize).cyan(),
+		chars::METER.repeat((WIDTH - filled) as usize).dimmed(),
+		format!("{}/{}", current, total).dimmed()
+	)
+}
+
+/// `MultiSpinner` struct
+///
+/// Several spinners stacked in one block, one for every task, created with [`Spinner::multi()`].
+///
+/// The tasks are updated through their [`TaskHandle`], which can be moved to another thread,
+/// and the whole block is redrawn at once with every frame.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::spinner;
+/// use std::thread;
+///
+/// let multi = spinner().multi();
+/// let workers = ["api", "web", "docs"].map(|name| {
+///     let task = multi.add(format!("building {}", name));
+///     thread::spawn(move || {
+///         // do stuff
+///         task.stop(format!("built {}", name));
+///     })
+/// });
+///
+/// multi.wait();
+/// ```
+pub struct MultiSpinner {
+	tasks: Arc<Mutex<Tasks>>,
+	handle: Option<JoinHandle<()>>,
+}
+
+impl MultiSpinner {
+	/// Start drawing the tasks from a separate thread, with the `frames` switching every `interval`.
+	fn start(
+		frames: Vec<String>,
+		interval: Duration,
+		style: Option<Arc<StyleFn>>,
+		timer: bool,
+	) -> Self {
+		let tasks = Tasks {
+			timer,
+			..Tasks::default()
+		};
+		let tasks = Arc::new(Mutex::new(tasks));
+
+		let shared = Arc::clone(&tasks);
+		let handle = thread::spawn(move || {
+			let mut r = Renderer::new();
+			for spin in frames.iter().cycle() {
+				let (lines, is_finished) = {
+					let tasks = shared.lock().unwrap_or_else(PoisonError::into_inner);
+					let lines =
+						(!tasks.tasks.is_empty()).then(|| tasks.lines(spin, style.as_deref()));
+					(lines, tasks.is_finished())
+				};
+
+				match lines {
+					Some(lines) if is_finished => r.finish(lines),
+					Some(lines) => {
+						r.hide_cursor();
+						let row = lines.len() - 1;
+						r.draw(lines, row);
+						let _ = frame::flush();
+					}
+					None => {}
+				}
+
+				if is_finished {
+					break;
+				}
+
+				thread::sleep(interval);
+			}
+		});
+
+		MultiSpinner {
+			tasks,
+			handle: Some(handle),
+		}
+	}
+
+	/// Add a task with the `message`, drawn below the tasks that were added before.
+	///
+	/// The block is drawn once the first task is added.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let multi = spinner().multi();
+	/// let task = multi.add("downloading");
+	/// // do stuff
+	/// task.stop("downloaded");
+	/// multi.wait();
+	/// ```
+	pub fn add<M: Display>(&self, message: M) -> TaskHandle {
+		self.push(message.to_string(), TaskState::Running)
+	}
+
+	fn push(&self, message: String, state: TaskState) -> TaskHandle {
+		let idx = {
+			let mut tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+			tasks.tasks.push(Task {
+				message,
+				progress: None,
+				state,
+				started: Instant::now(),
+				elapsed: None,
+			});
+			tasks.tasks.len() - 1
+		};
+
+		TaskHandle {
+			tasks: Arc::clone(&self.tasks),
+			idx,
+			stopped: false,
+		}
+	}
+
+	/// Wait until all tasks are stopped, and finish the block.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let multi = spinner().multi();
+	/// let task = multi.add("downloading");
+	/// std::thread::spawn(move || task.stop("downloaded"));
+	/// multi.wait();
+	/// ```
+	pub fn wait(mut self) {
+		self.tasks
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.closing = true;
+		self.join();
+	}
+
+	fn join(&mut self) {
+		if let Some(handle) = self.handle.take() {
+			let _ = handle.join();
+		}
+	}
+}
+
+impl Drop for MultiSpinner {
+	fn drop(&mut self) {
+		self.tasks
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.abort = true;
+		self.join();
+	}
+}
+
+/// `TaskHandle` struct
+///
+/// Handle to update a task of a [`MultiSpinner`], returned by [`MultiSpinner::add()`].
+///
+/// If the handle is dropped before the task is stopped, the task is drawn as failed.
+pub struct TaskHandle {
+	tasks: Arc<Mutex<Tasks>>,
+	idx: usize,
+	stopped: bool,
+}
+
+impl TaskHandle {
+	/// Change the message of the task.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let multi = spinner().multi();
+	/// let task = multi.add("connecting");
+	/// task.message("downloading");
+	/// task.stop("downloaded");
+	/// multi.wait();
+	/// ```
+	pub fn message<M: Display>(&self, message: M) {
+		self.update(|task| task.message = message.to_string());
+	}
+
+	/// Draw a progress bar for `current` of `total` next to the message.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner;
+	///
+	/// let multi = spinner().multi();
+	/// let task = multi.add("downloading");
+	/// for chunk in 0..=10 {
+	///     task.progress(chunk, 10);
+	/// }
+	/// task.stop("downloaded");
+	/// multi.wait();
+	/// ```
+	pub fn progress(&self, current: u64, total: u64) {
+		self.update(|task| task.progress = Some((current, total)));
+	}
+
+	/// Start a pending task, from then on its time is taken.
+	fn start(&self) {
+		self.update(|task| {
+			task.state = TaskState::Running;
+			task.started = Instant::now();
+		});
+	}
+
+	/// Stop the task, replacing its spinner with the `message`.
+	pub fn stop<M: Display>(mut self, message: M) {
+		self.finish(TaskState::Done, message.to_string());
+	}
+
+	/// Stop the task, replacing its spinner with the `message` drawn as an error.
+	pub fn stop_error<M: Display>(mut self, message: M) {
+		self.finish(TaskState::Failed, message.to_string());
+	}
+
+	fn finish(&mut self, state: TaskState, message: String) {
+		transcript::message("spinner", &message);
+		self.update(|task| {
+			task.message = message;
+			task.stop(state);
+		});
+		self.stopped = true;
+	}
+
+	fn update<F: FnOnce(&mut Task)>(&self, update: F) {
+		let mut tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+		update(&mut tasks.tasks[self.idx]);
+	}
+}
+
+impl Drop for TaskHandle {
+	fn drop(&mut self) {
+		if !self.stopped {
+			self.update(|task| task.stop(TaskState::Failed));
+		}
+	}
+}
+
+type WorkFn<'a> = dyn FnOnce() -> Result<(), String> + Send + 'a;
+
+/// Outcome of the tasks of a [`TaskGroup`], returned by [`TaskGroup::run()`].
+///
+/// It is drawn below the tasks like `4 ok, 1 failed, 12.3s`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskSummary {
+	/// Amount of tasks that succeeded
+	pub ok: usize,
+	/// Amount of tasks that failed
+	pub failed: usize,
+	/// Time it took to run all tasks
+	pub elapsed: Duration,
+}
+
+impl TaskSummary {
+	/// Whether all tasks succeeded.
+	pub fn is_ok(&self) -> bool {
+		self.failed == 0
+	}
+}
+
+impl Display for TaskSummary {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{} ok, {} failed, {:.1}s",
+			self.ok,
+			self.failed,
+			self.elapsed.as_secs_f64()
+		)
+	}
+}
+
+/// `TaskGroup` struct
+///
+/// Runs a set of independent tasks, drawn in one block with a pending, running, done or failed glyph in front of each,
+/// and finishes with a summary of the tasks below them.
+///
+/// The tasks run one after the other, or all at once with [`TaskGroup::parallel()`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::task_group;
+/// use std::fs;
+///
+/// let summary = task_group()
+///     .task("reading config", || fs::read_to_string("config.toml"))
+///     .task("creating cache", || fs::create_dir_all(".cache"))
+///     .parallel(true)
+///     .run();
+///
+/// if !summary.is_ok() {
+///     std::process::exit(1);
+/// }
+/// ```
+pub struct TaskGroup<'a> {
+	tasks: Vec<(String, Box<WorkFn<'a>>)>,
+	parallel: bool,
+	timer: bool,
+}
+
+impl Default for TaskGroup<'_> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<'a> TaskGroup<'a> {
+	/// Creates a new `TaskGroup` struct.
+	///
+	/// Has a shorthand version in [`task_group()`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::spinner::TaskGroup;
+	///
+	/// let summary = TaskGroup::new()
+	///     .task("checking", || Ok::<_, std::io::Error>(()))
+	///     .run();
+	/// ```
+	pub fn new() -> Self {
+		TaskGroup {
+			tasks: vec![],
+			parallel: false,
+			timer: false,
+		}
+	}
+
+	/// Add a task with the `message`, that runs the `work`.
+	///
+	/// If the work fails, the task is drawn as failed with the error next to the message.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::task_group;
+	/// use std::fs;
+	///
+	/// let summary = task_group()
+	///     .task("creating cache", || fs::create_dir_all(".cache"))
+	///     .run();
+	/// ```
+	pub fn task<M, T, E, F>(&mut self, message: M, work: F) -> &mut Self
+	where
+		M: Display,
+		E: Display,
+		F: FnOnce() -> Result<T, E> + Send + 'a,
+	{
+		let work = move || work().map(drop).map_err(|err| err.to_string());
+		self.tasks.push((message.to_string(), Box::new(work)));
+		self
+	}
+
+	/// Run all tasks at the same time, each in its own thread.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::task_group;
+	/// use std::fs;
+	///
+	/// let summary = task_group()
+	///     .task("creating cache", || fs::create_dir_all(".cache"))
+	///     .task("creating logs", || fs::create_dir_all(".logs"))
+	///     .parallel(true)
+	///     .run();
+	/// ```
+	pub fn parallel(&mut self, parallel: bool) -> &mut Self {
+		self.parallel = parallel;
+		self
+	}
+
+	/// Show the time every task took next to it.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::task_group;
+	/// use std::fs;
+	///
+	/// let summary = task_group()
+	///     .task("creating cache", || fs::create_dir_all(".cache"))
+	///     .timer(true)
+	///     .run();
+	/// ```
+	pub fn timer(&mut self, timer: bool) -> &mut Self {
+		self.timer = timer;
+		self
+	}
+
+	/// Run the tasks and wait until all of them are finished, then draw the summary below them.
+	///
+	/// The tasks are taken out of the group, so it can be filled and run again.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::task_group;
+	/// use std::fs;
+	///
+	/// let summary = task_group()
+	///     .task("reading config", || fs::read_to_string("config.toml"))
+	///     .run();
+	/// println!("{} failed", summary.failed);
+	/// ```
+	pub fn run(&mut self) -> TaskSummary {
+		let started = Instant::now();
+		let multi = MultiSpinner::start(default_frames(), INTERVAL, None, self.timer);
+
+		let tasks = std::mem::take(&mut self.tasks);
+		let tasks = tasks
+			.into_iter()
+			.map(|(message, work)| {
+				(
+					multi.push(message.clone(), TaskState::Pending),
+					message,
+					work,
+				)
+			})
+			.collect::<Vec<_>>();
+
+		let parallel = self.parallel;
+		thread::scope(|scope| {
+			for (handle, message, work) in tasks {
+				if parallel {
+					scope.spawn(move || run_task(handle, message, work));
+				} else {
+					run_task(handle, message, work);
+				}
+			}
+		});
+
+		let mut tasks = multi.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+		let failed = tasks
+			.tasks
+			.iter()
+			.filter(|task| task.state == TaskState::Failed)
+			.count();
+		let summary = TaskSummary {
+			ok: tasks.tasks.len() - failed,
+			failed,
+			elapsed: started.elapsed(),
+		};
+
+		transcript::message("spinner", &summary.to_string());
+		tasks.summary = Some(fmt_summary(&summary));
+		drop(tasks);
+
+		multi.wait();
+		summary
+	}
+}
+
+/// Run the `work` of a task of a [`TaskGroup`], and stop the task with its outcome.
+fn run_task(handle: TaskHandle, message: String, work: Box<WorkFn<'_>>) {
+	handle.start();
+	match work() {
+		Ok(()) => handle.stop(message),
+		Err(err) => handle.stop_error(format!("{}: {}", message, err)),
+	}
+}
+
+/// The `summary` drawn below the tasks, with the failed tasks in red.
+fn fmt_summary(summary: &TaskSummary) -> String {
+	let failed = format!("{} failed", summary.failed);
+	let failed = if summary.is_ok() {
+		failed.dimmed().to_string()
+	} else {
+		failed.red().to_string()
+	};
+
+	format!(
+		"{} {}{}",
+		format!("{} ok,", summary.ok).dimmed(),
+		failed,
+		format!(", {:.1}s", summary.elapsed.as_secs_f64()).dimmed()
+	)
+}
+
+/// Shorthand for [`Spinner::new()`]
+pub fn spinner() -> Spinner {
+	Spinner::new()
+}
+
+/// Shorthand for [`TaskGroup::new()`]
+pub fn task_group<'a>() -> TaskGroup<'a> {
+	TaskGroup::new()
+}
@@ -0,0 +1,65 @@
+//! Machine-readable summary of the answers, see [`json_mode()`](super::json_mode)
+
+use super::transcript::json;
+use std::{
+	fmt::Display,
+	io::{self, Write},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Mutex, PoisonError,
+	},
+};
+
+/// Whether the answers are collected and written as a summary.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Messages and answers of the prompts since the last summary, [`None`] if the prompt was cancelled.
+static PROMPTS: Mutex<Vec<(String, Option<String>)>> = Mutex::new(Vec::new());
+
+/// Enable or disable collecting the answers.
+pub(super) fn set_enabled(enabled: bool) {
+	ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the answers are collected and written as a summary.
+pub(super) fn is_enabled() -> bool {
+	ENABLED.load(Ordering::Relaxed)
+}
+
+/// Add a prompt and its answer to the summary, [`None`] if the prompt was cancelled.
+pub(super) fn prompt<M: Display>(message: M, answer: Option<&str>) {
+	if is_enabled() {
+		let mut prompts = PROMPTS.lock().unwrap_or_else(PoisonError::into_inner);
+		prompts.push((message.to_string(), answer.map(str::to_owned)));
+	}
+}
+
+/// Write the summary to stdout with the `status` of the session, and start a new one.
+pub(super) fn emit(status: &str) {
+	if !is_enabled() {
+		return;
+	}
+
+	let prompts = std::mem::take(&mut *PROMPTS.lock().unwrap_or_else(PoisonError::into_inner));
+	let prompts = prompts
+		.iter()
+		.map(|(message, answer)| {
+			format!(
+				r#"{{"message":{},"answer":{},"cancelled":{}}}"#,
+				json(message),
+				answer.as_deref().map_or_else(|| "null".into(), json),
+				answer.is_none()
+			)
+		})
+		.collect::<Vec<_>>();
+
+	let summary = format!(
+		r#"{{"status":{},"prompts":[{}]}}"#,
+		json(status),
+		prompts.join(",")
+	);
+
+	let mut stdout = io::stdout().lock();
+	let _ = writeln!(stdout, "{}", summary);
+	let _ = stdout.flush();
+}
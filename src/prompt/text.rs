@@ -0,0 +1,217 @@
+//! Display width helpers
+//!
+//! Text is measured and cut per grapheme cluster, so combining characters and
+//! ZWJ emoji sequences are never split in half.
+//! Escape sequences, like colors and hyperlinks, take up no columns and are never cut.
+//!
+//! Right-to-left text is wrapped in a bidi isolate, so that terminals which reorder
+//! right-to-left text don't move the bars, symbols and hints around it.
+
+use crate::style::ansi;
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+/// First strong isolate, starts a run with the direction of its first strong char.
+const ISOLATE_START: char = '\u{2068}';
+/// Pop directional isolate, ends the run.
+const ISOLATE_END: char = '\u{2069}';
+
+/// Whether the char is from a right-to-left script, like Hebrew or Arabic.
+fn is_rtl_char(c: char) -> bool {
+	matches!(
+		c,
+		'\u{0590}'..='\u{08ff}'
+			| '\u{fb1d}'..='\u{fdff}'
+			| '\u{fe70}'..='\u{feff}'
+			| '\u{10800}'..='\u{10fff}'
+			| '\u{1e800}'..='\u{1efff}'
+	)
+}
+
+/// Wrap the text in a bidi isolate if it contains right-to-left text.
+///
+/// Isolates take up no columns.
+pub(super) fn isolate(text: Cow<'_, str>) -> Cow<'_, str> {
+	if text.chars().any(is_rtl_char) {
+		Cow::Owned(format!("{}{}{}", ISOLATE_START, text, ISOLATE_END))
+	} else {
+		text
+	}
+}
+
+/// Move the first symbol of the line, usually a bar or step, to the right edge of the terminal `columns`
+/// and right-align the rest of the line next to it.
+///
+/// The last column is left empty, so the terminal doesn't wrap the line.
+pub(super) fn mirror(line: &str, columns: usize) -> String {
+	let columns = columns.saturating_sub(1);
+
+	let mut symbol = String::new();
+	let mut rest = line;
+	while !rest.is_empty() {
+		let (chunk, is_escape) = chunk(rest);
+		if !is_escape {
+			if width(&symbol) > 0 {
+				break;
+			}
+
+			let len = chunk.graphemes(true).next().map_or(0, str::len);
+			symbol.push_str(&chunk[..len]);
+			rest = &rest[len..];
+		} else {
+			symbol.push_str(chunk);
+			rest = &rest[chunk.len()..];
+		}
+	}
+
+	if width(&symbol) == 0 {
+		return line.to_owned();
+	}
+
+	let content = rest.strip_prefix("  ").unwrap_or(rest).trim_end();
+	let content = truncate(content, columns.saturating_sub(3));
+	let used = if content.is_empty() {
+		width(&symbol)
+	} else {
+		width(&content) + 2 + width(&symbol)
+	};
+	let pad = " ".repeat(columns.saturating_sub(used));
+
+	if content.is_empty() {
+		format!("{}{}", pad, symbol)
+	} else {
+		format!("{}{}  {}", pad, content, symbol)
+	}
+}
+
+/// Amount of terminal columns a grapheme cluster takes up.
+///
+/// Terminals draw a cluster as a single glyph, as wide as its widest char.
+fn grapheme_width(grapheme: &str) -> usize {
+	grapheme
+		.chars()
+		.map(|c| c.width().unwrap_or(0))
+		.max()
+		.unwrap_or(0)
+}
+
+/// Length of the CSI or OSC escape sequence at the start of the text.
+fn escape_len(text: &str) -> Option<usize> {
+	let rest = text.strip_prefix('\x1b')?;
+
+	if let Some(csi) = rest.strip_prefix('[') {
+		let end = csi.bytes().position(|b| (0x40..=0x7e).contains(&b))?;
+		Some(2 + end + 1)
+	} else if let Some(osc) = rest.strip_prefix(']') {
+		let bel = osc.find('\x07').map(|idx| idx + 1);
+		let st = osc.find("\x1b\\").map(|idx| idx + 2);
+		let end = bel.into_iter().chain(st).min()?;
+		Some(2 + end)
+	} else {
+		None
+	}
+}
+
+/// Split off the start of the text, either a whole escape sequence
+/// or the visible text up to the next one.
+fn chunk(text: &str) -> (&str, bool) {
+	if let Some(len) = escape_len(text) {
+		return (&text[..len], true);
+	}
+
+	let start = text.chars().next().map_or(0, char::len_utf8);
+	let end = text[start..]
+		.find('\x1b')
+		.map_or(text.len(), |idx| start + idx);
+	(&text[..end], false)
+}
+
+/// Amount of terminal columns the text takes up.
+pub(super) fn width(text: &str) -> usize {
+	let mut width = 0;
+
+	let mut rest = text;
+	while !rest.is_empty() {
+		let (chunk, is_escape) = chunk(rest);
+		if !is_escape {
+			width += chunk.graphemes(true).map(grapheme_width).sum::<usize>();
+		}
+
+		rest = &rest[chunk.len()..];
+	}
+
+	width
+}
+
+/// The text without any escape sequences.
+pub(super) fn strip(text: &str) -> String {
+	let mut stripped = String::new();
+
+	let mut rest = text;
+	while !rest.is_empty() {
+		let (chunk, is_escape) = chunk(rest);
+		if !is_escape {
+			stripped.push_str(chunk);
+		}
+
+		rest = &rest[chunk.len()..];
+	}
+
+	stripped
+}
+
+/// Cut the text down to at most `width` terminal columns.
+///
+/// A hyperlink that is cut off is closed at the end.
+pub(super) fn truncate(text: &str, width: usize) -> Cow<'_, str> {
+	let mut used = 0;
+	let mut idx = 0;
+
+	let mut rest = text;
+	while !rest.is_empty() {
+		let (chunk, is_escape) = chunk(rest);
+		if !is_escape {
+			for (i, grapheme) in chunk.grapheme_indices(true) {
+				used += grapheme_width(grapheme);
+				if used > width {
+					return close(&text[..idx + i]);
+				}
+			}
+		}
+
+		idx += chunk.len();
+		rest = &rest[chunk.len()..];
+	}
+
+	Cow::Borrowed(text)
+}
+
+/// Close a hyperlink or bidi isolate that was cut off.
+fn close(text: &str) -> Cow<'_, str> {
+	let mut text = Cow::Borrowed(text);
+	if text.contains(ansi::LINK_START) {
+		text = Cow::Owned(format!("{}{}", text, ansi::LINK_END));
+	}
+
+	let open = text.matches(ISOLATE_START).count();
+	let closed = text.matches(ISOLATE_END).count();
+	if open > closed {
+		let end = ISOLATE_END.to_string().repeat(open - closed);
+		text = Cow::Owned(format!("{}{}", text, end));
+	}
+
+	text
+}
+
+/// Cut the text down to fit the terminal `width`, with `used` columns already taken up on the line.
+///
+/// Right-to-left text is isolated from the rest of the line.
+pub(super) fn fit(text: &str, width: Option<usize>, used: usize) -> Cow<'_, str> {
+	let text = match width {
+		Some(width) => truncate(text, width.saturating_sub(used)),
+		None => Cow::Borrowed(text),
+	};
+
+	isolate(text)
+}
@@ -0,0 +1,82 @@
+//! External cancellation
+
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc,
+};
+
+/// `CancelToken` struct
+///
+/// Cancels the prompts from another thread, e.g. when a deadline passes or a parent process asks the wizard to stop.
+///
+/// Once it is attached with [`Session::cancel_on()`](crate::session::Session::cancel_on())
+/// or [`Context::cancel_on()`](crate::session::Context::cancel_on()) and cancelled,
+/// the active prompt draws its cancel frame and returns [`ClackError::Cancelled`](crate::error::ClackError::Cancelled),
+/// just like when the user presses ctrl+c, and so do all prompts that are started with it after that.
+///
+/// The token can be cloned and moved to other threads, every clone cancels the same prompts.
+///
+/// The line editor of [`input`](crate::input::Input) and [`multi_input`](crate::multi_input::MultiInput) prompts
+/// only wakes up on key presses, so on unix a key press that cancels the prompt is put into the terminal input.
+/// On other platforms, or if the terminal doesn't allow it, an active text prompt is cancelled with the next key press.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{select, session, token::CancelToken};
+/// use std::{thread, time::Duration};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let token = CancelToken::new();
+/// let mut session = session("intro");
+/// session.cancel_on(token.clone());
+///
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_secs(60));
+///     token.cancel();
+/// });
+///
+/// let region = session.prompt("region", |cx| {
+///     select("region")
+///         .option("eu", "Europe")
+///         .option("us", "North America")
+///         .context(cx)
+///         .interact()
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+	cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+	/// Create a token that isn't cancelled yet.
+	pub fn new() -> Self {
+		CancelToken::default()
+	}
+
+	/// Cancel the prompts the token is attached to.
+	pub fn cancel(&self) {
+		self.cancelled.store(true, Ordering::Relaxed);
+	}
+
+	/// Whether the token was cancelled.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::token::CancelToken;
+	///
+	/// let token = CancelToken::new();
+	/// let clone = token.clone();
+	/// assert!(!token.is_cancelled());
+	///
+	/// clone.cancel();
+	/// assert!(token.is_cancelled());
+	/// ```
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
+}
@@ -0,0 +1,255 @@
+//! Transcript
+
+use super::{render, summary, text};
+use std::{
+	fmt::Display,
+	fs::File,
+	io::{self, BufWriter, Write},
+	path::Path,
+	sync::{Arc, Mutex, PoisonError},
+};
+
+type RedactFn = dyn Fn(&str) -> bool + Send + Sync;
+
+/// The transcript that is currently being written.
+static ACTIVE: Mutex<Option<Active>> = Mutex::new(None);
+
+struct Active {
+	writer: Box<dyn Write + Send>,
+	format: Format,
+	redact: Option<Arc<RedactFn>>,
+}
+
+/// Format of a [`Transcript`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+	/// One `kind: text` line per entry.
+	#[default]
+	Text,
+	/// One JSON object per line.
+	JsonLines,
+}
+
+/// `Transcript` struct.
+///
+/// Writes every message, prompt and answer of the session as plain text, for audit logs and bug reports.
+/// Colors and other escape sequences are left out.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{input, intro, outro, transcript::Transcript};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// Transcript::new().file("install.log")?;
+///
+/// intro!("install");
+/// let name = input("name").required()?;
+/// outro!("done");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct Transcript {
+	format: Format,
+	redact: Option<Arc<RedactFn>>,
+}
+
+impl Transcript {
+	/// Creates a new `Transcript` struct.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::transcript::Transcript;
+	///
+	/// Transcript::new().start(std::io::stderr());
+	/// ```
+	pub fn new() -> Transcript {
+		Transcript {
+			format: Format::Text,
+			redact: None,
+		}
+	}
+
+	/// Change the format of the transcript.
+	///
+	/// Default: [`Format::Text`]
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::transcript::{Format, Transcript};
+	///
+	/// Transcript::new()
+	///     .format(Format::JsonLines)
+	///     .start(std::io::stderr());
+	/// ```
+	pub fn format(&mut self, format: Format) -> &mut Self {
+		self.format = format;
+		self
+	}
+
+	/// Leave out the answers to the prompts whose message matches.
+	///
+	/// The answer is written as `[redacted]` instead.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::transcript::Transcript;
+	///
+	/// Transcript::new()
+	///     .redact(|message| message.contains("token"))
+	///     .start(std::io::stderr());
+	/// ```
+	pub fn redact<F>(&mut self, redact: F) -> &mut Self
+	where
+		F: Fn(&str) -> bool + Send + Sync + 'static,
+	{
+		self.redact = Some(Arc::new(redact));
+		self
+	}
+
+	/// Start writing the transcript to the `writer`, replacing the previous transcript.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::transcript::Transcript;
+	///
+	/// Transcript::new().start(std::io::stderr());
+	/// ```
+	pub fn start<W: Write + Send + 'static>(&self, writer: W) {
+		let active = Active {
+			writer: Box::new(writer),
+			format: self.format,
+			redact: self.redact.clone(),
+		};
+
+		let mut transcript = ACTIVE.lock().unwrap_or_else(PoisonError::into_inner);
+		if let Some(mut previous) = transcript.replace(active) {
+			let _ = previous.writer.flush();
+		}
+	}
+
+	/// Start writing the transcript to a file, replacing the previous transcript.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the file can't be created.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use may_clack::transcript::Transcript;
+	///
+	/// # fn main() -> std::io::Result<()> {
+	/// Transcript::new().file("install.log")?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let file = File::create(path)?;
+		self.start(BufWriter::new(file));
+		Ok(())
+	}
+}
+
+/// Stop writing the transcript.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::transcript::{self, Transcript};
+///
+/// Transcript::new().start(std::io::stderr());
+/// // do stuff
+/// transcript::stop();
+/// ```
+pub fn stop() {
+	let mut transcript = ACTIVE.lock().unwrap_or_else(PoisonError::into_inner);
+	if let Some(mut active) = transcript.take() {
+		let _ = active.writer.flush();
+	}
+}
+
+/// Add a message to the transcript, used by the message macros.
+#[doc(hidden)]
+pub fn message(kind: &str, message: &str) {
+	let message = text::strip(message);
+	let message = message.trim();
+
+	write(|format, _| match format {
+		Format::Text => format!("{}: {}", kind, message),
+		Format::JsonLines => format!(r#"{{"kind":{},"message":{}}}"#, json(kind), json(message)),
+	});
+}
+
+/// Add a prompt and its answer to the transcript, [`None`] if the prompt was cancelled.
+pub(super) fn prompt<M: Display>(message: &M, answer: Option<&str>) {
+	let message = text::strip(&message.to_string());
+
+	let stripped = answer.map(text::strip);
+	summary::prompt(&message, stripped.as_deref());
+	render::answered(&message, stripped.as_deref());
+
+	write(|format, redact| {
+		let answer = match answer {
+			Some(_) if redact.is_some_and(|redact| redact(&message)) => Some("[redacted]".into()),
+			Some(answer) => Some(text::strip(answer)),
+			None => None,
+		};
+
+		match format {
+			Format::Text => match answer {
+				Some(answer) => format!("prompt: {}\nanswer: {}", message, answer),
+				None => format!("prompt: {}\ncancelled", message),
+			},
+			Format::JsonLines => format!(
+				r#"{{"kind":"prompt","message":{},"answer":{},"cancelled":{}}}"#,
+				json(&message),
+				answer.as_deref().map_or_else(|| "null".into(), json),
+				answer.is_none()
+			),
+		}
+	});
+}
+
+/// Like [`prompt()`], but for an answer that is only previewed with `render_submitted`,
+/// which is left out of the transcript and the summary.
+pub(super) fn preview<M: Display>(message: &M, answer: &str) {
+	let message = text::strip(&message.to_string());
+	render::answered(&message, Some(&text::strip(answer)));
+}
+
+/// Write an entry, if a transcript is active.
+fn write<F>(entry: F)
+where
+	F: FnOnce(Format, Option<&RedactFn>) -> String,
+{
+	let mut transcript = ACTIVE.lock().unwrap_or_else(PoisonError::into_inner);
+	if let Some(active) = transcript.as_mut() {
+		let entry = entry(active.format, active.redact.as_deref());
+		let _ = writeln!(active.writer, "{}", entry);
+		let _ = active.writer.flush();
+	}
+}
+
+/// Quote a string as a JSON string.
+pub(super) fn json(text: &str) -> String {
+	let mut json = String::from('"');
+	for c in text.chars() {
+		match c {
+			'"' => json.push_str("\\\""),
+			'\\' => json.push_str("\\\\"),
+			'\n' => json.push_str("\\n"),
+			'\r' => json.push_str("\\r"),
+			'\t' => json.push_str("\\t"),
+			c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+			c => json.push(c),
+		}
+	}
+	json.push('"');
+	json
+}
@@ -0,0 +1,197 @@
+//! Validators
+
+use std::borrow::Cow;
+
+/// `Validator` trait.
+///
+/// Checks a submitted value, and returns the error message if it is invalid.
+///
+/// It is implemented for closures like the ones passed to [`Input::validate()`](crate::input::Input::validate()),
+/// and validators can be passed to [`Input::validator()`](crate::input::Input::validator()),
+/// [`MultiInput::validator()`](crate::multi_input::MultiInput::validator())
+/// and [`Password::validator()`](crate::password::Password::validator()).
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{
+///     input,
+///     validate::{self, Validator},
+/// };
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let name = input("name")
+///     .validator(validate::not_empty().and(validate::max_len(20)))
+///     .interact()?;
+/// println!("name {:?}", name);
+/// # Ok(())
+/// # }
+/// ```
+pub trait Validator {
+	/// Check the `value`.
+	///
+	/// # Errors
+	///
+	/// Returns the error message if the value is invalid.
+	fn validate(&self, value: &str) -> Result<(), Cow<'static, str>>;
+
+	/// Check the value with this validator, and then with the `other` one.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::validate::{self, Validator};
+	///
+	/// let validator = validate::not_empty().and(validate::max_len(3));
+	/// assert!(validator.validate("abc").is_ok());
+	/// assert!(validator.validate("").is_err());
+	/// assert!(validator.validate("abcd").is_err());
+	/// ```
+	fn and<V: Validator>(self, other: V) -> And<Self, V>
+	where
+		Self: Sized,
+	{
+		And(self, other)
+	}
+}
+
+impl<F> Validator for F
+where
+	F: Fn(&str) -> Result<(), Cow<'static, str>>,
+{
+	fn validate(&self, value: &str) -> Result<(), Cow<'static, str>> {
+		self(value)
+	}
+}
+
+/// `Attempt` struct.
+///
+/// Context of a validation, passed to the validation functions of
+/// [`Input::validate_with()`](crate::input::Input::validate_with()),
+/// [`MultiInput::validate_with()`](crate::multi_input::MultiInput::validate_with())
+/// and [`Password::validate_with()`](crate::password::Password::validate_with()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Attempt {
+	/// Number of the submission that is validated, starting at `1`.
+	pub number: usize,
+	/// Error the previous submission was rejected with.
+	pub previous_error: Option<String>,
+}
+
+impl Attempt {
+	/// Move on to the next submission.
+	pub(crate) fn next(&mut self) {
+		self.number += 1;
+	}
+
+	/// Remember the error the submission was rejected with.
+	pub(crate) fn reject(&mut self, error: &str) {
+		self.previous_error = Some(error.to_owned());
+	}
+
+	/// Whether no more submissions are allowed after this one was rejected.
+	pub(crate) fn is_last(&self, max_attempts: Option<usize>) -> bool {
+		max_attempts.is_some_and(|max| self.number >= max)
+	}
+}
+
+/// Two validators combined with [`Validator::and()`].
+#[derive(Debug, Clone, Copy)]
+pub struct And<A, B>(A, B);
+
+impl<A: Validator, B: Validator> Validator for And<A, B> {
+	fn validate(&self, value: &str) -> Result<(), Cow<'static, str>> {
+		self.0.validate(value)?;
+		self.1.validate(value)
+	}
+}
+
+/// Reject empty values.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::validate::{self, Validator};
+///
+/// assert!(validate::not_empty().validate("").is_err());
+/// ```
+pub fn not_empty() -> impl Validator + Clone {
+	|value: &str| {
+		if value.is_empty() {
+			Err(Cow::Borrowed("value is required"))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+/// Reject values with less than `min` chars.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::validate::{self, Validator};
+///
+/// assert!(validate::min_len(3).validate("ab").is_err());
+/// ```
+pub fn min_len(min: usize) -> impl Validator + Clone {
+	move |value: &str| {
+		if value.chars().count() < min {
+			Err(Cow::Owned(format!("use at least {} characters", min)))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+/// Reject values with more than `max` chars.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::validate::{self, Validator};
+///
+/// assert!(validate::max_len(3).validate("abcd").is_err());
+/// ```
+pub fn max_len(max: usize) -> impl Validator + Clone {
+	move |value: &str| {
+		if value.chars().count() > max {
+			Err(Cow::Owned(format!("use at most {} characters", max)))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+/// Reject values that don't match the `pattern`, with the error `message`.
+///
+/// The pattern is a function, so any pattern matcher can be used,
+/// like a [`regex`](https://docs.rs/regex) with `move |value| regex.is_match(value)`.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::validate::{self, Validator};
+///
+/// let validator = validate::matches(
+///     |value| value.chars().all(|c| c.is_ascii_lowercase() || c == '-'),
+///     "only use lowercase letters and dashes",
+/// );
+/// assert!(validator.validate("my-app").is_ok());
+/// assert!(validator.validate("My App").is_err());
+/// ```
+pub fn matches<F, S>(pattern: F, message: S) -> impl Validator
+where
+	F: Fn(&str) -> bool,
+	S: ToString,
+{
+	let message = message.to_string();
+	move |value: &str| {
+		if pattern(value) {
+			Ok(())
+		} else {
+			Err(Cow::Owned(message.clone()))
+		}
+	}
+}
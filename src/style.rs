@@ -2,52 +2,484 @@
 
 use is_unicode_supported::is_unicode_supported;
 use once_cell::sync::Lazy;
+use owo_colors::{AnsiColors, DynColors, OwoColorize, XtermColors};
+use std::{
+	env,
+	fmt::Display,
+	io::IsTerminal,
+	ops::Deref,
+	sync::atomic::{AtomicBool, Ordering},
+};
 
-pub(crate) static IS_UNICODE: Lazy<bool> = Lazy::new(is_unicode_supported);
+static IS_LEGACY_CONSOLE: Lazy<bool> = Lazy::new(is_legacy_console);
+static IS_UNICODE: Lazy<bool> = Lazy::new(|| {
+	let ascii = env::var_os("MAY_CLACK_ASCII").is_some_and(|ascii| ascii != "0");
+	!ascii && is_unicode_supported() && !*IS_LEGACY_CONSOLE
+});
+pub(crate) static IS_HYPERLINK: Lazy<bool> = Lazy::new(is_hyperlink_supported);
+static COLOR_SUPPORT: Lazy<ColorSupport> = Lazy::new(detect_color_support);
 
-fn is_unicode(unicode: &'static str, non_unicode: &'static str) -> &'static str {
-	if *IS_UNICODE {
-		unicode
+/// Guess if the prompts run in the legacy Windows console (conhost), outside of Windows Terminal.
+///
+/// The legacy console draws the box-drawing glyphs poorly, so the prompts fall back to ascii chars.
+/// This only picks the chars, crossterm checks if the console supports escape codes for the cursor movements and clears.
+/// Set `MAY_CLACK_LEGACY_CONSOLE` to `1` or `0` to override the detection.
+fn is_legacy_console() -> bool {
+	if let Some(force) = env::var_os("MAY_CLACK_LEGACY_CONSOLE") {
+		return force != "0";
+	}
+
+	if !cfg!(windows) {
+		return false;
+	}
+
+	let vars = [
+		"WT_SESSION",
+		"SSH_TTY",
+		"SSH_CONNECTION",
+		"TERM_PROGRAM",
+		"TERM",
+		"ConEmuPID",
+		"ALACRITTY_WINDOW_ID",
+	];
+	!vars.iter().any(|var| env::var_os(var).is_some())
+}
+
+/// Guess if the terminal supports OSC 8 hyperlinks.
+fn is_hyperlink_supported() -> bool {
+	if let Some(force) = env::var_os("FORCE_HYPERLINK") {
+		return force != "0";
+	}
+
+	if !std::io::stdout().is_terminal() {
+		return false;
+	}
+
+	let vars = [
+		"WT_SESSION",
+		"KITTY_WINDOW_ID",
+		"KONSOLE_VERSION",
+		"DOMTERM",
+	];
+	if vars.iter().any(|var| env::var_os(var).is_some()) {
+		return true;
+	}
+
+	if let Ok(vte) = env::var("VTE_VERSION") {
+		return vte.parse::<u32>().is_ok_and(|vte| vte >= 5000);
+	}
+
+	if let Ok(program) = env::var("TERM_PROGRAM") {
+		let programs = ["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper"];
+		if programs.contains(&program.as_str()) {
+			return true;
+		}
+	}
+
+	env::var("TERM").is_ok_and(|term| {
+		["kitty", "alacritty", "foot", "wezterm"]
+			.iter()
+			.any(|name| term.contains(name))
+	})
+}
+
+/// Make the text a clickable link to the url in terminals that support
+/// [OSC 8 hyperlinks](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda).
+///
+/// Falls back to the plain text in other terminals.
+/// Set `FORCE_HYPERLINK` to `1` or `0` to override the detection.
+///
+/// Works in messages and option labels, the link does not count towards the width of the line.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{select, style::hyperlink};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let docs = hyperlink("open the docs", "https://docs.rs/may-clack");
+/// let answer = select("what next?")
+///     .option("docs", docs)
+///     .option("exit", "exit".to_string())
+///     .interact()?;
+/// println!("answer {:?}", answer);
+/// # Ok(())
+/// # }
+/// ```
+pub fn hyperlink<T: Display, U: Display>(text: T, url: U) -> String {
+	if *IS_HYPERLINK {
+		format!(
+			"{}{}\x1b\\{}{}",
+			ansi::LINK_START,
+			url,
+			text,
+			ansi::LINK_END
+		)
 	} else {
-		non_unicode
+		text.to_string()
+	}
+}
+
+/// Colors the terminal supports, see [`color_support()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorSupport {
+	/// The 16 basic colors.
+	Basic,
+	/// The palette of 256 colors.
+	Ansi256,
+	/// Any RGB color.
+	TrueColor,
+}
+
+/// Guess which colors the terminal supports from the environment.
+fn detect_color_support() -> ColorSupport {
+	if let Ok(force) = env::var("FORCE_COLOR") {
+		match force.as_str() {
+			"2" => return ColorSupport::Ansi256,
+			"3" => return ColorSupport::TrueColor,
+			"" | "0" => {}
+			_ => return ColorSupport::Basic,
+		}
+	}
+
+	if env::var("COLORTERM").is_ok_and(|colorterm| colorterm == "truecolor" || colorterm == "24bit")
+	{
+		return ColorSupport::TrueColor;
+	}
+
+	if env::var_os("WT_SESSION").is_some() {
+		return ColorSupport::TrueColor;
+	}
+
+	if let Ok(program) = env::var("TERM_PROGRAM") {
+		match program.as_str() {
+			"iTerm.app" | "WezTerm" | "vscode" | "ghostty" => return ColorSupport::TrueColor,
+			"Apple_Terminal" => return ColorSupport::Ansi256,
+			_ => {}
+		}
+	}
+
+	let term = env::var("TERM").unwrap_or_default();
+	if term.ends_with("-direct") || term.contains("truecolor") {
+		ColorSupport::TrueColor
+	} else if term.contains("256") {
+		ColorSupport::Ansi256
+	} else {
+		ColorSupport::Basic
+	}
+}
+
+/// Guess which colors the terminal supports.
+///
+/// The guess is based on `COLORTERM`, `TERM` and `TERM_PROGRAM`.
+/// Set `FORCE_COLOR` to `1`, `2` or `3` to override it with 16, 256 or RGB colors.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::style::{color_support, ColorSupport};
+///
+/// if color_support() == ColorSupport::TrueColor {
+///     println!("all the colors");
+/// }
+/// ```
+pub fn color_support() -> ColorSupport {
+	*COLOR_SUPPORT
+}
+
+/// `Color` struct
+///
+/// A color with a fallback for every tier of [`ColorSupport`],
+/// so custom RGB accents degrade gracefully on terminals with fewer colors.
+///
+/// The fallbacks are the closest colors of the palette of 256 colors and of the 16 basic colors,
+/// unless they are picked by hand.
+///
+/// # Examples
+///
+/// ```no_run
+/// use may_clack::{select, select::Opt, style::Color};
+/// use owo_colors::{AnsiColors, Style};
+///
+/// # fn main() -> Result<(), may_clack::error::ClackError> {
+/// let coral = Color::rgb(255, 127, 80).basic(AnsiColors::Red);
+/// let opts = vec![
+///     Opt::simple("go", "continue"),
+///     Opt::simple("rm", "delete").style(Style::new().color(coral.resolve())),
+/// ];
+///
+/// let answer = select("next").options(opts).interact()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+	rgb: (u8, u8, u8),
+	ansi256: u8,
+	basic: AnsiColors,
+}
+
+impl Color {
+	/// The RGB color, with the closest colors of the other palettes as fallbacks.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::style::{Color, ColorSupport};
+	/// use owo_colors::{AnsiColors, DynColors};
+	///
+	/// let coral = Color::rgb(255, 127, 80);
+	/// assert_eq!(coral.tier(ColorSupport::TrueColor), DynColors::Rgb(255, 127, 80));
+	/// assert_eq!(coral.tier(ColorSupport::Ansi256), DynColors::Xterm(209.into()));
+	/// assert_eq!(coral.tier(ColorSupport::Basic), DynColors::Ansi(AnsiColors::BrightRed));
+	/// ```
+	pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+		Color {
+			rgb: (r, g, b),
+			ansi256: rgb_to_ansi256(r, g, b),
+			basic: rgb_to_basic(r, g, b),
+		}
+	}
+
+	/// Use the `color` of the palette of 256 colors on terminals without RGB colors.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::style::Color;
+	///
+	/// let coral = Color::rgb(255, 127, 80).ansi256(203);
+	/// ```
+	pub fn ansi256(mut self, color: u8) -> Self {
+		self.ansi256 = color;
+		self
+	}
+
+	/// Use the `color` on terminals with only the 16 basic colors.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use may_clack::style::Color;
+	/// use owo_colors::AnsiColors;
+	///
+	/// let coral = Color::rgb(255, 127, 80).basic(AnsiColors::Red);
+	/// ```
+	pub fn basic(mut self, color: AnsiColors) -> Self {
+		self.basic = color;
+		self
+	}
+
+	/// The color for the tier of colors the terminal supports, see [`color_support()`].
+	pub fn resolve(&self) -> DynColors {
+		self.tier(color_support())
+	}
+
+	/// The color for the `tier` of colors.
+	pub fn tier(&self, tier: ColorSupport) -> DynColors {
+		match tier {
+			ColorSupport::TrueColor => DynColors::Rgb(self.rgb.0, self.rgb.1, self.rgb.2),
+			ColorSupport::Ansi256 => DynColors::Xterm(XtermColors::from(self.ansi256)),
+			ColorSupport::Basic => DynColors::Ansi(self.basic),
+		}
+	}
+}
+
+/// The closest color of the palette of 256 colors, from the color cube or the grayscale ramp.
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+	if r == g && g == b {
+		return match r {
+			0..8 => 16,
+			249.. => 231,
+			_ => 232 + ((r as u16 - 8) * 24 / 247) as u8,
+		};
+	}
+
+	// the levels of the cube are 0, 95, 135, 175, 215 and 255
+	let cube = |c: u8| match c {
+		0..48 => 0,
+		48..115 => 1,
+		_ => (c - 35) / 40,
+	};
+	16 + 36 * cube(r) + 6 * cube(g) + cube(b)
+}
+
+/// The closest of the 16 basic colors.
+fn rgb_to_basic(r: u8, g: u8, b: u8) -> AnsiColors {
+	const COLORS: [AnsiColors; 8] = [
+		AnsiColors::Black,
+		AnsiColors::Red,
+		AnsiColors::Green,
+		AnsiColors::Yellow,
+		AnsiColors::Blue,
+		AnsiColors::Magenta,
+		AnsiColors::Cyan,
+		AnsiColors::White,
+	];
+	const BRIGHT: [AnsiColors; 8] = [
+		AnsiColors::BrightBlack,
+		AnsiColors::BrightRed,
+		AnsiColors::BrightGreen,
+		AnsiColors::BrightYellow,
+		AnsiColors::BrightBlue,
+		AnsiColors::BrightMagenta,
+		AnsiColors::BrightCyan,
+		AnsiColors::BrightWhite,
+	];
+
+	let on = |c: u8| usize::from(c >= 128);
+	let idx = on(b) << 2 | on(g) << 1 | on(r);
+
+	match (r.max(g).max(b) as u16 * 2 + 127) / 255 {
+		0 => AnsiColors::Black,
+		1 => COLORS[idx],
+		_ => BRIGHT[idx],
+	}
+}
+
+/// Style of the title of an [`Intro`](crate::intro::Intro) or [`Outro`](crate::outro::Outro).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BadgeStyle {
+	/// Reverse video badge, with a space on either side of the title.
+	#[default]
+	Reversed,
+	/// Black badge on a colored background, with a space on either side of the title.
+	Color(AnsiColors),
+	/// Black badge on a background of a [`Color`] for the tier of colors the terminal supports,
+	/// with a space on either side of the title.
+	Accent(Color),
+	/// Bold title without a badge.
+	Bold,
+	/// Plain title without a badge.
+	Plain,
+}
+
+impl BadgeStyle {
+	pub(crate) fn badge<T: Display>(&self, title: T) -> String {
+		match self {
+			BadgeStyle::Reversed => format!(" {} ", title).reversed().to_string(),
+			BadgeStyle::Color(color) => format!(" {} ", title).black().on_color(*color).to_string(),
+			BadgeStyle::Accent(color) => format!(" {} ", title)
+				.black()
+				.on_color(color.resolve())
+				.to_string(),
+			BadgeStyle::Bold => title.bold().to_string(),
+			BadgeStyle::Plain => title.to_string(),
+		}
+	}
+}
+
+/// Whether the ascii chars are used, even if the terminal supports unicode.
+static FORCE_ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Use the ascii chars, or the detected chars again.
+pub(crate) fn set_ascii(ascii: bool) {
+	FORCE_ASCII.store(ascii, Ordering::Relaxed);
+}
+
+/// Whether the unicode chars are used.
+///
+/// Set `MAY_CLACK_ASCII` to `1` to always use the ascii chars.
+pub(crate) fn is_unicode() -> bool {
+	!FORCE_ASCII.load(Ordering::Relaxed) && *IS_UNICODE
+}
+
+/// `Glyph` struct
+///
+/// A char of the prompts, that dereferences to its unicode or its ascii version,
+/// depending on whether the unicode chars are used.
+///
+/// # Examples
+///
+/// ```
+/// use may_clack::style::chars;
+///
+/// let bar: &str = *chars::BAR;
+/// assert!(bar == "│" || bar == "|");
+/// ```
+#[derive(Debug)]
+pub struct Glyph {
+	unicode: &'static str,
+	ascii: &'static str,
+}
+
+impl Glyph {
+	const fn new(unicode: &'static str, ascii: &'static str) -> Self {
+		Glyph { unicode, ascii }
+	}
+}
+
+impl Deref for Glyph {
+	type Target = &'static str;
+
+	fn deref(&self) -> &Self::Target {
+		if is_unicode() {
+			&self.unicode
+		} else {
+			&self.ascii
+		}
 	}
 }
 
 /// Clack prompt chars.
 ///
-/// Changes if the terminal supports unicode.
+/// Changes if the terminal supports unicode,
+/// the ascii chars are also used in the legacy Windows console,
+/// with [`force_ascii()`](crate::force_ascii) or if `MAY_CLACK_ASCII` is set to `1`.
 pub mod chars {
-	use super::is_unicode;
-	use once_cell::sync::Lazy;
+	use super::Glyph;
 
 	/// Straight left bar
-	pub static BAR: Lazy<&str> = Lazy::new(|| is_unicode("│", "|"));
+	pub static BAR: Glyph = Glyph::new("│", "|");
 	/// Start bar
-	pub static BAR_START: Lazy<&str> = Lazy::new(|| is_unicode("┌", "T"));
+	pub static BAR_START: Glyph = Glyph::new("┌", "T");
 	/// End bar
-	pub static BAR_END: Lazy<&str> = Lazy::new(|| is_unicode("└", "—"));
+	pub static BAR_END: Glyph = Glyph::new("└", "—");
 	/// Active step
-	pub static STEP_ACTIVE: Lazy<&str> = Lazy::new(|| is_unicode("◆", "*"));
+	pub static STEP_ACTIVE: Glyph = Glyph::new("◆", "*");
 	/// Cancelled step
-	pub static STEP_CANCEL: Lazy<&str> = Lazy::new(|| is_unicode("■", "x"));
+	pub static STEP_CANCEL: Glyph = Glyph::new("■", "x");
 	/// Error step
-	pub static STEP_ERROR: Lazy<&str> = Lazy::new(|| is_unicode("▲", "x"));
+	pub static STEP_ERROR: Glyph = Glyph::new("▲", "x");
 	/// Submitted step
-	pub static STEP_SUBMIT: Lazy<&str> = Lazy::new(|| is_unicode("◇", "o"));
+	pub static STEP_SUBMIT: Glyph = Glyph::new("◇", "o");
+	/// Pending step
+	pub static STEP_PENDING: Glyph = Glyph::new("○", ".");
 	/// Active radio
-	pub static RADIO_ACTIVE: Lazy<&str> = Lazy::new(|| is_unicode("●", ">"));
+	pub static RADIO_ACTIVE: Glyph = Glyph::new("●", ">");
 	/// Inactive radio
-	pub static RADIO_INACTIVE: Lazy<&str> = Lazy::new(|| is_unicode("○", " "));
+	pub static RADIO_INACTIVE: Glyph = Glyph::new("○", " ");
 	/// Active checkbox
-	pub static CHECKBOX_ACTIVE: Lazy<&str> = Lazy::new(|| is_unicode("◻", "[.]"));
+	pub static CHECKBOX_ACTIVE: Glyph = Glyph::new("◻", "[.]");
 	/// Selected checkbox
-	pub static CHECKBOX_SELECTED: Lazy<&str> = Lazy::new(|| is_unicode("◼", "[+]"));
+	pub static CHECKBOX_SELECTED: Glyph = Glyph::new("◼", "[+]");
 	/// Inactive checkbox
-	pub static CHECKBOX_INACTIVE: Lazy<&str> = Lazy::new(|| is_unicode("◻", "[ ]"));
+	pub static CHECKBOX_INACTIVE: Glyph = Glyph::new("◻", "[ ]");
+	/// Masked char of a password
+	pub static MASK: Glyph = Glyph::new("•", "*");
+	/// Segment of a meter
+	pub static METER: Glyph = Glyph::new("━", "=");
+	/// Followed rule
+	pub static CHECK: Glyph = Glyph::new("✔", "+");
+	/// Broken rule
+	pub static CROSS: Glyph = Glyph::new("✖", "-");
+	/// Ellipsis
+	pub static ELLIPSIS: Glyph = Glyph::new("…", "...");
+	/// Separator line
+	pub static SEPARATOR: Glyph = Glyph::new("─", "-");
+	/// Up and down arrow keys
+	pub static ARROWS_UP_DOWN: Glyph = Glyph::new("↑/↓", "up/down");
+	/// Left and right arrow keys
+	pub static ARROWS_LEFT_RIGHT: Glyph = Glyph::new("←/→", "left/right");
 }
 
 /// ANSI escape codes
 pub mod ansi {
 	/// ANSI escape code to clear the line
+	#[deprecated(note = "use `crossterm::terminal::Clear(ClearType::CurrentLine)` instead")]
 	pub const CLEAR_LINE: &str = "\x1b[2K";
+	/// Start of an OSC 8 hyperlink, followed by the url and a string terminator
+	pub const LINK_START: &str = "\x1b]8;;";
+	/// End of an OSC 8 hyperlink
+	pub const LINK_END: &str = "\x1b]8;;\x1b\\";
 }